@@ -92,7 +92,7 @@ pub trait Api {
   #[rpc(name = "sendtoaddress")]
   fn send_to_address(
     &self,
-    address: Address<NetworkUnchecked>,
+    address: String,
     amount: f64,
     comment: Option<String>,
     comment_to: Option<String>,
@@ -151,6 +151,13 @@ pub trait Api {
     req: Vec<ImportDescriptors>,
   ) -> Result<Vec<ImportMultiResult>, jsonrpc_core::Error>;
 
+  #[rpc(name = "importmulti")]
+  fn import_multi(
+    &self,
+    requests: Vec<ImportMultiRequest>,
+    options: Option<Value>,
+  ) -> Result<Vec<ImportMultiResult>, jsonrpc_core::Error>;
+
   #[rpc(name = "getnewaddress")]
   fn get_new_address(
     &self,
@@ -194,7 +201,7 @@ pub trait Api {
     &self,
     psbt: String,
     sign: Option<bool>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
     bip32derivs: Option<bool>,
   ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error>;
 
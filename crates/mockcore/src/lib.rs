@@ -164,6 +164,18 @@ pub struct FundRawTransactionOptions {
   change_position: Option<u32>,
 }
 
+#[derive(Deserialize)]
+pub struct ImportMultiRequest {
+  #[serde(rename = "scriptPubKey")]
+  script_pubkey: ImportMultiRequestScriptPubkey,
+  internal: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct ImportMultiRequestScriptPubkey {
+  address: Address<NetworkUnchecked>,
+}
+
 #[derive(Deserialize, Clone, PartialEq, Eq, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FundRawTransactionResult {
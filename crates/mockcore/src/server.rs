@@ -286,7 +286,8 @@ impl Api for Server {
   }
 
   fn get_wallet_info(&self) -> Result<GetWalletInfoResult, jsonrpc_core::Error> {
-    if let Some(wallet_name) = self.state().loaded_wallets.first().cloned() {
+    let state = self.state();
+    if let Some(wallet_name) = state.loaded_wallets.first().cloned() {
       Ok(GetWalletInfoResult {
         avoid_reuse: None,
         balance: Amount::from_sat(0),
@@ -296,7 +297,7 @@ impl Api for Server {
         keypool_size: 0,
         keypool_size_hd_internal: 0,
         pay_tx_fee: Amount::from_sat(0),
-        private_keys_enabled: true,
+        private_keys_enabled: !state.watch_only_wallets.contains(&wallet_name),
         scanning: None,
         tx_count: 0,
         unconfirmed_balance: Amount::from_sat(0),
@@ -346,12 +347,16 @@ impl Api for Server {
   fn create_wallet(
     &self,
     name: String,
-    _disable_private_keys: Option<bool>,
+    disable_private_keys: Option<bool>,
     _blank: Option<bool>,
     _passphrase: Option<String>,
     _avoid_reuse: Option<bool>,
   ) -> Result<LoadWalletResult, jsonrpc_core::Error> {
-    self.state().wallets.insert(name.clone());
+    let mut state = self.state();
+    state.wallets.insert(name.clone());
+    if disable_private_keys.unwrap_or_default() {
+      state.watch_only_wallets.insert(name.clone());
+    }
     Ok(LoadWalletResult {
       name,
       warning: None,
@@ -537,7 +542,7 @@ impl Api for Server {
 
   fn send_to_address(
     &self,
-    address: Address<NetworkUnchecked>,
+    address: String,
     amount: f64,
     comment: Option<String>,
     comment_to: Option<String>,
@@ -563,6 +568,12 @@ impl Api for Server {
 
     let value = Amount::from_btc(amount).expect("error converting amount to sat");
 
+    // MWEB addresses have no script pubkey, and are paid with a peg-in output
+    let script_pubkey = match address.parse::<Address<NetworkUnchecked>>() {
+      Ok(address) => address.payload.script_pubkey(),
+      Err(_) => ScriptBuf::from_bytes([[0x59, 0x20].as_slice(), &[0; 32]].concat()),
+    };
+
     let (outpoint, utxo_amount) = match state
       .utxos
       .iter()
@@ -584,11 +595,11 @@ impl Api for Server {
       output: vec![
         TxOut {
           value: value.to_sat(),
-          script_pubkey: address.payload.script_pubkey(),
+          script_pubkey: script_pubkey.clone(),
         },
         TxOut {
           value: (*utxo_amount - value).to_sat(),
-          script_pubkey: address.payload.script_pubkey(),
+          script_pubkey,
         },
       ],
     };
@@ -711,7 +722,11 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match state.transactions.get(&txid) {
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
@@ -821,6 +836,35 @@ impl Api for Server {
     }])
   }
 
+  fn import_multi(
+    &self,
+    requests: Vec<ImportMultiRequest>,
+    _options: Option<Value>,
+  ) -> Result<Vec<ImportMultiResult>, jsonrpc_core::Error> {
+    let mut state = self.state();
+
+    Ok(
+      requests
+        .into_iter()
+        .map(|request| {
+          let address = request.script_pubkey.address.assume_checked();
+
+          if request.internal.unwrap_or_default() {
+            state.change_addresses.push(address);
+          } else {
+            state.receive_addresses.push(address);
+          }
+
+          ImportMultiResult {
+            success: true,
+            warnings: Vec::new(),
+            error: None,
+          }
+        })
+        .collect(),
+    )
+  }
+
   fn get_new_address(
     &self,
     _label: Option<String>,
@@ -956,12 +1000,25 @@ impl Api for Server {
     &self,
     psbt: String,
     sign: Option<bool>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
     bip32derivs: Option<bool>,
   ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error> {
-    assert!(sighash_type.is_none());
     assert!(bip32derivs.is_none());
 
+    let mut signature = vec![0; 64];
+
+    if let Some(sighash_type) = sighash_type {
+      signature.push(match sighash_type.as_str() {
+        "ALL" => 0x01,
+        "NONE" => 0x02,
+        "SINGLE" => 0x03,
+        "ALL|ANYONECANPAY" => 0x81,
+        "NONE|ANYONECANPAY" => 0x82,
+        "SINGLE|ANYONECANPAY" => 0x83,
+        _ => panic!("unknown sighash type {sighash_type}"),
+      });
+    }
+
     let mut psbt = Psbt::deserialize(
       &base64::engine::general_purpose::STANDARD
         .decode(psbt)
@@ -984,7 +1041,7 @@ impl Api for Server {
     if let Some(sign) = sign {
       if sign {
         for input in psbt.inputs.iter_mut() {
-          input.final_script_witness = Some(Witness::from_slice(&[&[0; 64]]));
+          input.final_script_witness = Some(Witness::from_slice(&[&signature]));
         }
       }
     }
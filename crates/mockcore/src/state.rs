@@ -22,6 +22,7 @@ pub struct State {
   pub txid_to_block_height: BTreeMap<Txid, u32>,
   pub utxos: BTreeMap<OutPoint, Amount>,
   pub version: usize,
+  pub watch_only_wallets: BTreeSet<String>,
   pub receive_addresses: Vec<Address>,
   pub change_addresses: Vec<Address>,
   pub wallets: BTreeSet<String>,
@@ -54,6 +55,7 @@ impl State {
       utxos: BTreeMap::new(),
       version,
       wallets: BTreeSet::new(),
+      watch_only_wallets: BTreeSet::new(),
     }
   }
 
@@ -11,6 +11,12 @@ pub use crate::{
   },
 };
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Assets {
+  pub inscriptions: BTreeMap<InscriptionId, u64>,
+  pub runes: BTreeMap<SpacedRune, RuneBalance>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Block {
   pub best_height: u32,
@@ -75,6 +81,42 @@ pub struct BlockInfo {
   pub version: u32,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Collection {
+  pub children: u64,
+  pub content_types: Vec<CollectionContentType>,
+  pub holders: Option<u64>,
+  pub id: InscriptionId,
+  pub mints: Vec<CollectionMints>,
+  pub number: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionContentType {
+  pub children: u64,
+  pub content_type: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionHolder {
+  pub address: Option<String>,
+  pub children: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionHolders {
+  pub holders: Vec<CollectionHolder>,
+  pub id: InscriptionId,
+  pub more: bool,
+  pub page: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionMints {
+  pub children: u64,
+  pub height: u32,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Children {
   pub ids: Vec<InscriptionId>,
@@ -111,6 +153,14 @@ pub struct Inscription {
   pub value: Option<u64>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionHistory {
+  pub id: InscriptionId,
+  pub more: bool,
+  pub page: usize,
+  pub transfers: Vec<InscriptionTransfer>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InscriptionRecursive {
   pub charms: Vec<Charm>,
@@ -142,6 +192,24 @@ pub struct ChildInscriptionRecursive {
   pub timestamp: i64,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionTransfer {
+  pub address: Option<String>,
+  pub height: u32,
+  pub new_satpoint: SatPoint,
+  pub old_satpoint: Option<SatPoint>,
+  pub timestamp: i64,
+  pub transaction: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionListing {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub next_cursor: Option<u32>,
+  pub page_index: u32,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Inscriptions {
   pub ids: Vec<InscriptionId>,
@@ -154,6 +222,7 @@ pub struct Output {
   pub address: Option<Address<NetworkUnchecked>>,
   pub indexed: bool,
   pub inscriptions: Vec<InscriptionId>,
+  pub mweb: bool,
   pub runes: BTreeMap<SpacedRune, Pile>,
   pub sat_ranges: Option<Vec<(u64, u64)>>,
   pub script_pubkey: ScriptBuf,
@@ -180,6 +249,7 @@ impl Output {
         .map(|address| uncheck(&address)),
       indexed,
       inscriptions,
+      mweb: mweb::is_mweb_script(&tx_out.script_pubkey),
       runes,
       sat_ranges,
       script_pubkey: tx_out.script_pubkey,
@@ -190,6 +260,82 @@ impl Output {
   }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneBalance {
+  pub id: RuneId,
+  pub amount: u128,
+  pub divisibility: u8,
+  pub symbol: Option<char>,
+}
+
+impl RuneBalance {
+  pub fn pile(self) -> Pile {
+    Pile {
+      amount: self.amount,
+      divisibility: self.divisibility,
+      symbol: self.symbol,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHistory {
+  pub divisibility: u8,
+  pub id: RuneId,
+  pub rune: SpacedRune,
+  pub stats: Vec<RuneStats>,
+  pub symbol: Option<char>,
+}
+
+impl RuneHistory {
+  pub fn pile(&self, amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: self.divisibility,
+      symbol: self.symbol,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolder {
+  pub address: Option<String>,
+  pub amount: u128,
+  pub outputs: Vec<OutPoint>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolders {
+  pub divisibility: u8,
+  pub height: u32,
+  pub holders: Vec<RuneHolder>,
+  pub id: RuneId,
+  pub more: bool,
+  pub page: usize,
+  pub rune: SpacedRune,
+  pub stats: Option<RuneStats>,
+  pub symbol: Option<char>,
+}
+
+impl RuneHolders {
+  pub fn pile(&self, amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: self.divisibility,
+      symbol: self.symbol,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneStats {
+  pub burned: u128,
+  pub height: u32,
+  pub holders: u64,
+  pub mints: u128,
+  pub supply: u128,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Sat {
   pub block: u32,
@@ -221,6 +367,62 @@ pub struct SatInscriptions {
   pub page: u64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Search {
+  pub cursor: Option<u32>,
+  pub inscriptions: Vec<InscriptionId>,
+  pub more: bool,
+  pub next_cursor: Option<u32>,
+  pub query: String,
+  pub runes: Vec<SpacedRune>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Simulation {
+  pub txid: Txid,
+  pub inputs: Vec<SimulatedInput>,
+  pub outputs: Vec<SimulatedOutput>,
+  pub runestone: Option<Artifact>,
+  pub default_output: Option<u32>,
+  pub burned: BTreeMap<SpacedRune, Pile>,
+  pub fee: u64,
+  pub fee_inscriptions: BTreeMap<InscriptionId, u64>,
+  pub fee_sat_ranges: Option<Vec<(u64, u64)>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedInput {
+  pub outpoint: OutPoint,
+  pub value: u64,
+  pub assets: Assets,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedOutput {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub value: u64,
+  pub assets: Assets,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressActivity {
+  pub height: u32,
+  pub inscriptions_received: Vec<InscriptionId>,
+  pub inscriptions_sent: Vec<InscriptionId>,
+  pub received: u64,
+  pub runes_received: BTreeMap<SpacedRune, Pile>,
+  pub runes_sent: BTreeMap<SpacedRune, Pile>,
+  pub sent: u64,
+  pub transaction: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressHistory {
+  pub activity: Vec<AddressActivity>,
+  pub more: bool,
+  pub page: usize,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct AddressInfo {
   pub outputs: Vec<OutPoint>,
@@ -1,12 +1,16 @@
 use {
   self::{
     entry::{
-      Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      ActivityEntry, ActivityEntryValue, ContentEntry, ContentEntryValue, Entry, HeaderValue,
+      InscriptionEntry, InscriptionEntryValue, InscriptionIdValue, OutPointValue,
+      RuneBalanceChange, RuneBalanceChangeValue, RuneEntryValue, RuneIdValue, RuneStatsEntry,
+      RuneStatsEntryValue, SatPointValue, SatRange, TransferEntry, TransferEntryValue, TxidValue,
     },
     event::Event,
     lot::Lot,
     reorg::Reorg,
+    storage::Storage,
+    undo_log::UndoLog,
     updater::Updater,
     utxo_entry::{ParsedUtxoEntry, UtxoEntry, UtxoEntryBuf},
   },
@@ -32,45 +36,75 @@ use {
   std::{
     collections::HashMap,
     io::{BufWriter, Write},
+    ops::{Bound, Range},
     sync::Once,
   },
 };
 
 pub use self::entry::RuneEntry;
 
+mod block_files;
+mod block_source;
 pub(crate) mod entry;
 pub mod event;
 mod fetcher;
+pub(crate) mod listing;
 mod lot;
 mod reorg;
 mod rtx;
+pub(crate) mod search;
+mod storage;
+mod undo_log;
 mod updater;
 mod utxo_entry;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 28;
+const SCHEMA_VERSION: u64 = 39;
 
+define_multimap_table! { RUNE_ID_TO_BALANCE_CHANGE, RuneIdValue, RuneBalanceChangeValue }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
+define_multimap_table! { SCRIPT_PUBKEY_TO_ACTIVITY_NUMBER, &[u8], u64 }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
+define_multimap_table! { SEQUENCE_NUMBER_TO_TRANSFER_NUMBER, u32, u64 }
 define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], OutPointValue }
+define_table! { ACTIVITY_NUMBER_TO_ACTIVITY, u64, ActivityEntryValue }
+define_table! { CONTENT_LENGTH_AND_SEQUENCE_NUMBER, (u64, u32), () }
+define_table! { EVENT_NUMBER_TO_EVENT, u64, &[u8] }
+define_table! { FEE_AND_SEQUENCE_NUMBER, (u64, u32), () }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
+define_table! { HEIGHT_TO_UNDO_LOG, u32, &[u8] }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_UTXO_ENTRY, &OutPointValue, &UtxoEntry }
+define_table! { RUNE_ID_AND_HEIGHT_TO_RUNE_STATS, (RuneIdValue, u32), RuneStatsEntryValue }
+define_table! { RUNE_ID_AND_SCRIPT_PUBKEY_TO_BALANCE, (RuneIdValue, &[u8]), u128 }
+define_table! { RUNE_ID_BALANCE_AND_OUTPOINT, (RuneIdValue, u128, OutPointValue), () }
+define_table! { RUNE_ID_BALANCE_AND_SCRIPT_PUBKEY, (RuneIdValue, u128, &[u8]), () }
+define_table! { RUNE_ID_SCRIPT_PUBKEY_AND_HEIGHT_TO_BALANCE, (RuneIdValue, &[u8], u32), u128 }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SEARCH_TERM_AND_RUNE_ID, (&str, RuneIdValue), () }
+define_table! { SEARCH_TERM_AND_SEQUENCE_NUMBER, (&str, u32), () }
+define_table! { SEQUENCE_NUMBER_AND_CONTENT_TYPE_TO_CHILDREN, (u32, &str), u64 }
+define_table! { SEQUENCE_NUMBER_AND_HEIGHT_TO_CHILDREN, (u32, u32), u64 }
+define_table! { SEQUENCE_NUMBER_AND_SCRIPT_PUBKEY_TO_CHILDREN, (u32, &[u8]), u64 }
+define_table! { SEQUENCE_NUMBER_CHILDREN_AND_SCRIPT_PUBKEY, (u32, u64, &[u8]), () }
+define_table! { SEQUENCE_NUMBER_TO_COLLECTION_STATS, u32, (u64, u64) }
+define_table! { SEQUENCE_NUMBER_TO_CONTENT_ENTRY, u32, ContentEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
+define_table! { TRANSFER_NUMBER_TO_TRANSFER, u64, TransferEntryValue }
+define_table! { WEBHOOK_TO_EVENT_NUMBER, &str, u64 }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
 
 #[derive(Copy, Clone)]
@@ -91,6 +125,12 @@ pub(crate) enum Statistic {
   Runes = 13,
   SatRanges = 14,
   UnboundInscriptions = 16,
+  MwebSats = 17,
+  IndexAddressHistory = 18,
+  IndexInscriptionHistory = 19,
+  IndexRuneHistory = 20,
+  IndexSearch = 21,
+  IndexInscriptionDetails = 22,
 }
 
 impl Statistic {
@@ -192,16 +232,21 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 
 pub struct Index {
   pub(crate) client: Client,
-  database: Database,
+  database: Storage,
   durability: redb::Durability,
   event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_address_history: bool,
   index_addresses: bool,
+  index_inscription_details: bool,
+  index_inscription_history: bool,
   index_inscriptions: bool,
+  index_rune_history: bool,
   index_runes: bool,
   index_sats: bool,
+  index_search: bool,
   index_transactions: bool,
   path: PathBuf,
   settings: Settings,
@@ -261,168 +306,217 @@ impl Index {
       }
     };
 
-    let database = match Database::builder()
-      .set_cache_size(index_cache_size)
-      .set_repair_callback(repair_callback)
-      .open(&path)
-    {
-      Ok(database) => {
+    let database = if settings.snapshot_reload() {
+      let database = Storage::open_read_only(&path, index_cache_size)
+        .with_context(|| format!("failed to open index snapshot `{}`", path.display()))?;
+      Self::check_schema(&database, &path)?;
+      database
+    } else {
+      match Database::builder()
+        .set_cache_size(index_cache_size)
+        .set_repair_callback(repair_callback)
+        .open(&path)
+      {
+        Ok(database) => {
+          Self::check_schema(&database, &path)?;
+          database
+        }
+        Err(DatabaseError::Storage(StorageError::Io(error)))
+          if error.kind() == io::ErrorKind::NotFound =>
         {
-          let schema_version = database
-            .begin_read()?
-            .open_table(STATISTIC_TO_COUNT)?
-            .get(&Statistic::Schema.key())?
-            .map(|x| x.value())
-            .unwrap_or(0);
-
-          match schema_version.cmp(&SCHEMA_VERSION) {
-            cmp::Ordering::Less =>
-              bail!(
-                "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
-            cmp::Ordering::Greater =>
-              bail!(
-                "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
-            cmp::Ordering::Equal => {
-            }
+          let database = Database::builder()
+            .set_cache_size(index_cache_size)
+            .create(&path)?;
+
+          let mut tx = database.begin_write()?;
+
+          tx.set_durability(durability);
+
+          tx.open_multimap_table(RUNE_ID_TO_BALANCE_CHANGE)?;
+          tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+          tx.open_multimap_table(SCRIPT_PUBKEY_TO_ACTIVITY_NUMBER)?;
+          tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+          tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+          tx.open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFER_NUMBER)?;
+          tx.open_table(ACTIVITY_NUMBER_TO_ACTIVITY)?;
+          tx.open_table(CONTENT_LENGTH_AND_SEQUENCE_NUMBER)?;
+          tx.open_table(EVENT_NUMBER_TO_EVENT)?;
+          tx.open_table(FEE_AND_SEQUENCE_NUMBER)?;
+          tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+          tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+          tx.open_table(HEIGHT_TO_UNDO_LOG)?;
+          tx.open_table(HOME_INSCRIPTIONS)?;
+          tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+          tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+          tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+          tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+          tx.open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_STATS)?;
+          tx.open_table(RUNE_ID_AND_SCRIPT_PUBKEY_TO_BALANCE)?;
+          tx.open_table(RUNE_ID_BALANCE_AND_OUTPOINT)?;
+          tx.open_table(RUNE_ID_BALANCE_AND_SCRIPT_PUBKEY)?;
+          tx.open_table(RUNE_ID_SCRIPT_PUBKEY_AND_HEIGHT_TO_BALANCE)?;
+          tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+          tx.open_table(RUNE_TO_RUNE_ID)?;
+          tx.open_table(SAT_TO_SATPOINT)?;
+          tx.open_table(SEARCH_TERM_AND_RUNE_ID)?;
+          tx.open_table(SEARCH_TERM_AND_SEQUENCE_NUMBER)?;
+          tx.open_table(SEQUENCE_NUMBER_AND_CONTENT_TYPE_TO_CHILDREN)?;
+          tx.open_table(SEQUENCE_NUMBER_AND_HEIGHT_TO_CHILDREN)?;
+          tx.open_table(SEQUENCE_NUMBER_AND_SCRIPT_PUBKEY_TO_CHILDREN)?;
+          tx.open_table(SEQUENCE_NUMBER_CHILDREN_AND_SCRIPT_PUBKEY)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_COLLECTION_STATS)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_CONTENT_ENTRY)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+          tx.open_table(TRANSACTION_ID_TO_RUNE)?;
+          tx.open_table(TRANSFER_NUMBER_TO_TRANSFER)?;
+          tx.open_table(WEBHOOK_TO_EVENT_NUMBER)?;
+          tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
+
+          {
+            let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexAddressHistory,
+              u64::from(settings.index_address_history_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexAddresses,
+              u64::from(settings.index_addresses_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexInscriptionDetails,
+              u64::from(settings.index_inscription_details_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexInscriptionHistory,
+              u64::from(settings.index_inscription_history_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexInscriptions,
+              u64::from(settings.index_inscriptions_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexRuneHistory,
+              u64::from(settings.index_rune_history_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexRunes,
+              u64::from(settings.index_runes_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexSats,
+              u64::from(settings.index_sats_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexSearch,
+              u64::from(settings.index_search_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexTransactions,
+              u64::from(settings.index_transactions_raw()),
+            )?;
+
+            Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
           }
-        }
-
-        database
-      }
-      Err(DatabaseError::Storage(StorageError::Io(error)))
-        if error.kind() == io::ErrorKind::NotFound =>
-      {
-        let database = Database::builder()
-          .set_cache_size(index_cache_size)
-          .create(&path)?;
-
-        let mut tx = database.begin_write()?;
-
-        tx.set_durability(durability);
-
-        tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
-        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
-        tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
-        tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
-        tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
-        tx.open_table(HOME_INSCRIPTIONS)?;
-        tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
-        tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
-        tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-        tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
-        tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-        tx.open_table(RUNE_TO_RUNE_ID)?;
-        tx.open_table(SAT_TO_SATPOINT)?;
-        tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-        tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
-        tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
-        tx.open_table(TRANSACTION_ID_TO_RUNE)?;
-        tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
-        {
-          let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexAddresses,
-            u64::from(settings.index_addresses_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexInscriptions,
-            u64::from(settings.index_inscriptions_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexRunes,
-            u64::from(settings.index_runes_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexSats,
-            u64::from(settings.index_sats_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexTransactions,
-            u64::from(settings.index_transactions_raw()),
-          )?;
-
-          Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
-        }
+          if settings.index_runes_raw() && settings.chain() == Chain::Mainnet {
+            let rune = Rune(2055900680524219742);
+
+            let id = RuneId { block: 1, tx: 0 };
+            let etching = Txid::all_zeros();
+
+            tx.open_table(RUNE_TO_RUNE_ID)?
+              .insert(rune.store(), id.store())?;
+
+            let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+
+            Self::set_statistic(&mut statistics, Statistic::Runes, 1)?;
+
+            tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.insert(
+              id.store(),
+              RuneEntry {
+                block: id.block,
+                burned: 0,
+                divisibility: 0,
+                etching,
+                terms: Some(Terms {
+                  amount: Some(1),
+                  cap: Some(u128::MAX),
+                  height: (
+                    Some(2675600),
+                    Some((2675600 + SUBSIDY_HALVING_INTERVAL).into()),
+                  ),
+                  offset: (None, None),
+                }),
+                mints: 0,
+                number: 0,
+                premine: 0,
+                spaced_rune: SpacedRune { rune, spacers: 128 },
+                symbol: Some('\u{29C9}'),
+                timestamp: 0,
+                turbo: true,
+              }
+              .store(),
+            )?;
+
+            tx.open_table(TRANSACTION_ID_TO_RUNE)?
+              .insert(&etching.store(), rune.store())?;
+          }
 
-        if settings.index_runes_raw() && settings.chain() == Chain::Mainnet {
-          let rune = Rune(2055900680524219742);
-
-          let id = RuneId { block: 1, tx: 0 };
-          let etching = Txid::all_zeros();
-
-          tx.open_table(RUNE_TO_RUNE_ID)?
-            .insert(rune.store(), id.store())?;
-
-          let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
-
-          Self::set_statistic(&mut statistics, Statistic::Runes, 1)?;
-
-          tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.insert(
-            id.store(),
-            RuneEntry {
-              block: id.block,
-              burned: 0,
-              divisibility: 0,
-              etching,
-              terms: Some(Terms {
-                amount: Some(1),
-                cap: Some(u128::MAX),
-                height: (
-                  Some(2675600),
-                  Some((2675600 + SUBSIDY_HALVING_INTERVAL).into()),
-                ),
-                offset: (None, None),
-              }),
-              mints: 0,
-              number: 0,
-              premine: 0,
-              spaced_rune: SpacedRune { rune, spacers: 128 },
-              symbol: Some('\u{29C9}'),
-              timestamp: 0,
-              turbo: true,
-            }
-            .store(),
-          )?;
+          tx.commit()?;
 
-          tx.open_table(TRANSACTION_ID_TO_RUNE)?
-            .insert(&etching.store(), rune.store())?;
+          database
         }
-
-        tx.commit()?;
-
-        database
+        Err(error) => bail!("failed to open index: {error}"),
       }
-      Err(error) => bail!("failed to open index: {error}"),
     };
 
+    let index_address_history;
     let index_addresses;
+    let index_inscription_details;
+    let index_inscription_history;
+    let index_rune_history;
     let index_runes;
     let index_sats;
+    let index_search;
     let index_transactions;
     let index_inscriptions;
 
     {
       let tx = database.begin_read()?;
       let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+      index_address_history = Self::is_statistic_set(&statistics, Statistic::IndexAddressHistory)?;
       index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+      index_inscription_details =
+        Self::is_statistic_set(&statistics, Statistic::IndexInscriptionDetails)?;
+      index_inscription_history =
+        Self::is_statistic_set(&statistics, Statistic::IndexInscriptionHistory)?;
       index_inscriptions = Self::is_statistic_set(&statistics, Statistic::IndexInscriptions)?;
+      index_rune_history = Self::is_statistic_set(&statistics, Statistic::IndexRuneHistory)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
+      index_search = Self::is_statistic_set(&statistics, Statistic::IndexSearch)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
     }
 
@@ -442,15 +536,25 @@ impl Index {
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
       client,
-      database,
+      database: Storage::new(
+        database,
+        path.clone(),
+        index_cache_size,
+        settings.snapshot_reload(),
+      )?,
       durability,
       event_sender,
       first_index_height,
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
+      index_address_history,
       index_addresses,
+      index_inscription_details,
+      index_inscription_history,
+      index_rune_history,
       index_runes,
       index_sats,
+      index_search,
       index_transactions,
       index_inscriptions,
       settings: settings.clone(),
@@ -460,6 +564,29 @@ impl Index {
     })
   }
 
+  fn check_schema(database: &Database, path: &Path) -> Result {
+    let schema_version = database
+      .begin_read()?
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Schema.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    match schema_version.cmp(&SCHEMA_VERSION) {
+      cmp::Ordering::Less =>
+        bail!(
+          "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+          path.display()
+        ),
+      cmp::Ordering::Greater =>
+        bail!(
+          "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+          path.display()
+        ),
+      cmp::Ordering::Equal => Ok(()),
+    }
+  }
+
   pub fn have_full_utxo_index(&self) -> bool {
     self.first_index_height == 0
   }
@@ -489,14 +616,30 @@ impl Index {
     )
   }
 
+  pub fn has_address_history_index(&self) -> bool {
+    self.index_address_history
+  }
+
   pub fn has_address_index(&self) -> bool {
     self.index_addresses
   }
 
+  pub fn has_inscription_details_index(&self) -> bool {
+    self.index_inscription_details
+  }
+
+  pub fn has_inscription_history_index(&self) -> bool {
+    self.index_inscription_history
+  }
+
   pub fn has_inscription_index(&self) -> bool {
     self.index_inscriptions
   }
 
+  pub fn has_rune_history_index(&self) -> bool {
+    self.index_rune_history
+  }
+
   pub fn has_rune_index(&self) -> bool {
     self.index_runes
   }
@@ -505,6 +648,10 @@ impl Index {
     self.index_sats
   }
 
+  pub fn has_search_index(&self) -> bool {
+    self.index_search
+  }
+
   pub fn status(&self) -> Result<StatusHtml> {
     let rtx = self.database.begin_read()?;
 
@@ -533,22 +680,28 @@ impl Index {
     let initial_sync_time = statistic(Statistic::InitialSyncTime)?;
 
     Ok(StatusHtml {
+      address_history_index: self.has_address_history_index(),
       address_index: self.has_address_index(),
       blessed_inscriptions,
       chain: self.settings.chain(),
       cursed_inscriptions,
       height,
       initial_sync_time: Duration::from_micros(initial_sync_time),
+      inscription_details_index: self.has_inscription_details_index(),
+      inscription_history_index: self.has_inscription_history_index(),
       inscription_index: self.has_inscription_index(),
       inscriptions: blessed_inscriptions + cursed_inscriptions,
       lost_sats: statistic(Statistic::LostSats)?,
+      mweb_sats: statistic(Statistic::MwebSats)?,
       minimum_rune_for_next_block: Rune::minimum_at_height(
         self.settings.chain().network(),
         Height(next_height),
       ),
+      rune_history_index: self.has_rune_history_index(),
       rune_index: self.has_rune_index(),
       runes: statistic(Statistic::Runes)?,
       sat_index: self.has_sat_index(),
+      search_index: self.has_search_index(),
       started: self.started,
       transaction_index: statistic(Statistic::IndexTransactions)? != 0,
       unrecoverably_reorged: self.unrecoverably_reorged.load(atomic::Ordering::Relaxed),
@@ -643,6 +796,15 @@ impl Index {
   }
 
   pub fn update(&self) -> Result {
+    self.update_to(self.height_limit)
+  }
+
+  fn update_to(&self, height_limit: Option<u32>) -> Result {
+    if self.database.reloads_snapshot() {
+      self.database.reload()?;
+      return Ok(());
+    }
+
     loop {
       let wtx = self.begin_write()?;
 
@@ -654,10 +816,12 @@ impl Index {
           .transpose()?
           .map(|(height, _header)| height.value() + 1)
           .unwrap_or(0),
+        height_limit,
         index: self,
         outputs_cached: 0,
         outputs_traversed: 0,
         sat_ranges_since_flush: 0,
+        undo_log: UndoLog::default(),
       };
 
       match updater.update_index(wtx) {
@@ -765,6 +929,441 @@ impl Index {
     Ok(())
   }
 
+  /// Call `f` with every inscription inscribed in a block in `heights`, in
+  /// sequence number order, along with its parents, current satpoint, and
+  /// content entry, which is only recorded by the inscription details index.
+  /// If `script_pubkeys` is true, the script pubkey of the output holding the
+  /// inscription is also passed, which requires the address index.
+  pub(crate) fn export_inscriptions(
+    &self,
+    heights: Range<u32>,
+    script_pubkeys: bool,
+    mut f: impl FnMut(
+      InscriptionEntry,
+      Vec<InscriptionId>,
+      SatPoint,
+      Option<ContentEntry>,
+      Option<ScriptBuf>,
+    ) -> Result,
+  ) -> Result {
+    ensure!(
+      !script_pubkeys || self.index_addresses,
+      "exporting addresses requires index created with `--index-addresses` flag",
+    );
+
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+    let sequence_number_to_content_entry = rtx.open_table(SEQUENCE_NUMBER_TO_CONTENT_ENTRY)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    // sequence numbers are assigned in block order, so inscriptions in range
+    // follow the last inscription of an earlier block
+    let start = rtx
+      .open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?
+      .range(..heights.start)?
+      .next_back()
+      .transpose()?
+      .map(|(_height, sequence_number)| sequence_number.value())
+      .unwrap_or(0);
+
+    for result in sequence_number_to_inscription_entry.range(start..)? {
+      let entry = InscriptionEntry::load(result?.1.value());
+
+      if entry.height < heights.start {
+        continue;
+      }
+
+      if entry.height >= heights.end || SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+
+      let parents = entry
+        .parents
+        .iter()
+        .map(|parent| {
+          Ok(
+            InscriptionEntry::load(
+              sequence_number_to_inscription_entry
+                .get(parent)?
+                .unwrap()
+                .value(),
+            )
+            .id,
+          )
+        })
+        .collect::<Result<Vec<InscriptionId>>>()?;
+
+      let satpoint = SatPoint::load(
+        *sequence_number_to_satpoint
+          .get(entry.sequence_number)?
+          .unwrap()
+          .value(),
+      );
+
+      let content = if self.index_inscription_details {
+        sequence_number_to_content_entry
+          .get(entry.sequence_number)?
+          .map(|content| ContentEntry::load(content.value()))
+      } else {
+        None
+      };
+
+      let script_pubkey = if script_pubkeys && satpoint.outpoint != unbound_outpoint() {
+        outpoint_to_utxo_entry
+          .get(&satpoint.outpoint.store())?
+          .map(|utxo_entry| {
+            ScriptBuf::from_bytes(utxo_entry.value().parse(self).script_pubkey().to_vec())
+          })
+      } else {
+        None
+      };
+
+      f(entry, parents, satpoint, content, script_pubkey)?;
+    }
+
+    Ok(())
+  }
+
+  /// Call `f` with every rune etched in a block in `heights`.
+  pub(crate) fn export_runes(
+    &self,
+    heights: Range<u32>,
+    mut f: impl FnMut(RuneId, RuneEntry) -> Result,
+  ) -> Result {
+    let rtx = self.database.begin_read()?;
+
+    for result in rtx
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+      .range((u64::from(heights.start), 0)..(u64::from(heights.end), 0))?
+    {
+      let (id, entry) = result?;
+      f(RuneId::load(id.value()), RuneEntry::load(entry.value()))?;
+    }
+
+    Ok(())
+  }
+
+  /// Call `f` with the rune balances of every output which received runes in
+  /// a block in `heights`, and was unspent at the end of the last block in
+  /// `heights`. Unless `heights` covers the whole index, this requires the
+  /// rune history index.
+  pub(crate) fn export_rune_balances(
+    &self,
+    heights: Range<u32>,
+    mut f: impl FnMut(OutPoint, Vec<(RuneId, u128)>) -> Result,
+  ) -> Result {
+    let rtx = self.database.begin_read()?;
+
+    let block_count = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    if heights.start > 0 || heights.end < block_count {
+      ensure!(
+        self.index_rune_history,
+        "exporting rune balances for a height range requires index created with `--index-rune-history` flag",
+      );
+
+      let mut outputs = BTreeMap::<OutPoint, Vec<(RuneId, u128)>>::new();
+
+      for result in rtx.open_multimap_table(RUNE_ID_TO_BALANCE_CHANGE)?.iter()? {
+        let (id, changes) = result?;
+        let id = RuneId::load(id.value());
+
+        let mut balances = HashMap::new();
+
+        // changes are ordered by height, and outputs are created before they
+        // are spent
+        for change in changes {
+          let change = RuneBalanceChange::load(change?.value());
+
+          if change.height >= heights.end {
+            break;
+          }
+
+          if change.spent {
+            balances.remove(&change.outpoint);
+          } else if change.height >= heights.start {
+            balances.insert(change.outpoint, change.amount);
+          }
+        }
+
+        for (outpoint, amount) in balances {
+          outputs.entry(outpoint).or_default().push((id, amount));
+        }
+
+        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+          return Ok(());
+        }
+      }
+
+      for (outpoint, balances) in outputs {
+        f(outpoint, balances)?;
+      }
+
+      return Ok(());
+    }
+
+    for result in rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+      let (outpoint, balances_buffer) = result?;
+      let balances_buffer = balances_buffer.value();
+
+      let mut balances = Vec::new();
+      let mut i = 0;
+      while i < balances_buffer.len() {
+        let (balance, length) = Index::decode_rune_balance(&balances_buffer[i..])?;
+        i += length;
+        balances.push(balance);
+      }
+
+      f(OutPoint::load(*outpoint.value()), balances)?;
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Call `f` with the height and header of every block in `heights`, along
+  /// with the number of inscriptions inscribed and runes etched in it.
+  pub(crate) fn export_blocks(
+    &self,
+    heights: Range<u32>,
+    mut f: impl FnMut(u32, Header, u32, u64) -> Result,
+  ) -> Result {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+
+    let mut runes = BTreeMap::<u64, u64>::new();
+
+    for result in rtx
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+      .range((u64::from(heights.start), 0)..(u64::from(heights.end), 0))?
+    {
+      *runes.entry(result?.0.value().0).or_default() += 1;
+    }
+
+    let mut next_sequence_number = height_to_last_sequence_number
+      .range(..heights.start)?
+      .next_back()
+      .transpose()?
+      .map(|(_height, sequence_number)| sequence_number.value())
+      .unwrap_or(0);
+
+    for result in rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?.range(heights)? {
+      let (height, header) = result?;
+      let height = height.value();
+
+      let last_sequence_number = height_to_last_sequence_number
+        .get(height)?
+        .map(|sequence_number| sequence_number.value())
+        .unwrap_or(next_sequence_number);
+
+      f(
+        height,
+        Header::load(*header.value()),
+        last_sequence_number - next_sequence_number,
+        runes.get(&u64::from(height)).copied().unwrap_or_default(),
+      )?;
+
+      next_sequence_number = last_sequence_number;
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Roll back the index so that the block at `height` is the last one
+  /// indexed.
+  pub fn roll_back(&self, height: u32) -> Result {
+    Reorg::roll_back_to_height(self, height)
+  }
+
+  /// Write a consistent copy of the index to `path`, which may be taken while
+  /// the index is being updated. Tables are copied in batches, each committed
+  /// separately, so that a large index doesn't have to fit in a single write
+  /// transaction. Returns the number of blocks in the snapshot.
+  pub fn snapshot(&self, path: &Path) -> Result<u32> {
+    let rtx = self.database.begin_read()?;
+
+    let block_count = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    log::info!(
+      "writing snapshot at block height {block_count} to {}",
+      path.display()
+    );
+
+    let partial = path.with_extension("partial");
+
+    if partial.exists() {
+      fs::remove_file(&partial)?;
+    }
+
+    {
+      let snapshot = Database::create(&partial)?;
+
+      Self::copy_multimap_table(&rtx, &snapshot, RUNE_ID_TO_BALANCE_CHANGE)?;
+      Self::copy_multimap_table(&rtx, &snapshot, SAT_TO_SEQUENCE_NUMBER)?;
+      Self::copy_multimap_table(&rtx, &snapshot, SCRIPT_PUBKEY_TO_ACTIVITY_NUMBER)?;
+      Self::copy_multimap_table(&rtx, &snapshot, SCRIPT_PUBKEY_TO_OUTPOINT)?;
+      Self::copy_multimap_table(&rtx, &snapshot, SEQUENCE_NUMBER_TO_CHILDREN)?;
+      Self::copy_multimap_table(&rtx, &snapshot, SEQUENCE_NUMBER_TO_TRANSFER_NUMBER)?;
+      Self::copy_table(&rtx, &snapshot, ACTIVITY_NUMBER_TO_ACTIVITY)?;
+      Self::copy_table(&rtx, &snapshot, CONTENT_LENGTH_AND_SEQUENCE_NUMBER)?;
+      Self::copy_table(&rtx, &snapshot, EVENT_NUMBER_TO_EVENT)?;
+      Self::copy_table(&rtx, &snapshot, FEE_AND_SEQUENCE_NUMBER)?;
+      Self::copy_table(&rtx, &snapshot, HEIGHT_TO_BLOCK_HEADER)?;
+      Self::copy_table(&rtx, &snapshot, HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+      Self::copy_table(&rtx, &snapshot, HEIGHT_TO_UNDO_LOG)?;
+      Self::copy_table(&rtx, &snapshot, HOME_INSCRIPTIONS)?;
+      Self::copy_table(&rtx, &snapshot, INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+      Self::copy_table(&rtx, &snapshot, INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+      Self::copy_table(&rtx, &snapshot, OUTPOINT_TO_RUNE_BALANCES)?;
+      Self::copy_table(&rtx, &snapshot, OUTPOINT_TO_UTXO_ENTRY)?;
+      Self::copy_table(&rtx, &snapshot, RUNE_ID_AND_HEIGHT_TO_RUNE_STATS)?;
+      Self::copy_table(&rtx, &snapshot, RUNE_ID_AND_SCRIPT_PUBKEY_TO_BALANCE)?;
+      Self::copy_table(&rtx, &snapshot, RUNE_ID_BALANCE_AND_OUTPOINT)?;
+      Self::copy_table(&rtx, &snapshot, RUNE_ID_BALANCE_AND_SCRIPT_PUBKEY)?;
+      Self::copy_table(&rtx, &snapshot, RUNE_ID_SCRIPT_PUBKEY_AND_HEIGHT_TO_BALANCE)?;
+      Self::copy_table(&rtx, &snapshot, RUNE_ID_TO_RUNE_ENTRY)?;
+      Self::copy_table(&rtx, &snapshot, RUNE_TO_RUNE_ID)?;
+      Self::copy_table(&rtx, &snapshot, SAT_TO_SATPOINT)?;
+      Self::copy_table(&rtx, &snapshot, SEARCH_TERM_AND_RUNE_ID)?;
+      Self::copy_table(&rtx, &snapshot, SEARCH_TERM_AND_SEQUENCE_NUMBER)?;
+      Self::copy_table(
+        &rtx,
+        &snapshot,
+        SEQUENCE_NUMBER_AND_CONTENT_TYPE_TO_CHILDREN,
+      )?;
+      Self::copy_table(&rtx, &snapshot, SEQUENCE_NUMBER_AND_HEIGHT_TO_CHILDREN)?;
+      Self::copy_table(
+        &rtx,
+        &snapshot,
+        SEQUENCE_NUMBER_AND_SCRIPT_PUBKEY_TO_CHILDREN,
+      )?;
+      Self::copy_table(&rtx, &snapshot, SEQUENCE_NUMBER_CHILDREN_AND_SCRIPT_PUBKEY)?;
+      Self::copy_table(&rtx, &snapshot, SEQUENCE_NUMBER_TO_COLLECTION_STATS)?;
+      Self::copy_table(&rtx, &snapshot, SEQUENCE_NUMBER_TO_CONTENT_ENTRY)?;
+      Self::copy_table(&rtx, &snapshot, SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+      Self::copy_table(&rtx, &snapshot, SEQUENCE_NUMBER_TO_RUNE_ID)?;
+      Self::copy_table(&rtx, &snapshot, SEQUENCE_NUMBER_TO_SATPOINT)?;
+      Self::copy_table(&rtx, &snapshot, STATISTIC_TO_COUNT)?;
+      Self::copy_table(&rtx, &snapshot, TRANSACTION_ID_TO_RUNE)?;
+      Self::copy_table(&rtx, &snapshot, TRANSACTION_ID_TO_TRANSACTION)?;
+      Self::copy_table(&rtx, &snapshot, TRANSFER_NUMBER_TO_TRANSFER)?;
+      Self::copy_table(&rtx, &snapshot, WEBHOOK_TO_EVENT_NUMBER)?;
+      Self::copy_table(
+        &rtx,
+        &snapshot,
+        WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP,
+      )?;
+
+      // an empty durable commit makes the preceding batches durable
+      snapshot.begin_write()?.commit()?;
+    }
+
+    fs::rename(&partial, path)?;
+
+    Ok(block_count)
+  }
+
+  const SNAPSHOT_BATCH_SIZE: usize = 100_000;
+
+  /// Snapshot batches are committed without durability, since the snapshot
+  /// is only renamed into place after a final durable commit.
+  fn begin_snapshot_batch(snapshot: &Database) -> Result<WriteTransaction> {
+    let mut wtx = snapshot.begin_write()?;
+    wtx.set_durability(redb::Durability::None);
+    Ok(wtx)
+  }
+
+  fn copy_table<K: redb::Key + 'static, V: redb::Value + 'static>(
+    rtx: &redb::ReadTransaction,
+    snapshot: &Database,
+    definition: TableDefinition<K, V>,
+  ) -> Result {
+    let source = match rtx.open_table(definition) {
+      Ok(source) => source,
+      Err(redb::TableError::TableDoesNotExist(_)) => return Ok(()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut entries = source.iter()?.peekable();
+
+    loop {
+      let wtx = Self::begin_snapshot_batch(snapshot)?;
+
+      {
+        let mut destination = wtx.open_table(definition)?;
+
+        for result in entries.by_ref().take(Self::SNAPSHOT_BATCH_SIZE) {
+          let (key, value) = result?;
+          destination.insert(key.value(), value.value())?;
+        }
+      }
+
+      wtx.commit()?;
+
+      if entries.peek().is_none() {
+        return Ok(());
+      }
+    }
+  }
+
+  fn copy_multimap_table<K: redb::Key + 'static, V: redb::Key + 'static>(
+    rtx: &redb::ReadTransaction,
+    snapshot: &Database,
+    definition: MultimapTableDefinition<K, V>,
+  ) -> Result {
+    let source = match rtx.open_multimap_table(definition) {
+      Ok(source) => source,
+      Err(redb::TableError::TableDoesNotExist(_)) => return Ok(()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut entries = source.iter()?.peekable();
+
+    loop {
+      let wtx = Self::begin_snapshot_batch(snapshot)?;
+
+      {
+        let mut destination = wtx.open_multimap_table(definition)?;
+
+        for result in entries.by_ref().take(Self::SNAPSHOT_BATCH_SIZE) {
+          let (key, values) = result?;
+          for value in values {
+            destination.insert(key.value(), value?.value())?;
+          }
+        }
+      }
+
+      wtx.commit()?;
+
+      if entries.peek().is_none() {
+        return Ok(());
+      }
+    }
+  }
+
   fn begin_read(&self) -> Result<rtx::Rtx> {
     Ok(rtx::Rtx(self.database.begin_read()?))
   }
@@ -1105,24 +1704,334 @@ impl Index {
     Ok(rune_balances)
   }
 
-  pub fn get_rune_balances(&self) -> Result<Vec<(OutPoint, Vec<(RuneId, u128)>)>> {
-    let mut result = Vec::new();
+  /// Get a page of the holders of `rune`, largest first, and its supply,
+  /// mints, burns and holder count, at the end of block `height`, or
+  /// currently if `height` is `None`. Past heights require the rune history
+  /// index.
+  pub fn get_rune_holders(
+    &self,
+    rune: Rune,
+    height: Option<u32>,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<Option<api::RuneHolders>> {
+    let Some((id, entry, _parent)) = self.rune(rune)? else {
+      return Ok(None);
+    };
 
-    for entry in self
-      .database
-      .begin_read()?
-      .open_table(OUTPOINT_TO_RUNE_BALANCES)?
-      .iter()?
-    {
-      let (outpoint, balances_buffer) = entry?;
-      let outpoint = OutPoint::load(*outpoint.value());
-      let balances_buffer = balances_buffer.value();
+    let (height, mut holders) = match height {
+      Some(height) => (
+        height,
+        self.get_rune_holders_at_height(id, height, page_size, page_index)?,
+      ),
+      None => (
+        self.block_height()?.unwrap_or(Height(0)).n(),
+        self.get_current_rune_holders(id, page_size, page_index)?,
+      ),
+    };
 
-      let mut balances = Vec::new();
-      let mut i = 0;
-      while i < balances_buffer.len() {
-        let ((id, balance), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
-        i += length;
+    let more = holders.len() > page_size;
+
+    if more {
+      holders.pop();
+    }
+
+    Ok(Some(api::RuneHolders {
+      divisibility: entry.divisibility,
+      height,
+      holders,
+      id,
+      more,
+      page: page_index,
+      rune: entry.spaced_rune,
+      stats: self.get_rune_stats_at_height(id, height)?,
+      symbol: entry.symbol,
+    }))
+  }
+
+  /// Get the supply, mints, burns and holder count of `rune` at the end of
+  /// each block in which it was etched, minted, burned or transferred.
+  pub fn get_rune_history(&self, rune: Rune) -> Result<Option<api::RuneHistory>> {
+    let Some((id, entry, _parent)) = self.rune(rune)? else {
+      return Ok(None);
+    };
+
+    Ok(Some(api::RuneHistory {
+      divisibility: entry.divisibility,
+      id,
+      rune: entry.spaced_rune,
+      stats: self.get_rune_stats(id)?,
+      symbol: entry.symbol,
+    }))
+  }
+
+  /// Get up to `page_size + 1` holders of the rune with `id` at the end of
+  /// block `height`, starting at page `page_index`, ordered by script pubkey.
+  /// Each script pubkey's balance history is read with a point lookup, and
+  /// the scan skips from one script pubkey to the next, so only the script
+  /// pubkeys up to the end of the page are visited.
+  fn get_rune_holders_at_height(
+    &self,
+    id: RuneId,
+    height: u32,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<Vec<api::RuneHolder>> {
+    let rtx = self.database.begin_read()?;
+
+    let balances = rtx.open_table(RUNE_ID_SCRIPT_PUBKEY_AND_HEIGHT_TO_BALANCE)?;
+
+    let mut skip = page_index.saturating_mul(page_size);
+    let mut holders = Vec::new();
+    let mut previous: Option<Vec<u8>> = None;
+
+    while holders.len() <= page_size {
+      let start: (RuneIdValue, &[u8], u32) = match &previous {
+        Some(script_pubkey) => (id.store(), script_pubkey.as_slice(), u32::MAX),
+        None => (id.store(), &[], 0),
+      };
+
+      let lower = if previous.is_some() {
+        Bound::Excluded(start)
+      } else {
+        Bound::Included(start)
+      };
+
+      let Some(result) = balances.range((lower, Bound::Unbounded))?.next() else {
+        break;
+      };
+
+      let (key, _balance) = result?;
+
+      let (rune_id, script_pubkey, _height) = key.value();
+
+      if rune_id != id.store() {
+        break;
+      }
+
+      let script_pubkey = script_pubkey.to_vec();
+
+      let amount = balances
+        .range(
+          (id.store(), script_pubkey.as_slice(), 0)
+            ..=(id.store(), script_pubkey.as_slice(), height),
+        )?
+        .next_back()
+        .transpose()?
+        .map(|(_key, balance)| balance.value())
+        .unwrap_or_default();
+
+      if amount > 0 {
+        if skip > 0 {
+          skip -= 1;
+        } else {
+          holders.push(api::RuneHolder {
+            address: self
+              .settings
+              .chain()
+              .address_from_script(Script::from_bytes(&script_pubkey))
+              .ok()
+              .map(|address| address.to_string()),
+            amount,
+            outputs: Vec::new(),
+          });
+        }
+      }
+
+      previous = Some(script_pubkey);
+    }
+
+    Ok(holders)
+  }
+
+  /// Get up to `page_size + 1` current holders of the rune with `id`,
+  /// starting at page `page_index`, largest first. With the address index,
+  /// holders are script pubkeys, otherwise they are outputs. Holders are read
+  /// in order from tables keyed by rune ID and balance, so only the holders up
+  /// to the end of the page are visited.
+  fn get_current_rune_holders(
+    &self,
+    id: RuneId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<Vec<api::RuneHolder>> {
+    let rtx = self.database.begin_read()?;
+
+    // holders are read backwards from the start of the next rune ID's range
+    let next = match id.tx.checked_add(1) {
+      Some(tx) => RuneId {
+        block: id.block,
+        tx,
+      },
+      None => RuneId {
+        block: id.block + 1,
+        tx: 0,
+      },
+    };
+
+    if self.index_addresses {
+      let outpoint_to_rune_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+
+      let holds_rune = |outpoint: OutPoint| -> Result<bool> {
+        let Some(balances) = outpoint_to_rune_balances.get(&outpoint.store())? else {
+          return Ok(false);
+        };
+
+        let buffer = balances.value();
+
+        let mut i = 0;
+        while i < buffer.len() {
+          let ((rune_id, _amount), length) = Index::decode_rune_balance(&buffer[i..]).unwrap();
+          i += length;
+
+          if rune_id == id {
+            return Ok(true);
+          }
+        }
+
+        Ok(false)
+      };
+
+      let script_pubkey_to_outpoint = rtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+      let start: (RuneIdValue, u128, &[u8]) = (id.store(), 0, &[]);
+      let end: (RuneIdValue, u128, &[u8]) = (next.store(), 0, &[]);
+
+      rtx
+        .open_table(RUNE_ID_BALANCE_AND_SCRIPT_PUBKEY)?
+        .range(start..end)?
+        .rev()
+        .skip(page_index.saturating_mul(page_size))
+        .take(page_size.saturating_add(1))
+        .map(|result| {
+          let (key, _value) = result?;
+          let (_id, amount, script_pubkey) = key.value();
+
+          let mut outputs = Vec::new();
+
+          for result in script_pubkey_to_outpoint.get(script_pubkey)? {
+            let outpoint = OutPoint::load(result?.value());
+
+            if holds_rune(outpoint)? {
+              outputs.push(outpoint);
+            }
+          }
+
+          Ok(api::RuneHolder {
+            address: self
+              .settings
+              .chain()
+              .address_from_script(Script::from_bytes(script_pubkey))
+              .ok()
+              .map(|address| address.to_string()),
+            amount,
+            outputs,
+          })
+        })
+        .collect()
+    } else {
+      rtx
+        .open_table(RUNE_ID_BALANCE_AND_OUTPOINT)?
+        .range((id.store(), 0, [0; 36])..(next.store(), 0, [0; 36]))?
+        .rev()
+        .skip(page_index.saturating_mul(page_size))
+        .take(page_size.saturating_add(1))
+        .map(|result| {
+          let (key, _value) = result?;
+          let (_id, amount, outpoint) = key.value();
+          let outpoint = OutPoint::load(outpoint);
+
+          Ok(api::RuneHolder {
+            address: self.get_output_address(outpoint)?,
+            amount,
+            outputs: vec![outpoint],
+          })
+        })
+        .collect()
+    }
+  }
+
+  fn get_output_address(&self, outpoint: OutPoint) -> Result<Option<String>> {
+    Ok(
+      self
+        .get_transaction(outpoint.txid)?
+        .and_then(|tx| tx.output.into_iter().nth(outpoint.vout.into_usize()))
+        .and_then(|output| {
+          self
+            .settings
+            .chain()
+            .address_from_script(&output.script_pubkey)
+            .ok()
+        })
+        .map(|address| address.to_string()),
+    )
+  }
+
+  fn get_rune_stats(&self, id: RuneId) -> Result<Vec<api::RuneStats>> {
+    self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_STATS)?
+      .range((id.store(), 0)..=(id.store(), u32::MAX))?
+      .map(|result| {
+        let (key, value) = result?;
+        let (_id, height) = key.value();
+        Ok(Self::rune_stats(
+          height,
+          RuneStatsEntry::load(value.value()),
+        ))
+      })
+      .collect()
+  }
+
+  /// Get the stats of the rune with `id` as of the end of block `height`,
+  /// from the last block at or before `height` in which they changed.
+  fn get_rune_stats_at_height(&self, id: RuneId, height: u32) -> Result<Option<api::RuneStats>> {
+    self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_STATS)?
+      .range((id.store(), 0)..=(id.store(), height))?
+      .next_back()
+      .map(|result| {
+        let (key, value) = result?;
+        let (_id, height) = key.value();
+        Ok(Self::rune_stats(
+          height,
+          RuneStatsEntry::load(value.value()),
+        ))
+      })
+      .transpose()
+  }
+
+  fn rune_stats(height: u32, stats: RuneStatsEntry) -> api::RuneStats {
+    api::RuneStats {
+      burned: stats.burned,
+      height,
+      holders: stats.holders,
+      mints: stats.mints,
+      supply: stats.supply,
+    }
+  }
+
+  pub fn get_rune_balances(&self) -> Result<Vec<(OutPoint, Vec<(RuneId, u128)>)>> {
+    let mut result = Vec::new();
+
+    for entry in self
+      .database
+      .begin_read()?
+      .open_table(OUTPOINT_TO_RUNE_BALANCES)?
+      .iter()?
+    {
+      let (outpoint, balances_buffer) = entry?;
+      let outpoint = OutPoint::load(*outpoint.value());
+      let balances_buffer = balances_buffer.value();
+
+      let mut balances = Vec::new();
+      let mut i = 0;
+      while i < balances_buffer.len() {
+        let ((id, balance), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
+        i += length;
         balances.push((id, balance));
       }
 
@@ -1194,6 +2103,125 @@ impl Index {
     Ok((collections, more))
   }
 
+  /// Get the number of children of `parent`, their content types, the
+  /// number minted in each block, and, with the address index, the number of
+  /// holders. Statistics are maintained as children are inscribed and
+  /// transferred, so children are not visited.
+  pub fn get_collection(&self, parent: InscriptionId) -> Result<Option<api::Collection>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&parent.store())?
+      .map(|sequence_number| sequence_number.value())
+    else {
+      return Ok(None);
+    };
+
+    let number = InscriptionEntry::load(
+      rtx
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .get(sequence_number)?
+        .unwrap()
+        .value(),
+    )
+    .inscription_number;
+
+    let (children, holders) = rtx
+      .open_table(SEQUENCE_NUMBER_TO_COLLECTION_STATS)?
+      .get(sequence_number)?
+      .map(|stats| stats.value())
+      .unwrap_or_default();
+
+    let mut content_types = rtx
+      .open_table(SEQUENCE_NUMBER_AND_CONTENT_TYPE_TO_CHILDREN)?
+      .range((sequence_number, "")..(sequence_number + 1, ""))?
+      .map(|result| {
+        result
+          .map(|(key, children)| {
+            let (_sequence_number, content_type) = key.value();
+            api::CollectionContentType {
+              children: children.value(),
+              content_type: (!content_type.is_empty()).then(|| content_type.into()),
+            }
+          })
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<api::CollectionContentType>>>()?;
+
+    content_types.sort_by(|a, b| b.children.cmp(&a.children));
+
+    let mints = rtx
+      .open_table(SEQUENCE_NUMBER_AND_HEIGHT_TO_CHILDREN)?
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+      .map(|result| {
+        result
+          .map(|(key, children)| api::CollectionMints {
+            children: children.value(),
+            height: key.value().1,
+          })
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<api::CollectionMints>>>()?;
+
+    Ok(Some(api::Collection {
+      children,
+      content_types,
+      holders: self.index_addresses.then_some(holders),
+      id: parent,
+      mints,
+      number,
+    }))
+  }
+
+  /// Get up to `page_size` holders of children of the inscription with
+  /// `sequence_number`, starting at page `page_index`, largest first. Holders
+  /// are read in order from a table keyed by parent and number of children
+  /// held, so only the holders up to the end of the page are visited.
+  pub fn get_collection_holders(
+    &self,
+    sequence_number: u32,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::CollectionHolder>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let start: (u32, u64, &[u8]) = (sequence_number, 0, &[]);
+    let end: (u32, u64, &[u8]) = (sequence_number + 1, 0, &[]);
+
+    let mut holders = rtx
+      .open_table(SEQUENCE_NUMBER_CHILDREN_AND_SCRIPT_PUBKEY)?
+      .range(start..end)?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        result
+          .map(|(key, _value)| {
+            let (_sequence_number, children, script_pubkey) = key.value();
+            api::CollectionHolder {
+              address: self
+                .settings
+                .chain()
+                .address_from_script(Script::from_bytes(script_pubkey))
+                .ok()
+                .map(|address| address.to_string()),
+              children,
+            }
+          })
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<api::CollectionHolder>>>()?;
+
+    let more = holders.len() > page_size;
+
+    if more {
+      holders.pop();
+    }
+
+    Ok((holders, more))
+  }
+
   #[cfg(test)]
   pub(crate) fn get_children_by_inscription_id(
     &self,
@@ -1262,6 +2290,21 @@ impl Index {
     sequence_number: u32,
     page_size: usize,
     page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    self.get_children_by_sequence_number_from(
+      sequence_number,
+      page_index.saturating_mul(page_size),
+      page_size,
+    )
+  }
+
+  /// Get up to `limit` children of the inscription with `sequence_number`,
+  /// skipping the first `offset`, and whether there are more.
+  pub(crate) fn get_children_by_sequence_number_from(
+    &self,
+    sequence_number: u32,
+    offset: usize,
+    limit: usize,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
@@ -1270,8 +2313,8 @@ impl Index {
     let mut children = rtx
       .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
       .get(sequence_number)?
-      .skip(page_index * page_size)
-      .take(page_size.saturating_add(1))
+      .skip(offset)
+      .take(limit.saturating_add(1))
       .map(|result| {
         result
           .and_then(|sequence_number| {
@@ -1283,7 +2326,7 @@ impl Index {
       })
       .collect::<Result<Vec<InscriptionId>>>()?;
 
-    let more = children.len() > page_size;
+    let more = children.len() > limit;
 
     if more {
       children.pop();
@@ -2128,46 +3171,425 @@ impl Index {
     )))
   }
 
-  pub fn get_inscription_entry(
+  /// Get a page of the transfers of the inscription with `sequence_number`,
+  /// oldest first, and whether there are more pages.
+  pub fn get_inscription_history_paginated(
     &self,
-    inscription_id: InscriptionId,
-  ) -> Result<Option<InscriptionEntry>> {
+    sequence_number: u32,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::InscriptionTransfer>, bool)> {
     let rtx = self.database.begin_read()?;
 
-    let Some(sequence_number) = rtx
-      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
-      .get(&inscription_id.store())?
-      .map(|guard| guard.value())
-    else {
-      return Ok(None);
-    };
+    let transfer_number_to_transfer = rtx.open_table(TRANSFER_NUMBER_TO_TRANSFER)?;
 
-    let entry = rtx
-      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+    let mut transfers = rtx
+      .open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFER_NUMBER)?
       .get(sequence_number)?
-      .map(|value| InscriptionEntry::load(value.value()));
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let transfer = TransferEntry::load(
+          transfer_number_to_transfer
+            .get(result?.value())?
+            .unwrap()
+            .value(),
+        );
 
-    Ok(entry)
+        Ok(api::InscriptionTransfer {
+          address: transfer.script_pubkey.and_then(|script_pubkey| {
+            self
+              .settings
+              .chain()
+              .address_from_script(&script_pubkey)
+              .ok()
+              .map(|address| address.to_string())
+          }),
+          height: transfer.height,
+          new_satpoint: transfer.new_satpoint,
+          old_satpoint: transfer.old_satpoint,
+          timestamp: timestamp(transfer.timestamp.into()).timestamp(),
+          transaction: transfer.txid,
+        })
+      })
+      .collect::<Result<Vec<api::InscriptionTransfer>>>()?;
+
+    let more = transfers.len() > page_size;
+
+    if more {
+      transfers.pop();
+    }
+
+    Ok((transfers, more))
   }
 
-  #[cfg(test)]
-  fn assert_inscription_location(
+  /// Get up to `page_size` inscriptions containing every term in `query` and
+  /// matching `filter`, newest first, starting before the inscription with
+  /// sequence number `cursor`, and the cursor of the next page if there may
+  /// be more. With no terms, every inscription is a candidate. At most
+  /// `search::MAX_CANDIDATES` candidates are examined per page, so a page may
+  /// be short, or even empty, and still have a next page.
+  pub(crate) fn search_inscriptions(
     &self,
-    inscription_id: InscriptionId,
-    satpoint: SatPoint,
-    sat: Option<u64>,
-  ) {
-    let rtx = self.database.begin_read().unwrap();
+    query: &str,
+    filter: &listing::InscriptionFilter,
+    cursor: Option<u32>,
+    page_size: usize,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
 
-    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY).unwrap();
+    let mut terms = search::terms(query)
+      .collect::<BTreeSet<String>>()
+      .into_iter()
+      .collect::<Vec<String>>();
 
-    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT).unwrap();
+    if let Some(content_type) = &filter.content_type {
+      terms.push(search::content_type_term(content_type));
+    }
 
-    let sequence_number = rtx
-      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
-      .unwrap()
-      .get(&inscription_id.store())
-      .unwrap()
+    let parent = match filter.parent {
+      Some(parent) => match rtx
+        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+        .get(&parent.store())?
+      {
+        Some(sequence_number) => Some(sequence_number.value()),
+        None => return Ok((Vec::new(), None)),
+      },
+      None => None,
+    };
+
+    let search_term_and_sequence_number = rtx.open_table(SEARCH_TERM_AND_SEQUENCE_NUMBER)?;
+
+    let sequence_number_to_content_entry = rtx.open_table(SEQUENCE_NUMBER_TO_CONTENT_ENTRY)?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let end = cursor.unwrap_or(u32::MAX);
+
+    let candidates: Box<dyn Iterator<Item = Result<u32, StorageError>>> = match terms.first() {
+      Some(first) => {
+        let start: (&str, u32) = (first.as_str(), 0);
+        let end: (&str, u32) = (first.as_str(), end);
+
+        Box::new(
+          search_term_and_sequence_number
+            .range(start..end)?
+            .rev()
+            .map(|result| result.map(|(key, _value)| key.value().1)),
+        )
+      }
+      None => Box::new(
+        sequence_number_to_inscription_entry
+          .range(..end)?
+          .rev()
+          .map(|result| result.map(|(sequence_number, _entry)| sequence_number.value())),
+      ),
+    };
+
+    let rest = terms.get(1..).unwrap_or_default();
+
+    let mut inscriptions = Vec::new();
+    let mut next = None;
+
+    'candidates: for (examined, result) in candidates.enumerate() {
+      if examined == search::MAX_CANDIDATES {
+        return Ok((inscriptions, next));
+      }
+
+      let sequence_number = result?;
+
+      let full = inscriptions.len() >= page_size;
+
+      if !full {
+        next = Some(sequence_number);
+      }
+
+      for term in rest {
+        if search_term_and_sequence_number
+          .get((term.as_str(), sequence_number))?
+          .is_none()
+        {
+          continue 'candidates;
+        }
+      }
+
+      let entry = InscriptionEntry::load(
+        sequence_number_to_inscription_entry
+          .get(sequence_number)?
+          .unwrap()
+          .value(),
+      );
+
+      let content = sequence_number_to_content_entry
+        .get(sequence_number)?
+        .map(|content| ContentEntry::load(content.value()));
+
+      if !filter.matches(&entry, content.as_ref(), parent) {
+        continue;
+      }
+
+      if full {
+        return Ok((inscriptions, next));
+      }
+
+      inscriptions.push(entry.id);
+    }
+
+    Ok((inscriptions, None))
+  }
+
+  /// Get up to `page_size` inscriptions matching `filter`, sorted by `sort`,
+  /// starting after the inscription with sequence number `cursor`, and the
+  /// cursor of the next page if there may be more. At most
+  /// `listing::MAX_CANDIDATES` inscriptions are examined per page, so a page
+  /// may be short, or even empty, and still have a next page. Returns `None`
+  /// if `cursor` is not the sequence number of an inscription.
+  pub(crate) fn list_inscriptions(
+    &self,
+    filter: &listing::InscriptionFilter,
+    sort: listing::InscriptionSort,
+    order: listing::SortOrder,
+    cursor: Option<u32>,
+    page_size: usize,
+  ) -> Result<Option<(Vec<InscriptionId>, Option<u32>)>> {
+    use listing::{InscriptionSort, SortOrder};
+
+    fn bounds<T>(cursor: Option<T>, order: SortOrder) -> (Bound<T>, Bound<T>) {
+      match (cursor, order) {
+        (None, _) => (Bound::Unbounded, Bound::Unbounded),
+        (Some(cursor), SortOrder::Asc) => (Bound::Excluded(cursor), Bound::Unbounded),
+        (Some(cursor), SortOrder::Desc) => (Bound::Unbounded, Bound::Excluded(cursor)),
+      }
+    }
+
+    fn ordered<'a>(
+      sequence_numbers: impl DoubleEndedIterator<Item = Result<u32, StorageError>> + 'a,
+      order: SortOrder,
+    ) -> Box<dyn Iterator<Item = Result<u32, StorageError>> + 'a> {
+      match order {
+        SortOrder::Asc => Box::new(sequence_numbers),
+        SortOrder::Desc => Box::new(sequence_numbers.rev()),
+      }
+    }
+
+    let rtx = self.database.begin_read()?;
+
+    let parent = match filter.parent {
+      Some(parent) => match rtx
+        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+        .get(&parent.store())?
+      {
+        Some(sequence_number) => Some(sequence_number.value()),
+        None => return Ok(Some((Vec::new(), None))),
+      },
+      None => None,
+    };
+
+    let sequence_number_to_content_entry = rtx.open_table(SEQUENCE_NUMBER_TO_CONTENT_ENTRY)?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let cursor = match cursor {
+      Some(sequence_number) => {
+        let Some(entry) = sequence_number_to_inscription_entry.get(sequence_number)? else {
+          return Ok(None);
+        };
+
+        let content = sequence_number_to_content_entry
+          .get(sequence_number)?
+          .map(|content| ContentEntry::load(content.value()));
+
+        Some((InscriptionEntry::load(entry.value()), content))
+      }
+      None => None,
+    };
+
+    let sequence_numbers = match sort {
+      InscriptionSort::Fee => ordered(
+        rtx
+          .open_table(FEE_AND_SEQUENCE_NUMBER)?
+          .range(bounds(
+            cursor
+              .as_ref()
+              .map(|(entry, _content)| (entry.fee, entry.sequence_number)),
+            order,
+          ))?
+          .map(|result| result.map(|(key, _)| key.value().1)),
+        order,
+      ),
+      InscriptionSort::Height => ordered(
+        sequence_number_to_inscription_entry
+          .range(bounds(
+            cursor
+              .as_ref()
+              .map(|(entry, _content)| entry.sequence_number),
+            order,
+          ))?
+          .map(|result| result.map(|(sequence_number, _)| sequence_number.value())),
+        order,
+      ),
+      InscriptionSort::Number => ordered(
+        rtx
+          .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+          .range(bounds(
+            cursor
+              .as_ref()
+              .map(|(entry, _content)| entry.inscription_number),
+            order,
+          ))?
+          .map(|result| result.map(|(_, sequence_number)| sequence_number.value())),
+        order,
+      ),
+      InscriptionSort::Size => ordered(
+        rtx
+          .open_table(CONTENT_LENGTH_AND_SEQUENCE_NUMBER)?
+          .range(bounds(
+            cursor.as_ref().map(|(entry, content)| {
+              (
+                content
+                  .as_ref()
+                  .and_then(|content| content.content_length)
+                  .unwrap_or_default(),
+                entry.sequence_number,
+              )
+            }),
+            order,
+          ))?
+          .map(|result| result.map(|(key, _)| key.value().1)),
+        order,
+      ),
+    };
+
+    let mut inscriptions = Vec::new();
+    let mut next = None;
+
+    for (examined, result) in sequence_numbers.enumerate() {
+      if examined == listing::MAX_CANDIDATES {
+        return Ok(Some((inscriptions, next)));
+      }
+
+      let sequence_number = result?;
+
+      let full = inscriptions.len() >= page_size;
+
+      if !full {
+        next = Some(sequence_number);
+      }
+
+      let entry = InscriptionEntry::load(
+        sequence_number_to_inscription_entry
+          .get(sequence_number)?
+          .unwrap()
+          .value(),
+      );
+
+      let content = sequence_number_to_content_entry
+        .get(sequence_number)?
+        .map(|content| ContentEntry::load(content.value()));
+
+      if !filter.matches(&entry, content.as_ref(), parent) {
+        continue;
+      }
+
+      if full {
+        return Ok(Some((inscriptions, next)));
+      }
+
+      inscriptions.push(entry.id);
+    }
+
+    Ok(Some((inscriptions, None)))
+  }
+
+  /// Get up to `limit` runes whose names contain every term in `query`,
+  /// newest first, from the newest `search::MAX_CANDIDATES` runes containing
+  /// the first term.
+  pub(crate) fn search_runes(&self, query: &str, limit: usize) -> Result<Vec<SpacedRune>> {
+    let rtx = self.database.begin_read()?;
+
+    let terms = search::terms(query)
+      .collect::<BTreeSet<String>>()
+      .into_iter()
+      .collect::<Vec<String>>();
+
+    let Some((first, rest)) = terms.split_first() else {
+      return Ok(Vec::new());
+    };
+
+    let search_term_and_rune_id = rtx.open_table(SEARCH_TERM_AND_RUNE_ID)?;
+
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let start: (&str, RuneIdValue) = (first.as_str(), (0, 0));
+    let end: (&str, RuneIdValue) = (first.as_str(), (u64::MAX, u32::MAX));
+
+    let mut runes = Vec::new();
+
+    'candidates: for result in search_term_and_rune_id
+      .range(start..=end)?
+      .rev()
+      .take(search::MAX_CANDIDATES)
+    {
+      if runes.len() >= limit {
+        break;
+      }
+
+      let id = result?.0.value().1;
+
+      for term in rest {
+        if search_term_and_rune_id.get((term.as_str(), id))?.is_none() {
+          continue 'candidates;
+        }
+      }
+
+      runes.push(RuneEntry::load(rune_id_to_rune_entry.get(id)?.unwrap().value()).spaced_rune);
+    }
+
+    Ok(runes)
+  }
+
+  pub fn get_inscription_entry(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<InscriptionEntry>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let entry = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .get(sequence_number)?
+      .map(|value| InscriptionEntry::load(value.value()));
+
+    Ok(entry)
+  }
+
+  #[cfg(test)]
+  fn assert_inscription_location(
+    &self,
+    inscription_id: InscriptionId,
+    satpoint: SatPoint,
+    sat: Option<u64>,
+  ) {
+    let rtx = self.database.begin_read().unwrap();
+
+    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY).unwrap();
+
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT).unwrap();
+
+    let sequence_number = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .get(&inscription_id.store())
+      .unwrap()
       .unwrap()
       .value();
 
@@ -2276,6 +3698,91 @@ impl Index {
       .collect()
   }
 
+  /// Get a page of the activity of `address`, most recent first, and whether
+  /// there are more pages.
+  pub fn get_address_history_paginated(
+    &self,
+    address: &Address,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::AddressActivity>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let activity_number_to_activity = rtx.open_table(ACTIVITY_NUMBER_TO_ACTIVITY)?;
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let inscription_ids = |sequence_numbers: Vec<u32>| -> Result<Vec<InscriptionId>> {
+      sequence_numbers
+        .into_iter()
+        .map(|sequence_number| {
+          Ok(
+            InscriptionEntry::load(
+              sequence_number_to_inscription_entry
+                .get(sequence_number)?
+                .unwrap()
+                .value(),
+            )
+            .id,
+          )
+        })
+        .collect()
+    };
+
+    let piles = |balances: Vec<(RuneId, u128)>| -> Result<BTreeMap<SpacedRune, Pile>> {
+      balances
+        .into_iter()
+        .map(|(id, amount)| {
+          let entry = RuneEntry::load(rune_id_to_rune_entry.get(id.store())?.unwrap().value());
+          Ok((
+            entry.spaced_rune,
+            Pile {
+              amount,
+              divisibility: entry.divisibility,
+              symbol: entry.symbol,
+            },
+          ))
+        })
+        .collect()
+    };
+
+    let mut activity = rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_ACTIVITY_NUMBER)?
+      .get(address.script_pubkey().as_bytes())?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let entry = ActivityEntry::load(
+          activity_number_to_activity
+            .get(result?.value())?
+            .unwrap()
+            .value(),
+        );
+
+        Ok(api::AddressActivity {
+          height: entry.height,
+          inscriptions_received: inscription_ids(entry.inscriptions_received)?,
+          inscriptions_sent: inscription_ids(entry.inscriptions_sent)?,
+          received: entry.received,
+          runes_received: piles(entry.runes_received)?,
+          runes_sent: piles(entry.runes_sent)?,
+          sent: entry.sent,
+          transaction: entry.txid,
+        })
+      })
+      .collect::<Result<Vec<api::AddressActivity>>>()?;
+
+    let more = activity.len() > page_size;
+
+    if more {
+      activity.pop();
+    }
+
+    Ok((activity, more))
+  }
+
   pub(crate) fn get_aggregated_rune_balances_for_outputs(
     &self,
     outputs: &Vec<OutPoint>,
@@ -2380,6 +3887,326 @@ impl Index {
       txout,
     )))
   }
+
+  /// Simulate what `tx` would do to the inscriptions and runes held by the
+  /// outputs it spends. `prevouts` may hold the outputs spent by each input,
+  /// for example from a PSBT, and otherwise they are looked up.
+  pub(crate) fn simulate(
+    &self,
+    tx: &Transaction,
+    prevouts: &[Option<TxOut>],
+  ) -> Result<api::Simulation> {
+    let mut inputs = Vec::new();
+    let mut fee_sat_ranges = Some(Vec::new());
+
+    for (i, txin) in tx.input.iter().enumerate() {
+      let outpoint = txin.previous_output;
+
+      let txout = match prevouts.get(i).cloned().flatten() {
+        Some(txout) => txout,
+        None => self
+          .get_transaction(outpoint.txid)?
+          .and_then(|tx| tx.output.into_iter().nth(outpoint.vout.into_usize()))
+          .ok_or_else(|| anyhow!("output {outpoint} not found"))?,
+      };
+
+      let mut inscriptions = BTreeMap::new();
+      for (satpoint, inscription_id) in self.get_inscriptions_on_output_with_satpoints(outpoint)? {
+        inscriptions.insert(inscription_id, satpoint.offset);
+      }
+
+      let mut runes = BTreeMap::new();
+      for (spaced_rune, pile) in self.get_rune_balances_for_output(outpoint)? {
+        let (id, ..) = self.rune(spaced_rune.rune)?.unwrap();
+
+        runes.insert(
+          spaced_rune,
+          api::RuneBalance {
+            id,
+            amount: pile.amount,
+            divisibility: pile.divisibility,
+            symbol: pile.symbol,
+          },
+        );
+      }
+
+      fee_sat_ranges = match (fee_sat_ranges, self.list(outpoint)?) {
+        (Some(mut ranges), Some(input_ranges)) => {
+          ranges.extend(input_ranges);
+          Some(ranges)
+        }
+        _ => None,
+      };
+
+      inputs.push((
+        txout.value,
+        api::Assets {
+          inscriptions,
+          runes,
+        },
+      ));
+    }
+
+    let artifact = Runestone::decipher(tx);
+
+    let transfer = Transfer::simulate(tx, &inputs, &self.created_runes(artifact.as_ref())?);
+
+    let input_value = inputs.iter().map(|(value, _)| value).sum::<u64>();
+    let output_value = tx.output.iter().map(|txout| txout.value).sum::<u64>();
+
+    let Some(fee) = input_value.checked_sub(output_value) else {
+      bail!(
+        "transaction outputs exceed inputs by {}",
+        output_value - input_value
+      );
+    };
+
+    let fee_sat_ranges = fee_sat_ranges.map(|ranges| {
+      let mut remaining = output_value;
+      let mut fee_ranges = Vec::new();
+      for (start, end) in ranges {
+        let size = end - start;
+        if remaining >= size {
+          remaining -= size;
+        } else {
+          fee_ranges.push((start + remaining, end));
+          remaining = 0;
+        }
+      }
+      fee_ranges
+    });
+
+    let chain = self.settings.chain();
+
+    Ok(api::Simulation {
+      txid: tx.txid(),
+      inputs: tx
+        .input
+        .iter()
+        .zip(inputs)
+        .map(|(txin, (value, assets))| api::SimulatedInput {
+          outpoint: txin.previous_output,
+          value,
+          assets,
+        })
+        .collect(),
+      outputs: tx
+        .output
+        .iter()
+        .zip(transfer.outputs)
+        .map(|(txout, assets)| api::SimulatedOutput {
+          address: chain
+            .address_from_script(&txout.script_pubkey)
+            .ok()
+            .map(|address| uncheck(&address)),
+          value: txout.value,
+          assets,
+        })
+        .collect(),
+      runestone: artifact,
+      default_output: transfer.default_output.map(|vout| vout.try_into().unwrap()),
+      burned: transfer
+        .burned
+        .into_iter()
+        .map(|(rune, balance)| (rune, balance.pile()))
+        .collect(),
+      fee,
+      fee_inscriptions: transfer.fee,
+      fee_sat_ranges,
+    })
+  }
+
+  /// Runes minted or premined by `artifact` if it were mined in the next
+  /// block. Etched runes are given the default rune ID.
+  fn created_runes(
+    &self,
+    artifact: Option<&Artifact>,
+  ) -> Result<Vec<(SpacedRune, api::RuneBalance)>> {
+    let Some(Artifact::Runestone(runestone)) = artifact else {
+      return Ok(Vec::new());
+    };
+
+    let mut created = Vec::new();
+
+    if let Some(id) = runestone.mint {
+      if let Some(rune) = self.get_rune_by_id(id)? {
+        let (_, entry, _) = self.rune(rune)?.unwrap();
+
+        if let Ok(amount) = entry.mintable(self.block_count()?.into()) {
+          created.push((
+            entry.spaced_rune,
+            api::RuneBalance {
+              id,
+              amount,
+              divisibility: entry.divisibility,
+              symbol: entry.symbol,
+            },
+          ));
+        }
+      }
+    }
+
+    if let Some(Etching {
+      divisibility,
+      premine: Some(premine),
+      rune: Some(rune),
+      spacers,
+      symbol,
+      ..
+    }) = runestone.etching
+    {
+      if self.rune(rune)?.is_none() {
+        created.push((
+          SpacedRune {
+            rune,
+            spacers: spacers.unwrap_or_default(),
+          },
+          api::RuneBalance {
+            id: RuneId::default(),
+            amount: premine,
+            divisibility: divisibility.unwrap_or_default(),
+            symbol,
+          },
+        ));
+      }
+    }
+
+    Ok(created)
+  }
+
+  /// Events are only logged while at least one webhook is registered, since
+  /// only webhooks read them back.
+  pub(crate) fn has_webhooks(wtx: &WriteTransaction) -> Result<bool> {
+    Ok(!wtx.open_table(WEBHOOK_TO_EVENT_NUMBER)?.is_empty()?)
+  }
+
+  pub(crate) fn register_webhooks(&self, webhooks: &[Url]) -> Result {
+    let registered = self
+      .database
+      .begin_read()?
+      .open_table(WEBHOOK_TO_EVENT_NUMBER)?
+      .iter()?
+      .map(|result| result.map(|(webhook, _event_number)| webhook.value().to_string()))
+      .collect::<Result<BTreeSet<String>, StorageError>>()?;
+
+    if registered
+      == webhooks
+        .iter()
+        .map(|url| url.as_str().to_string())
+        .collect::<BTreeSet<String>>()
+    {
+      return Ok(());
+    }
+
+    let wtx = self.begin_write()?;
+
+    {
+      let next_event_number = wtx
+        .open_table(EVENT_NUMBER_TO_EVENT)?
+        .last()?
+        .map(|(event_number, _event)| event_number.value() + 1)
+        .unwrap_or_default();
+
+      let mut webhook_to_event_number = wtx.open_table(WEBHOOK_TO_EVENT_NUMBER)?;
+
+      for webhook in registered {
+        if !webhooks.iter().any(|url| url.as_str() == webhook) {
+          webhook_to_event_number.remove(webhook.as_str())?;
+        }
+      }
+
+      for webhook in webhooks {
+        if webhook_to_event_number.get(webhook.as_str())?.is_none() {
+          webhook_to_event_number.insert(webhook.as_str(), next_event_number)?;
+        }
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn get_webhook_events(&self, webhook: &Url, n: usize) -> Result<Vec<(u64, Event)>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(next_event_number) = rtx
+      .open_table(WEBHOOK_TO_EVENT_NUMBER)?
+      .get(webhook.as_str())?
+      .map(|event_number| event_number.value())
+    else {
+      return Ok(Vec::new());
+    };
+
+    let event_number_to_event = rtx.open_table(EVENT_NUMBER_TO_EVENT)?;
+
+    let events = event_number_to_event
+      .range(next_event_number..)?
+      .take(n)
+      .map(|result| {
+        let (event_number, event) = result?;
+        Ok((event_number.value(), serde_json::from_slice(event.value())?))
+      })
+      .collect::<Result<Vec<(u64, Event)>>>()?;
+
+    Ok(events)
+  }
+
+  /// Advance `webhook` past the delivered `events` in a single transaction,
+  /// returning how many were acknowledged.
+  pub(crate) fn acknowledge_webhook_events(
+    &self,
+    webhook: &Url,
+    events: &[(u64, Event)],
+  ) -> Result<usize> {
+    if events.is_empty() {
+      return Ok(0);
+    }
+
+    let wtx = self.begin_write()?;
+
+    let mut acknowledged = 0;
+
+    {
+      let event_number_to_event = wtx.open_table(EVENT_NUMBER_TO_EVENT)?;
+
+      let mut webhook_to_event_number = wtx.open_table(WEBHOOK_TO_EVENT_NUMBER)?;
+
+      let Some(mut next_event_number) = webhook_to_event_number
+        .get(webhook.as_str())?
+        .map(|next_event_number| next_event_number.value())
+      else {
+        return Ok(0);
+      };
+
+      for (event_number, event) in events {
+        // a reorg may have replaced the event since it was delivered, in which
+        // case the webhook must be sent the rolled back events first
+        let logged = event_number_to_event
+          .get(event_number)?
+          .map(|logged| serde_json::from_slice::<Event>(logged.value()))
+          .transpose()?;
+
+        if logged.as_ref() != Some(event) {
+          break;
+        }
+
+        next_event_number = next_event_number.max(event_number + 1);
+
+        acknowledged += 1;
+      }
+
+      if acknowledged == 0 {
+        return Ok(0);
+      }
+
+      webhook_to_event_number.insert(webhook.as_str(), next_event_number)?;
+    }
+
+    wtx.commit()?;
+
+    Ok(acknowledged)
+  }
 }
 
 #[cfg(test)]
@@ -3284,6 +5111,100 @@ mod tests {
     );
   }
 
+  #[test]
+  fn hogex_pays_peg_outs_before_hog_addr() {
+    let context = Context::builder().args(["--index-sats"]).build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      fee: 47 * COIN_VALUE,
+      outputs: 1,
+      op_return: Some(
+        script::Builder::new()
+          .push_opcode(bitcoin::opcodes::all::OP_PUSHNUM_8)
+          .push_slice([0; 32])
+          .into_script(),
+      ),
+      op_return_index: Some(0),
+      op_return_value: Some(2 * COIN_VALUE),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint { txid, vout: 1 })
+        .unwrap()
+        .unwrap(),
+      [(50 * COIN_VALUE, 53 * COIN_VALUE)]
+    );
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint { txid, vout: 0 })
+        .unwrap()
+        .unwrap(),
+      [(53 * COIN_VALUE, 55 * COIN_VALUE)]
+    );
+
+    assert_eq!(context.index.statistic(Statistic::MwebSats), 2 * COIN_VALUE);
+  }
+
+  #[test]
+  fn simulate_reports_inscriptions_lost_to_fees() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+      let tx = Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: [OutPoint::new(coinbase, 0), OutPoint::new(txid, 0)]
+          .into_iter()
+          .map(|previous_output| TxIn {
+            previous_output,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::MAX,
+            witness: Witness::new(),
+          })
+          .collect(),
+        output: vec![TxOut {
+          value: 50 * COIN_VALUE,
+          script_pubkey: ScriptBuf::new(),
+        }],
+      };
+
+      let simulation = context.index.simulate(&tx, &[None, None]).unwrap();
+
+      assert_eq!(
+        simulation.inputs[1].assets.inscriptions,
+        [(inscription_id, 0)].into()
+      );
+      assert!(simulation.outputs[0].assets.inscriptions.is_empty());
+      assert_eq!(simulation.fee, 50 * COIN_VALUE);
+      assert_eq!(simulation.fee_inscriptions, [(inscription_id, 0)].into());
+      assert_eq!(
+        simulation.fee_sat_ranges,
+        context
+          .index
+          .has_sat_index()
+          .then(|| vec![(50 * COIN_VALUE, 100 * COIN_VALUE)])
+      );
+    }
+  }
+
   #[test]
   fn lost_inscriptions_get_lost_satpoints() {
     for context in Context::configurations() {
@@ -3450,6 +5371,60 @@ mod tests {
       format!("index at `{}{delimiter}regtest{delimiter}index.redb` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {}, ord schema {SCHEMA_VERSION}", path.display(), u64::MAX));
   }
 
+  #[test]
+  fn snapshot_reload_reopens_replaced_snapshot() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let tempdir = TempDir::new().unwrap();
+
+    let path = tempdir.path().join("snapshot.redb");
+
+    context.index.snapshot(&path).unwrap();
+
+    let published = fs::read(&path).unwrap();
+
+    let reloaded = Context::builder()
+      .args(["--index", path.to_str().unwrap(), "--snapshot-reload"])
+      .build();
+
+    assert_eq!(reloaded.index.block_count().unwrap(), 2);
+
+    context.mine_blocks(1);
+
+    reloaded.index.update().unwrap();
+
+    assert_eq!(reloaded.index.block_count().unwrap(), 2);
+
+    assert_eq!(fs::read(&path).unwrap(), published);
+
+    context.index.snapshot(&path).unwrap();
+
+    reloaded.index.update().unwrap();
+
+    assert_eq!(reloaded.index.block_count().unwrap(), 3);
+
+    assert_eq!(
+      reloaded.index.begin_write().err().unwrap().to_string(),
+      format!("index snapshot at `{}` is read-only", path.display()),
+    );
+  }
+
+  #[test]
+  fn snapshot_reload_does_not_create_index() {
+    let tempdir = TempDir::new().unwrap();
+
+    let path = tempdir.path().join("snapshot.redb");
+
+    assert!(Context::builder()
+      .args(["--index", path.to_str().unwrap(), "--snapshot-reload"])
+      .try_build()
+      .is_err());
+
+    assert!(!path.exists());
+  }
+
   #[test]
   fn inscriptions_on_output() {
     for context in Context::configurations() {
@@ -4711,6 +6686,124 @@ mod tests {
     }
   }
 
+  #[test]
+  fn recover_from_25_block_deep_reorg_with_undo_log() {
+    for args in [
+      vec!["--undo-depth", "30"],
+      vec!["--undo-depth", "30", "--index-sats"],
+    ] {
+      let context = Context::builder().args(args).build();
+
+      context.mine_blocks(1);
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          1,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let first_id = InscriptionId { txid, index: 0 };
+      let first_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..default()
+      });
+
+      let second_id = InscriptionId { txid, index: 0 };
+      let second_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
+
+      for _ in 0..25 {
+        context.mine_blocks(1);
+      }
+
+      context
+        .index
+        .assert_inscription_location(second_id, second_location, Some(100 * COIN_VALUE));
+
+      for _ in 0..25 {
+        context.core.invalidate_tip();
+      }
+
+      context.mine_blocks(27);
+
+      assert!(!context.index.inscription_exists(second_id).unwrap());
+
+      context
+        .index
+        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
+    }
+  }
+
+  #[test]
+  fn undo_log_has_an_entry_for_every_block_within_undo_depth() {
+    let context = Context::builder().args(["--undo-depth", "3"]).build();
+
+    context.mine_blocks(6);
+
+    let heights = || {
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(HEIGHT_TO_UNDO_LOG)
+        .unwrap()
+        .iter()
+        .unwrap()
+        .map(|result| result.unwrap().0.value())
+        .collect::<Vec<u32>>()
+    };
+
+    assert_eq!(heights(), [4, 5, 6]);
+
+    context.index.roll_back(4).unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 5);
+    assert_eq!(heights(), [4]);
+  }
+
+  #[test]
+  fn reorg_deeper_than_undo_depth_is_unrecoverable() {
+    let context = Context::builder().args(["--undo-depth", "5"]).build();
+
+    for _ in 0..10 {
+      context.mine_blocks(1);
+    }
+
+    for _ in 0..8 {
+      context.core.invalidate_tip();
+    }
+
+    context.core.mine_blocks(9);
+
+    assert_eq!(
+      context
+        .index
+        .update()
+        .unwrap_err()
+        .downcast_ref::<reorg::Error>(),
+      Some(&reorg::Error::Unrecoverable),
+    );
+  }
+
   #[test]
   fn inscription_without_parent_tag_has_no_parent_entry() {
     for context in Context::configurations() {
@@ -6668,6 +8761,116 @@ mod tests {
     );
   }
 
+  #[test]
+  fn events_are_only_logged_while_webhooks_are_registered() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    let event_count = |context: &Context| {
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(EVENT_NUMBER_TO_EVENT)
+        .unwrap()
+        .len()
+        .unwrap()
+    };
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(event_receiver.try_recv().is_ok());
+    assert_eq!(event_count(&context), 0);
+
+    context
+      .index
+      .register_webhooks(&["http://localhost:9000/events".parse::<Url>().unwrap()])
+      .unwrap();
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(event_count(&context), 1);
+  }
+
+  #[test]
+  fn webhook_events_are_read_from_acknowledged_cursor() {
+    let context = Context::builder().build();
+
+    let webhook = "http://localhost:9000/events".parse::<Url>().unwrap();
+
+    context.mine_blocks(1);
+
+    context.index.register_webhooks(&[webhook.clone()]).unwrap();
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let events = context.index.get_webhook_events(&webhook, 10).unwrap();
+
+    assert_eq!(events.len(), 1);
+
+    let (event_number, event) = &events[0];
+
+    assert_eq!(
+      event.inscription_id(),
+      Some(InscriptionId { txid, index: 0 })
+    );
+
+    let replaced = vec![(*event_number + 1, event.clone())];
+
+    assert_eq!(
+      context
+        .index
+        .acknowledge_webhook_events(&webhook, &replaced)
+        .unwrap(),
+      0
+    );
+
+    assert_eq!(
+      context.index.get_webhook_events(&webhook, 10).unwrap(),
+      events
+    );
+
+    assert_eq!(
+      context
+        .index
+        .acknowledge_webhook_events(&webhook, &events)
+        .unwrap(),
+      1
+    );
+
+    assert!(context
+      .index
+      .get_webhook_events(&webhook, 10)
+      .unwrap()
+      .is_empty());
+
+    context.index.register_webhooks(&[]).unwrap();
+
+    assert!(context
+      .index
+      .get_webhook_events(&webhook, 10)
+      .unwrap()
+      .is_empty());
+  }
+
   #[test]
   fn assert_schema_statistic_key_is_zero() {
     // other schema statistic keys may chenge when the schema changes, but for
@@ -6675,16 +8878,4 @@ mod tests {
     // zero
     assert_eq!(Statistic::Schema.key(), 0);
   }
-
-  #[test]
-  fn reminder_to_update_utxo_entry_type_name() {
-    // This test will break when the schema version is updated, and is a
-    // reminder to fix the type name in `impl redb::Value for &UtxoEntry`.
-    //
-    // The type name should be changed from `ord::index::utxo_entry::UtxoValue`
-    // to `ord::UtxoEntry`. I think it's probably best if we just name types
-    // `ord::NAME`, instead of including the full path, since the full path
-    // will change if we reorganize the code.
-    assert_eq!(SCHEMA_VERSION, 28);
-  }
 }
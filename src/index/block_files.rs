@@ -0,0 +1,380 @@
+use {
+  super::*,
+  std::{
+    fs::File,
+    io::{ErrorKind, Read, Seek, SeekFrom},
+  },
+};
+
+mod leveldb;
+
+const BLOCK_HAVE_DATA: u64 = 8;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Location {
+  file: u32,
+  offset: u64,
+}
+
+/// Reads blocks directly from Litecoin Core's `blk*.dat` files, which is much
+/// faster than fetching them over RPC. Blocks are located using Litecoin
+/// Core's block index, which is loaded when the reader is opened, so blocks
+/// stored after that are not found.
+pub(crate) struct BlockFiles {
+  dir: PathBuf,
+  height: Option<u32>,
+  locations: HashMap<BlockHash, Location>,
+  xor: [u8; 8],
+}
+
+impl BlockFiles {
+  pub(crate) fn open(dir: PathBuf) -> Result<Self> {
+    let index = dir.join("index");
+
+    ensure!(
+      index.is_dir(),
+      "no block index found in `{}`",
+      dir.display(),
+    );
+
+    let mut xor = [0; 8];
+
+    match fs::read(dir.join("xor.dat")) {
+      Ok(key) => {
+        xor = key
+          .try_into()
+          .map_err(|key: Vec<u8>| anyhow!("invalid xor.dat key length: {}", key.len()))?;
+      }
+      Err(err) if err.kind() == ErrorKind::NotFound => {}
+      Err(err) => return Err(err.into()),
+    }
+
+    let mut height = None;
+    let mut locations = HashMap::new();
+
+    for (key, value) in leveldb::read(&index, b"b").context("failed to read block index")? {
+      if key.len() != 33 {
+        continue;
+      }
+
+      let hash = consensus::encode::deserialize::<BlockHash>(&key[1..])
+        .context("invalid block index key")?;
+
+      let (entry_height, location) = Self::parse_entry(&value)
+        .with_context(|| format!("invalid block index entry for block {hash}"))?;
+
+      if let Some(location) = location {
+        height = height.max(Some(entry_height));
+        locations.insert(hash, location);
+      }
+    }
+
+    log::info!(
+      "Loaded {} block locations from block index `{}`",
+      locations.len(),
+      index.display(),
+    );
+
+    Ok(Self {
+      dir,
+      height,
+      locations,
+      xor,
+    })
+  }
+
+  /// The height of the highest block stored in the block files, when the
+  /// block index was loaded.
+  pub(crate) fn height(&self) -> Option<u32> {
+    self.height
+  }
+
+  /// Returns the block with `hash`, or `None` if it was not in the block
+  /// index.
+  pub(crate) fn get(&self, hash: BlockHash) -> Result<Option<Block>> {
+    let Some(location) = self.locations.get(&hash).copied() else {
+      return Ok(None);
+    };
+
+    let path = self.path(location.file);
+
+    let mut file = File::open(&path)?;
+
+    // blocks are preceded by the network magic and their size
+    let mut size = [0; 4];
+    self.read_at(
+      &mut file,
+      location
+        .offset
+        .checked_sub(4)
+        .context("invalid block offset")?,
+      &mut size,
+    )?;
+
+    let mut bytes = vec![0; u32::from_le_bytes(size).into_usize()];
+    self.read_at(&mut file, location.offset, &mut bytes)?;
+
+    // Litecoin blocks may carry MWEB data after their transactions, which is
+    // not needed for indexing
+    let (block, _) = consensus::encode::deserialize_partial::<Block>(&bytes)?;
+
+    ensure!(
+      block.block_hash() == hash,
+      "block file {} contains block {} at offset {}, expected {hash}",
+      path.display(),
+      block.block_hash(),
+      location.offset,
+    );
+
+    Ok(Some(block))
+  }
+
+  /// Block index entries start with the client version, the block's height,
+  /// status, and transaction count, followed by the number of the file
+  /// containing the block and the block's offset, if the block is stored,
+  /// which is all that is needed here. Integers are serialized as Litecoin
+  /// Core `VARINT`s.
+  fn parse_entry(mut entry: &[u8]) -> Result<(u32, Option<Location>)> {
+    let _version = Self::varint(&mut entry)?;
+    let height = Self::varint(&mut entry)?.try_into()?;
+    let status = Self::varint(&mut entry)?;
+    let _transactions = Self::varint(&mut entry)?;
+
+    if status & BLOCK_HAVE_DATA == 0 {
+      return Ok((height, None));
+    }
+
+    let file = Self::varint(&mut entry)?.try_into()?;
+    let offset = Self::varint(&mut entry)?;
+
+    Ok((height, Some(Location { file, offset })))
+  }
+
+  fn varint(bytes: &mut &[u8]) -> Result<u64> {
+    let mut n = 0u64;
+
+    loop {
+      let (&byte, rest) = bytes.split_first().context("truncated varint")?;
+      *bytes = rest;
+
+      n = n.checked_mul(128).context("varint too large")? | u64::from(byte & 0x7f);
+
+      if byte & 0x80 == 0 {
+        return Ok(n);
+      }
+
+      n = n.checked_add(1).context("varint too large")?;
+    }
+  }
+
+  fn path(&self, file: u32) -> PathBuf {
+    self.dir.join(format!("blk{file:05}.dat"))
+  }
+
+  fn read_at(&self, file: &mut File, offset: u64, buffer: &mut [u8]) -> io::Result<()> {
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buffer)?;
+
+    let start = (offset % 8) as usize;
+
+    for (i, byte) in buffer.iter_mut().enumerate() {
+      *byte ^= self.xor[(start + i) % self.xor.len()];
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const BLOCK_HAVE_UNDO: u64 = 16;
+
+  fn block(previous: BlockHash) -> Block {
+    Block {
+      header: Header {
+        version: bitcoin::block::Version::ONE,
+        prev_blockhash: previous,
+        merkle_root: TxMerkleNode::all_zeros(),
+        time: 0,
+        bits: bitcoin::CompactTarget::from_consensus(0),
+        nonce: 0,
+      },
+      txdata: vec![Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: Vec::new(),
+        output: vec![TxOut {
+          value: 50 * COIN_VALUE,
+          script_pubkey: ScriptBuf::new(),
+        }],
+      }],
+    }
+  }
+
+  /// Write `blocks` to block file `file`, returning their offsets.
+  fn write(dir: &Path, file: u32, blocks: &[&Block], xor: [u8; 8]) -> Vec<u64> {
+    let mut bytes = Vec::new();
+    let mut offsets = Vec::new();
+
+    for block in blocks {
+      let block = consensus::encode::serialize(*block);
+      bytes.extend(Network::Regtest.magic().to_bytes());
+      bytes.extend(u32::try_from(block.len()).unwrap().to_le_bytes());
+      offsets.push(bytes.len().try_into().unwrap());
+      bytes.extend(block);
+    }
+
+    bytes.extend(vec![0; 1000]);
+
+    for (i, byte) in bytes.iter_mut().enumerate() {
+      *byte ^= xor[i % xor.len()];
+    }
+
+    fs::write(dir.join(format!("blk{file:05}.dat")), bytes).unwrap();
+
+    offsets
+  }
+
+  fn write_varint(bytes: &mut Vec<u8>, mut n: u64) {
+    let mut varint = vec![(n & 0x7f) as u8];
+
+    while n > 0x7f {
+      n = (n >> 7) - 1;
+      varint.push((n & 0x7f) as u8 | 0x80);
+    }
+
+    bytes.extend(varint.iter().rev());
+  }
+
+  /// Write block index entries for `blocks`, which are `(block, height,
+  /// location)` tuples, where blocks without a location are not stored.
+  fn index(dir: &Path, blocks: &[(&Block, u32, Option<(u32, u64)>)]) {
+    fs::create_dir_all(dir.join("index")).unwrap();
+
+    let entries = blocks
+      .iter()
+      .map(|(block, height, location)| {
+        let mut key = vec![b'b'];
+        key.extend(consensus::encode::serialize(&block.block_hash()));
+
+        let mut value = Vec::new();
+        write_varint(&mut value, 259900);
+        write_varint(&mut value, (*height).into());
+
+        match location {
+          Some((file, offset)) => {
+            write_varint(&mut value, 3 | BLOCK_HAVE_DATA | BLOCK_HAVE_UNDO);
+            write_varint(&mut value, block.txdata.len().try_into().unwrap());
+            write_varint(&mut value, (*file).into());
+            write_varint(&mut value, *offset);
+            write_varint(&mut value, 0);
+          }
+          None => {
+            write_varint(&mut value, 1);
+            write_varint(&mut value, 0);
+          }
+        }
+
+        value.extend(consensus::encode::serialize(&block.header));
+
+        (key, value)
+      })
+      .collect::<Vec<(Vec<u8>, Vec<u8>)>>();
+
+    leveldb::tests::write_log(
+      &dir.join("index/000003.log"),
+      1,
+      &entries
+        .iter()
+        .map(|(key, value)| (key.as_slice(), Some(value.as_slice())))
+        .collect::<Vec<(&[u8], Option<&[u8]>)>>(),
+    );
+  }
+
+  #[test]
+  fn varints_are_decoded() {
+    for n in [0, 1, 127, 128, 255, 16511, 16512, 259900, u32::MAX.into()] {
+      let mut bytes = Vec::new();
+      write_varint(&mut bytes, n);
+      assert_eq!(BlockFiles::varint(&mut bytes.as_slice()).unwrap(), n);
+    }
+  }
+
+  #[test]
+  fn missing_block_index_is_an_error() {
+    let tempdir = TempDir::new().unwrap();
+
+    write(tempdir.path(), 0, &[&block(BlockHash::all_zeros())], [0; 8]);
+
+    assert!(BlockFiles::open(tempdir.path().into()).is_err());
+  }
+
+  #[test]
+  fn blocks_are_read_from_block_files() {
+    let tempdir = TempDir::new().unwrap();
+
+    let a = block(BlockHash::all_zeros());
+    let b = block(a.block_hash());
+    let c = block(b.block_hash());
+    let d = block(c.block_hash());
+
+    let first = write(tempdir.path(), 0, &[&a, &b], [0; 8]);
+    let second = write(tempdir.path(), 1, &[&c], [0; 8]);
+
+    index(
+      tempdir.path(),
+      &[
+        (&a, 0, Some((0, first[0]))),
+        (&b, 1, Some((0, first[1]))),
+        (&c, 2, Some((1, second[0]))),
+        (&d, 3, None),
+      ],
+    );
+
+    let block_files = BlockFiles::open(tempdir.path().into()).unwrap();
+
+    assert_eq!(block_files.height(), Some(2));
+    assert_eq!(block_files.get(a.block_hash()).unwrap(), Some(a));
+    assert_eq!(block_files.get(c.block_hash()).unwrap(), Some(c));
+    assert_eq!(block_files.get(b.block_hash()).unwrap(), Some(b));
+    assert_eq!(block_files.get(d.block_hash()).unwrap(), None);
+    assert_eq!(block_files.get(BlockHash::all_zeros()).unwrap(), None);
+  }
+
+  #[test]
+  fn mismatched_blocks_are_an_error() {
+    let tempdir = TempDir::new().unwrap();
+
+    let a = block(BlockHash::all_zeros());
+    let b = block(a.block_hash());
+
+    let offsets = write(tempdir.path(), 0, &[&a, &b], [0; 8]);
+
+    index(tempdir.path(), &[(&b, 1, Some((0, offsets[0])))]);
+
+    let block_files = BlockFiles::open(tempdir.path().into()).unwrap();
+
+    assert!(block_files.get(b.block_hash()).is_err());
+  }
+
+  #[test]
+  fn obfuscated_block_files_are_read() {
+    let tempdir = TempDir::new().unwrap();
+
+    let xor = [1, 2, 3, 4, 5, 6, 7, 8];
+
+    fs::write(tempdir.path().join("xor.dat"), xor).unwrap();
+
+    let a = block(BlockHash::all_zeros());
+
+    let offsets = write(tempdir.path(), 0, &[&a], xor);
+
+    index(tempdir.path(), &[(&a, 0, Some((0, offsets[0])))]);
+
+    let block_files = BlockFiles::open(tempdir.path().into()).unwrap();
+
+    assert_eq!(block_files.get(a.block_hash()).unwrap(), Some(a));
+  }
+}
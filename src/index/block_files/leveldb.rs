@@ -0,0 +1,476 @@
+use {super::*, std::borrow::Cow};
+
+const LOG_BLOCK_SIZE: usize = 32768;
+const LOG_HEADER_SIZE: usize = 7;
+const TABLE_FOOTER_SIZE: usize = 48;
+const TABLE_MAGIC: u64 = 0xdb4775248b80fb57;
+
+/// Reads the most recent value of every key starting with `prefix` from the
+/// LevelDB database in `dir`, without taking the database lock, so that it
+/// can be read while Litecoin Core is running. Tables and logs are read
+/// directly, so keys in files which are compacted away while reading may be
+/// missing, and callers must tolerate missing keys.
+pub(super) fn read(dir: &Path, prefix: &[u8]) -> Result<HashMap<Vec<u8>, Vec<u8>>> {
+  let mut entries = HashMap::<Vec<u8>, (u64, Option<Vec<u8>>)>::new();
+
+  let mut insert = |key: &[u8], sequence: u64, value: Option<&[u8]>| {
+    if !key.starts_with(prefix) {
+      return;
+    }
+
+    if let Some((newest, _)) = entries.get(key) {
+      if *newest >= sequence {
+        return;
+      }
+    }
+
+    entries.insert(key.to_vec(), (sequence, value.map(<[u8]>::to_vec)));
+  };
+
+  for entry in fs::read_dir(dir)? {
+    let path = entry?.path();
+
+    let extension = path.extension().and_then(|extension| extension.to_str());
+
+    if !matches!(extension, Some("ldb" | "log" | "sst")) {
+      continue;
+    }
+
+    let bytes = match fs::read(&path) {
+      Ok(bytes) => bytes,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+      Err(err) => return Err(err.into()),
+    };
+
+    let result = if extension == Some("log") {
+      read_log(&bytes, &mut insert)
+    } else {
+      read_table(&bytes, &mut insert)
+    };
+
+    result.with_context(|| format!("failed to read `{}`", path.display()))?;
+  }
+
+  Ok(
+    entries
+      .into_iter()
+      .filter_map(|(key, (_, value))| Some((key, value?)))
+      .collect(),
+  )
+}
+
+/// Log files are a sequence of 32 KiB blocks, each containing records with a
+/// checksum, a length, and a type, which indicates whether the record is a
+/// whole write batch or a fragment of one. The record being written when the
+/// file was read may be incomplete, so reading stops at the first record with
+/// an invalid checksum.
+fn read_log(log: &[u8], insert: &mut impl FnMut(&[u8], u64, Option<&[u8]>)) -> Result {
+  let mut batch = Vec::new();
+
+  for mut block in log.chunks(LOG_BLOCK_SIZE) {
+    while block.len() >= LOG_HEADER_SIZE {
+      let checksum = u32::from_le_bytes(block[..4].try_into().unwrap());
+      let length = usize::from(u16::from_le_bytes(block[4..6].try_into().unwrap()));
+      let kind = block[6];
+
+      // zeroed space at the end of a block, or preallocated space at the end
+      // of the file
+      if kind == 0 && length == 0 {
+        break;
+      }
+
+      let Some(fragment) = block.get(LOG_HEADER_SIZE..LOG_HEADER_SIZE + length) else {
+        return Ok(());
+      };
+
+      if unmask(checksum) != crc32c(crc32c(0, &[kind]), fragment) {
+        return Ok(());
+      }
+
+      block = &block[LOG_HEADER_SIZE + length..];
+
+      match kind {
+        1 => read_batch(fragment, insert)?,
+        2 => batch = fragment.to_vec(),
+        3 => batch.extend_from_slice(fragment),
+        4 => {
+          batch.extend_from_slice(fragment);
+          read_batch(&batch, insert)?;
+          batch.clear();
+        }
+        kind => bail!("invalid log record type {kind}"),
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Write batches are a sequence number and a count, followed by that many
+/// puts and deletes, which are assigned consecutive sequence numbers.
+fn read_batch(mut batch: &[u8], insert: &mut impl FnMut(&[u8], u64, Option<&[u8]>)) -> Result {
+  let sequence = u64::from_le_bytes(take(&mut batch, 8)?.try_into().unwrap());
+  let count = u32::from_le_bytes(take(&mut batch, 4)?.try_into().unwrap());
+
+  for i in 0..count {
+    let kind = take(&mut batch, 1)?[0];
+    let key = length_prefixed(&mut batch)?;
+
+    let value = match kind {
+      0 => None,
+      1 => Some(length_prefixed(&mut batch)?),
+      kind => bail!("invalid write batch record type {kind}"),
+    };
+
+    insert(key, sequence + u64::from(i), value);
+  }
+
+  Ok(())
+}
+
+/// Tables end with a footer containing the location of the index block, whose
+/// values are the locations of the table's data blocks. Table keys are user
+/// keys followed by eight bytes containing the sequence number and whether
+/// the entry is a put or a delete.
+fn read_table(table: &[u8], insert: &mut impl FnMut(&[u8], u64, Option<&[u8]>)) -> Result {
+  ensure!(table.len() >= TABLE_FOOTER_SIZE, "table too short");
+
+  let mut footer = &table[table.len() - TABLE_FOOTER_SIZE..];
+
+  ensure!(
+    u64::from_le_bytes(footer[TABLE_FOOTER_SIZE - 8..].try_into().unwrap()) == TABLE_MAGIC,
+    "invalid table magic",
+  );
+
+  let _metaindex = block_handle(&mut footer)?;
+  let index = block(table, block_handle(&mut footer)?)?;
+
+  for (_, mut handle) in entries(&index)? {
+    let data = block(table, block_handle(&mut handle)?)?;
+
+    for (key, value) in entries(&data)? {
+      ensure!(key.len() >= 8, "table key too short");
+
+      let (key, trailer) = key.split_at(key.len() - 8);
+      let trailer = u64::from_le_bytes(trailer.try_into().unwrap());
+
+      insert(key, trailer >> 8, (trailer & 0xff == 1).then_some(value));
+    }
+  }
+
+  Ok(())
+}
+
+fn block_handle(bytes: &mut &[u8]) -> Result<(usize, usize)> {
+  Ok((varint(bytes)?.try_into()?, varint(bytes)?.try_into()?))
+}
+
+/// Blocks are followed by a one byte compression type and a checksum.
+fn block(table: &[u8], (offset, size): (usize, usize)) -> Result<Cow<[u8]>> {
+  let contents = offset
+    .checked_add(size)
+    .and_then(|end| table.get(offset..=end))
+    .context("block out of bounds")?;
+
+  match contents[size] {
+    0 => Ok(Cow::Borrowed(&contents[..size])),
+    1 => Ok(Cow::Owned(
+      snap::raw::Decoder::new().decompress_vec(&contents[..size])?,
+    )),
+    compression => bail!("unsupported block compression type {compression}"),
+  }
+}
+
+/// Block entries share a prefix with the previous entry's key, and are
+/// followed by an array of restart points, which are only needed for seeking.
+fn entries(block: &[u8]) -> Result<Vec<(Vec<u8>, &[u8])>> {
+  ensure!(block.len() >= 4, "block too short");
+
+  let restarts = u32::from_le_bytes(block[block.len() - 4..].try_into().unwrap()).into_usize();
+
+  let end = restarts
+    .checked_mul(4)
+    .and_then(|restarts| block.len().checked_sub(restarts + 4))
+    .context("invalid block restart count")?;
+
+  let mut data = &block[..end];
+  let mut key = Vec::new();
+  let mut entries = Vec::new();
+
+  while !data.is_empty() {
+    let shared = usize::try_from(varint(&mut data)?)?;
+    let unshared = usize::try_from(varint(&mut data)?)?;
+    let length = usize::try_from(varint(&mut data)?)?;
+
+    ensure!(shared <= key.len(), "invalid shared key length");
+
+    key.truncate(shared);
+    key.extend_from_slice(take(&mut data, unshared)?);
+
+    entries.push((key.clone(), take(&mut data, length)?));
+  }
+
+  Ok(entries)
+}
+
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+  ensure!(bytes.len() >= n, "unexpected end of data");
+  let (head, tail) = bytes.split_at(n);
+  *bytes = tail;
+  Ok(head)
+}
+
+fn length_prefixed<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8]> {
+  let n = varint(bytes)?.try_into()?;
+  take(bytes, n)
+}
+
+fn varint(bytes: &mut &[u8]) -> Result<u64> {
+  let mut n = 0;
+
+  for shift in (0..64).step_by(7) {
+    let byte = take(bytes, 1)?[0];
+
+    n |= u64::from(byte & 0x7f) << shift;
+
+    if byte & 0x80 == 0 {
+      return Ok(n);
+    }
+  }
+
+  bail!("varint too long")
+}
+
+fn crc32c(crc: u32, bytes: &[u8]) -> u32 {
+  let mut crc = !crc;
+
+  for byte in bytes {
+    crc ^= u32::from(*byte);
+
+    for _ in 0..8 {
+      crc = (crc >> 1) ^ (0x82f63b78 & (crc & 1).wrapping_neg());
+    }
+  }
+
+  !crc
+}
+
+fn unmask(checksum: u32) -> u32 {
+  checksum.wrapping_sub(0xa282ead8).rotate_left(15)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+  use super::*;
+
+  fn write_varint(bytes: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+      bytes.push(n as u8 | 0x80);
+      n >>= 7;
+    }
+    bytes.push(n as u8);
+  }
+
+  pub(crate) fn write_log(path: &Path, sequence: u64, batch: &[(&[u8], Option<&[u8]>)]) {
+    let mut record = Vec::new();
+    record.extend(sequence.to_le_bytes());
+    record.extend(u32::try_from(batch.len()).unwrap().to_le_bytes());
+
+    for (key, value) in batch {
+      record.push(value.is_some().into());
+      write_varint(&mut record, key.len());
+      record.extend_from_slice(key);
+      if let Some(value) = value {
+        write_varint(&mut record, value.len());
+        record.extend_from_slice(value);
+      }
+    }
+
+    let mut log = fs::read(path).unwrap_or_default();
+    let mut first = true;
+
+    for (i, fragment) in record.chunks(1000).enumerate() {
+      let last = (i + 1) * 1000 >= record.len();
+
+      let kind = match (first, last) {
+        (true, true) => 1,
+        (true, false) => 2,
+        (false, false) => 3,
+        (false, true) => 4,
+      };
+
+      first = false;
+
+      let checksum = crc32c(crc32c(0, &[kind]), fragment)
+        .rotate_right(15)
+        .wrapping_add(0xa282ead8);
+
+      if log.len() % LOG_BLOCK_SIZE + LOG_HEADER_SIZE + fragment.len() > LOG_BLOCK_SIZE {
+        log.resize(log.len().next_multiple_of(LOG_BLOCK_SIZE), 0);
+      }
+
+      log.extend(checksum.to_le_bytes());
+      log.extend(u16::try_from(fragment.len()).unwrap().to_le_bytes());
+      log.push(kind);
+      log.extend_from_slice(fragment);
+    }
+
+    fs::write(path, log).unwrap();
+  }
+
+  fn table(entries: &[(&[u8], u64, Option<&[u8]>)], compress: bool) -> Vec<u8> {
+    fn write_block(table: &mut Vec<u8>, entries: &[(Vec<u8>, Vec<u8>)], compress: bool) -> Vec<u8> {
+      let mut block = Vec::new();
+      let mut previous: &[u8] = &[];
+
+      for (key, value) in entries {
+        let shared = key.iter().zip(previous).take_while(|(a, b)| a == b).count();
+        write_varint(&mut block, shared);
+        write_varint(&mut block, key.len() - shared);
+        write_varint(&mut block, value.len());
+        block.extend_from_slice(&key[shared..]);
+        block.extend_from_slice(value);
+        previous = key.as_slice();
+      }
+
+      block.extend(0u32.to_le_bytes());
+      block.extend(1u32.to_le_bytes());
+
+      if compress {
+        block = snap::raw::Encoder::new().compress_vec(&block).unwrap();
+      }
+
+      let mut handle = Vec::new();
+      write_varint(&mut handle, table.len());
+      write_varint(&mut handle, block.len());
+
+      table.extend(block);
+      table.push(compress.into());
+      table.extend([0; 4]);
+
+      handle
+    }
+
+    let data = entries
+      .iter()
+      .map(|(key, sequence, value)| {
+        let mut key = key.to_vec();
+        key.extend((sequence << 8 | u64::from(value.is_some())).to_le_bytes());
+        (key, value.unwrap_or_default().to_vec())
+      })
+      .collect::<Vec<(Vec<u8>, Vec<u8>)>>();
+
+    let mut table = Vec::new();
+
+    let data = write_block(&mut table, &data, compress);
+    let metaindex = write_block(&mut table, &[], compress);
+    let index = write_block(&mut table, &[(vec![0xff], data)], compress);
+
+    let mut footer = Vec::new();
+    footer.extend(metaindex);
+    footer.extend(index);
+    footer.resize(TABLE_FOOTER_SIZE - 8, 0);
+    footer.extend(TABLE_MAGIC.to_le_bytes());
+
+    table.extend(footer);
+
+    table
+  }
+
+  #[test]
+  fn crc32c_matches_known_value() {
+    assert_eq!(crc32c(0, b"123456789"), 0xe3069283);
+  }
+
+  #[test]
+  fn tables_are_read() {
+    let tempdir = TempDir::new().unwrap();
+
+    for compress in [false, true] {
+      fs::write(
+        tempdir.path().join("000005.ldb"),
+        table(
+          &[
+            (b"ba", 1, Some(b"foo")),
+            (b"bb", 2, Some(b"bar")),
+            (b"bc", 3, None),
+            (b"c", 4, Some(b"baz")),
+          ],
+          compress,
+        ),
+      )
+      .unwrap();
+
+      assert_eq!(
+        read(tempdir.path(), b"b").unwrap(),
+        [
+          (b"ba".to_vec(), b"foo".to_vec()),
+          (b"bb".to_vec(), b"bar".to_vec()),
+        ]
+        .into_iter()
+        .collect(),
+      );
+    }
+  }
+
+  #[test]
+  fn logs_are_read() {
+    let tempdir = TempDir::new().unwrap();
+
+    let path = tempdir.path().join("000003.log");
+
+    let value = vec![1; 3000];
+
+    write_log(&path, 1, &[(b"ba", Some(b"foo")), (b"bb", Some(&value))]);
+    write_log(&path, 3, &[(b"ba", None), (b"bc", Some(b"bar"))]);
+
+    assert_eq!(
+      read(tempdir.path(), b"b").unwrap(),
+      [(b"bb".to_vec(), value), (b"bc".to_vec(), b"bar".to_vec())]
+        .into_iter()
+        .collect(),
+    );
+  }
+
+  #[test]
+  fn newest_entry_wins() {
+    let tempdir = TempDir::new().unwrap();
+
+    fs::write(
+      tempdir.path().join("000005.ldb"),
+      table(&[(b"ba", 1, Some(b"foo")), (b"bb", 5, Some(b"bar"))], false),
+    )
+    .unwrap();
+
+    write_log(
+      &tempdir.path().join("000006.log"),
+      2,
+      &[(b"ba", Some(b"baz")), (b"bb", Some(b"qux"))],
+    );
+
+    assert_eq!(
+      read(tempdir.path(), b"b").unwrap(),
+      [
+        (b"ba".to_vec(), b"baz".to_vec()),
+        (b"bb".to_vec(), b"bar".to_vec()),
+      ]
+      .into_iter()
+      .collect(),
+    );
+  }
+
+  #[test]
+  fn incomplete_log_records_are_ignored() {
+    let tempdir = TempDir::new().unwrap();
+
+    let path = tempdir.path().join("000003.log");
+
+    write_log(&path, 1, &[(b"ba", Some(b"foo"))]);
+
+    let mut log = fs::read(&path).unwrap();
+    log.truncate(log.len() - 1);
+    fs::write(&path, log).unwrap();
+
+    assert_eq!(read(tempdir.path(), b"b").unwrap(), HashMap::new());
+  }
+}
@@ -0,0 +1,74 @@
+use {super::*, block_files::BlockFiles};
+
+/// Blocks within this many blocks of the tip of Litecoin Core's block index
+/// are fetched over RPC, since they are the most likely to be reorged, and
+/// the block index may not have been flushed to disk since they were stored.
+pub(crate) const TIP_DISTANCE: u32 = 100;
+
+/// A source of blocks for the updater. Block hashes always come from Litecoin
+/// Core over RPC, so every source follows Litecoin Core's active chain.
+pub(crate) trait BlockSource: Send {
+  fn block_hash(&mut self, height: u32) -> Result<Option<BlockHash>>;
+
+  fn block(&mut self, height: u32, hash: BlockHash) -> Result<Block>;
+
+  fn header(&mut self, hash: BlockHash) -> Result<Header>;
+}
+
+impl BlockSource for Client {
+  fn block_hash(&mut self, height: u32) -> Result<Option<BlockHash>> {
+    self.get_block_hash(height.into()).into_option()
+  }
+
+  fn block(&mut self, _height: u32, hash: BlockHash) -> Result<Block> {
+    Ok(self.get_block(&hash)?)
+  }
+
+  fn header(&mut self, hash: BlockHash) -> Result<Header> {
+    Ok(self.get_block_header(&hash)?)
+  }
+}
+
+/// Reads blocks from Litecoin Core's block files, and fetches blocks near the
+/// tip, and any which can't be read from the block files, over RPC.
+pub(crate) struct BlockFileSource {
+  block_files: BlockFiles,
+  client: Client,
+}
+
+impl BlockFileSource {
+  pub(crate) fn open(dir: PathBuf, client: Client) -> Result<Self> {
+    Ok(Self {
+      block_files: BlockFiles::open(dir)?,
+      client,
+    })
+  }
+}
+
+impl BlockSource for BlockFileSource {
+  fn block_hash(&mut self, height: u32) -> Result<Option<BlockHash>> {
+    self.client.block_hash(height)
+  }
+
+  fn block(&mut self, height: u32, hash: BlockHash) -> Result<Block> {
+    if self
+      .block_files
+      .height()
+      .is_some_and(|tip| height.saturating_add(TIP_DISTANCE) <= tip)
+    {
+      match self.block_files.get(hash) {
+        Ok(Some(block)) => return Ok(block),
+        Ok(None) => {}
+        Err(err) => {
+          log::warn!("failed to read block {hash} from block files: {err}");
+        }
+      }
+    }
+
+    self.client.block(height, hash)
+  }
+
+  fn header(&mut self, hash: BlockHash) -> Result<Header> {
+    self.client.header(hash)
+  }
+}
@@ -288,6 +288,209 @@ impl Entry for RuneId {
   }
 }
 
+/// An output which received or spent a balance of a rune.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) struct RuneBalanceChange {
+  pub(crate) amount: u128,
+  pub(crate) height: u32,
+  pub(crate) outpoint: OutPoint,
+  pub(crate) spent: bool,
+}
+
+pub(super) type RuneBalanceChangeValue = (
+  u32,           // height
+  OutPointValue, // outpoint
+  u128,          // amount
+  bool,          // spent
+);
+
+impl Entry for RuneBalanceChange {
+  type Value = RuneBalanceChangeValue;
+
+  fn load((height, outpoint, amount, spent): RuneBalanceChangeValue) -> Self {
+    Self {
+      amount,
+      height,
+      outpoint: OutPoint::load(outpoint),
+      spent,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.height, self.outpoint.store(), self.amount, self.spent)
+  }
+}
+
+/// The state of a rune at the end of a block in which it was etched, minted,
+/// burned or transferred.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub(crate) struct RuneStatsEntry {
+  pub(crate) burned: u128,
+  pub(crate) holders: u64,
+  pub(crate) mints: u128,
+  pub(crate) supply: u128,
+}
+
+pub(super) type RuneStatsEntryValue = (
+  u128, // burned
+  u64,  // holders
+  u128, // mints
+  u128, // supply
+);
+
+impl Entry for RuneStatsEntry {
+  type Value = RuneStatsEntryValue;
+
+  fn load((burned, holders, mints, supply): RuneStatsEntryValue) -> Self {
+    Self {
+      burned,
+      holders,
+      mints,
+      supply,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.burned, self.holders, self.mints, self.supply)
+  }
+}
+
+/// The value, inscriptions and runes a script pubkey received and sent in a
+/// single transaction. Inscriptions are stored by sequence number.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) struct ActivityEntry {
+  pub(crate) height: u32,
+  pub(crate) inscriptions_received: Vec<u32>,
+  pub(crate) inscriptions_sent: Vec<u32>,
+  pub(crate) received: u64,
+  pub(crate) runes_received: Vec<(RuneId, u128)>,
+  pub(crate) runes_sent: Vec<(RuneId, u128)>,
+  pub(crate) sent: u64,
+  pub(crate) txid: Txid,
+}
+
+pub(super) type ActivityEntryValue = (
+  u32,                      // height
+  Vec<u32>,                 // inscriptions received
+  Vec<u32>,                 // inscriptions sent
+  u64,                      // received
+  Vec<(RuneIdValue, u128)>, // runes received
+  Vec<(RuneIdValue, u128)>, // runes sent
+  u64,                      // sent
+  TxidValue,                // txid
+);
+
+impl Entry for ActivityEntry {
+  type Value = ActivityEntryValue;
+
+  #[rustfmt::skip]
+  fn load(
+    (
+      height,
+      inscriptions_received,
+      inscriptions_sent,
+      received,
+      runes_received,
+      runes_sent,
+      sent,
+      txid,
+    ): ActivityEntryValue,
+  ) -> Self {
+    Self {
+      height,
+      inscriptions_received,
+      inscriptions_sent,
+      received,
+      runes_received: runes_received
+        .into_iter()
+        .map(|(id, amount)| (RuneId::load(id), amount))
+        .collect(),
+      runes_sent: runes_sent
+        .into_iter()
+        .map(|(id, amount)| (RuneId::load(id), amount))
+        .collect(),
+      sent,
+      txid: Txid::load(txid),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.height,
+      self.inscriptions_received,
+      self.inscriptions_sent,
+      self.received,
+      self
+        .runes_received
+        .into_iter()
+        .map(|(id, amount)| (id.store(), amount))
+        .collect(),
+      self
+        .runes_sent
+        .into_iter()
+        .map(|(id, amount)| (id.store(), amount))
+        .collect(),
+      self.sent,
+      self.txid.store(),
+    )
+  }
+}
+
+/// The properties of an inscription's content used to filter and sort
+/// inscription listings.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub(crate) struct ContentEntry {
+  pub(crate) content_length: Option<u64>,
+  pub(crate) content_type: Option<String>,
+  pub(crate) metaprotocol: Option<String>,
+}
+
+pub(super) type ContentEntryValue = (
+  Option<u64>,    // content length
+  Option<String>, // content type
+  Option<String>, // metaprotocol
+);
+
+impl ContentEntry {
+  pub(crate) fn new(inscription: &Inscription) -> Self {
+    Self {
+      content_length: inscription
+        .content_length()
+        .map(|length| length.try_into().unwrap()),
+      content_type: inscription.content_type().map(str::to_string),
+      metaprotocol: inscription.metaprotocol().map(str::to_string),
+    }
+  }
+
+  pub(crate) fn media(&self) -> Media {
+    if self.content_length.is_none() {
+      return Media::Unknown;
+    }
+
+    let Some(content_type) = &self.content_type else {
+      return Media::Unknown;
+    };
+
+    content_type.parse().unwrap_or(Media::Unknown)
+  }
+}
+
+impl Entry for ContentEntry {
+  type Value = ContentEntryValue;
+
+  fn load((content_length, content_type, metaprotocol): ContentEntryValue) -> Self {
+    Self {
+      content_length,
+      content_type,
+      metaprotocol,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.content_length, self.content_type, self.metaprotocol)
+  }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct InscriptionEntry {
   pub charms: u16,
@@ -475,6 +678,57 @@ impl Entry for SatRange {
   }
 }
 
+/// A change in the location of an inscription. Creation is recorded as a
+/// transfer without an old satpoint. The script pubkey is that of the output
+/// the inscription was transferred to, and is `None` for lost and unbound
+/// inscriptions.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) struct TransferEntry {
+  pub(crate) height: u32,
+  pub(crate) new_satpoint: SatPoint,
+  pub(crate) old_satpoint: Option<SatPoint>,
+  pub(crate) script_pubkey: Option<ScriptBuf>,
+  pub(crate) timestamp: u32,
+  pub(crate) txid: Txid,
+}
+
+pub(super) type TransferEntryValue = (
+  u32,                   // height
+  SatPointValue,         // new satpoint
+  Option<SatPointValue>, // old satpoint
+  Option<Vec<u8>>,       // script pubkey
+  u32,                   // timestamp
+  TxidValue,             // txid
+);
+
+impl Entry for TransferEntry {
+  type Value = TransferEntryValue;
+
+  fn load(
+    (height, new_satpoint, old_satpoint, script_pubkey, timestamp, txid): TransferEntryValue,
+  ) -> Self {
+    Self {
+      height,
+      new_satpoint: SatPoint::load(new_satpoint),
+      old_satpoint: old_satpoint.map(SatPoint::load),
+      script_pubkey: script_pubkey.map(ScriptBuf::from_bytes),
+      timestamp,
+      txid: Txid::load(txid),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.height,
+      self.new_satpoint.store(),
+      self.old_satpoint.map(SatPoint::store),
+      self.script_pubkey.map(ScriptBuf::into_bytes),
+      self.timestamp,
+      self.txid.store(),
+    )
+  }
+}
+
 pub(super) type TxidValue = [u8; 32];
 
 impl Entry for Txid {
@@ -517,6 +771,145 @@ mod tests {
     assert_eq!(InscriptionEntry::load(value), entry);
   }
 
+  #[test]
+  fn activity_entry() {
+    let entry = ActivityEntry {
+      height: 0,
+      inscriptions_received: vec![1, 2],
+      inscriptions_sent: vec![3],
+      received: 4,
+      runes_received: vec![(RuneId { block: 5, tx: 6 }, 7)],
+      runes_sent: vec![(RuneId { block: 8, tx: 9 }, 10)],
+      sent: 11,
+      txid: txid(12),
+    };
+
+    let value = (
+      0,
+      vec![1, 2],
+      vec![3],
+      4,
+      vec![((5, 6), 7)],
+      vec![((8, 9), 10)],
+      11,
+      txid(12).store(),
+    );
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(ActivityEntry::load(value), entry);
+  }
+
+  #[test]
+  fn content_entry() {
+    let entry = ContentEntry {
+      content_length: Some(1),
+      content_type: Some("text/plain".into()),
+      metaprotocol: Some("foo".into()),
+    };
+
+    let value = (Some(1), Some("text/plain".into()), Some("foo".into()));
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(ContentEntry::load(value), entry);
+
+    let entry = ContentEntry::default();
+
+    let value = (None, None, None);
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(ContentEntry::load(value), entry);
+  }
+
+  #[test]
+  fn content_entry_media() {
+    assert_eq!(
+      ContentEntry::new(&Inscription {
+        content_type: Some("image/png".into()),
+        body: Some(Vec::new()),
+        ..default()
+      })
+      .media(),
+      Media::Image(ImageRendering::Pixelated)
+    );
+
+    assert_eq!(
+      ContentEntry::new(&Inscription {
+        content_type: Some("image/png".into()),
+        ..default()
+      })
+      .media(),
+      Media::Unknown
+    );
+  }
+
+  #[test]
+  fn rune_balance_change() {
+    let change = RuneBalanceChange {
+      amount: 1,
+      height: 2,
+      outpoint: outpoint(3),
+      spent: true,
+    };
+
+    let value = (2, outpoint(3).store(), 1, true);
+
+    assert_eq!(change.clone().store(), value);
+    assert_eq!(RuneBalanceChange::load(value), change);
+  }
+
+  #[test]
+  fn rune_stats_entry() {
+    let entry = RuneStatsEntry {
+      burned: 1,
+      holders: 2,
+      mints: 3,
+      supply: 4,
+    };
+
+    let value = (1, 2, 3, 4);
+
+    assert_eq!(entry.store(), value);
+    assert_eq!(RuneStatsEntry::load(value), entry);
+  }
+
+  #[test]
+  fn transfer_entry() {
+    let new_satpoint = satpoint(1, 2);
+    let old_satpoint = satpoint(3, 4);
+
+    let entry = TransferEntry {
+      height: 0,
+      new_satpoint,
+      old_satpoint: Some(old_satpoint),
+      script_pubkey: Some(ScriptBuf::from_bytes(vec![0x51])),
+      timestamp: 5,
+      txid: txid(1),
+    };
+
+    let value = (
+      0,
+      new_satpoint.store(),
+      Some(old_satpoint.store()),
+      Some(vec![0x51]),
+      5,
+      txid(1).store(),
+    );
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(TransferEntry::load(value), entry);
+
+    let entry = TransferEntry {
+      old_satpoint: None,
+      script_pubkey: None,
+      ..entry
+    };
+
+    let value = (0, new_satpoint.store(), None, None, 5, txid(1).store());
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(TransferEntry::load(value), entry);
+  }
+
   #[test]
   fn inscription_id_entry() {
     let inscription_id = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdefi0"
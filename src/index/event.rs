@@ -1,6 +1,7 @@
 use super::*;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Event {
   InscriptionCreated {
     block_height: u32,
@@ -17,6 +18,9 @@ pub enum Event {
     old_location: SatPoint,
     sequence_number: u32,
   },
+  RolledBack {
+    event: Box<Event>,
+  },
   RuneBurned {
     amount: u128,
     block_height: u32,
@@ -42,3 +46,125 @@ pub enum Event {
     txid: Txid,
   },
 }
+
+impl Event {
+  pub const KINDS: [&'static str; 7] = [
+    "inscription_created",
+    "inscription_transferred",
+    "rolled_back",
+    "rune_burned",
+    "rune_etched",
+    "rune_minted",
+    "rune_transferred",
+  ];
+
+  pub fn kind(&self) -> &'static str {
+    match self {
+      Self::InscriptionCreated { .. } => "inscription_created",
+      Self::InscriptionTransferred { .. } => "inscription_transferred",
+      Self::RolledBack { .. } => "rolled_back",
+      Self::RuneBurned { .. } => "rune_burned",
+      Self::RuneEtched { .. } => "rune_etched",
+      Self::RuneMinted { .. } => "rune_minted",
+      Self::RuneTransferred { .. } => "rune_transferred",
+    }
+  }
+
+  pub fn block_height(&self) -> u32 {
+    match self {
+      Self::InscriptionCreated { block_height, .. }
+      | Self::InscriptionTransferred { block_height, .. }
+      | Self::RuneBurned { block_height, .. }
+      | Self::RuneEtched { block_height, .. }
+      | Self::RuneMinted { block_height, .. }
+      | Self::RuneTransferred { block_height, .. } => *block_height,
+      Self::RolledBack { event } => event.block_height(),
+    }
+  }
+
+  pub fn inscription_id(&self) -> Option<InscriptionId> {
+    match self {
+      Self::InscriptionCreated { inscription_id, .. }
+      | Self::InscriptionTransferred { inscription_id, .. } => Some(*inscription_id),
+      Self::RolledBack { event } => event.inscription_id(),
+      _ => None,
+    }
+  }
+
+  pub fn rune_id(&self) -> Option<RuneId> {
+    match self {
+      Self::RuneBurned { rune_id, .. }
+      | Self::RuneEtched { rune_id, .. }
+      | Self::RuneMinted { rune_id, .. }
+      | Self::RuneTransferred { rune_id, .. } => Some(*rune_id),
+      Self::RolledBack { event } => event.rune_id(),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn kinds_match_serialized_names() {
+    let events = [
+      Event::InscriptionCreated {
+        block_height: 0,
+        charms: 0,
+        inscription_id: inscription_id(1),
+        location: None,
+        parent_inscription_ids: Vec::new(),
+        sequence_number: 0,
+      },
+      Event::InscriptionTransferred {
+        block_height: 0,
+        inscription_id: inscription_id(1),
+        new_location: satpoint(1, 0),
+        old_location: satpoint(2, 0),
+        sequence_number: 0,
+      },
+      Event::RolledBack {
+        event: Box::new(Event::RuneEtched {
+          block_height: 0,
+          rune_id: RuneId::default(),
+          txid: txid(1),
+        }),
+      },
+      Event::RuneBurned {
+        amount: 0,
+        block_height: 0,
+        rune_id: RuneId::default(),
+        txid: txid(1),
+      },
+      Event::RuneEtched {
+        block_height: 0,
+        rune_id: RuneId::default(),
+        txid: txid(1),
+      },
+      Event::RuneMinted {
+        amount: 0,
+        block_height: 0,
+        rune_id: RuneId::default(),
+        txid: txid(1),
+      },
+      Event::RuneTransferred {
+        amount: 0,
+        block_height: 0,
+        outpoint: outpoint(1),
+        rune_id: RuneId::default(),
+        txid: txid(1),
+      },
+    ];
+
+    for (event, kind) in events.iter().zip(Event::KINDS) {
+      assert_eq!(event.kind(), kind);
+
+      let json = serde_json::to_value(event).unwrap();
+      assert!(json.get(kind).is_some(), "{json}");
+
+      assert_eq!(&serde_json::from_value::<Event>(json).unwrap(), event);
+    }
+  }
+}
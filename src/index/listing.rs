@@ -0,0 +1,250 @@
+use super::*;
+
+/// The most inscriptions examined for a single page of a listing, which
+/// bounds the cost of filters which rarely match.
+pub(crate) const MAX_CANDIDATES: usize = 10_000;
+
+/// Narrows both full-text search results and inscription listings. Every
+/// field which is set must match.
+#[derive(Debug, Default)]
+pub(crate) struct InscriptionFilter {
+  pub(crate) charm: Option<Charm>,
+  pub(crate) content_type: Option<String>,
+  pub(crate) cursed: Option<bool>,
+  pub(crate) max_height: Option<u32>,
+  pub(crate) media: Option<String>,
+  pub(crate) metaprotocol: Option<String>,
+  pub(crate) min_height: Option<u32>,
+  pub(crate) parent: Option<InscriptionId>,
+  pub(crate) rarity: Option<Rarity>,
+}
+
+impl InscriptionFilter {
+  /// Whether this filter can only match inscriptions with content entries.
+  pub(crate) fn needs_details(&self) -> bool {
+    self.content_type.is_some() || self.media.is_some() || self.metaprotocol.is_some()
+  }
+
+  pub(crate) fn is_empty(&self) -> bool {
+    self.charm.is_none()
+      && self.content_type.is_none()
+      && self.cursed.is_none()
+      && self.max_height.is_none()
+      && self.media.is_none()
+      && self.metaprotocol.is_none()
+      && self.min_height.is_none()
+      && self.parent.is_none()
+      && self.rarity.is_none()
+  }
+
+  /// Filters on content types, media and metaprotocols never match
+  /// inscriptions without a content entry, which are only recorded with
+  /// `--index-inscription-details`.
+  pub(crate) fn matches(
+    &self,
+    entry: &InscriptionEntry,
+    content: Option<&ContentEntry>,
+    parent: Option<u32>,
+  ) -> bool {
+    self.min_height.map_or(true, |min| entry.height >= min)
+      && self.max_height.map_or(true, |max| entry.height <= max)
+      && self.charm.map_or(true, |charm| charm.is_set(entry.charms))
+      && self
+        .cursed
+        .map_or(true, |cursed| (entry.inscription_number < 0) == cursed)
+      && self
+        .rarity
+        .map_or(true, |rarity| entry.sat.map(Sat::rarity) == Some(rarity))
+      && parent.map_or(true, |parent| entry.parents.contains(&parent))
+      && self.content_type.as_deref().map_or(true, |expected| {
+        content
+          .and_then(|content| content.content_type.as_deref())
+          .is_some_and(|actual| content_type_matches(actual, expected))
+      })
+      && self.media.as_deref().map_or(true, |media| {
+        content.is_some_and(|content| content.media().kind() == media)
+      })
+      && self.metaprotocol.as_deref().map_or(true, |metaprotocol| {
+        content.and_then(|content| content.metaprotocol.as_deref()) == Some(metaprotocol)
+      })
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum InscriptionSort {
+  Fee,
+  #[default]
+  Height,
+  Number,
+  Size,
+}
+
+impl InscriptionSort {
+  /// Whether sorting requires the fee and content length tables.
+  pub(crate) fn needs_details(self) -> bool {
+    matches!(self, Self::Fee | Self::Size)
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SortOrder {
+  Asc,
+  #[default]
+  Desc,
+}
+
+/// The essence of a content type, `text/plain` for
+/// `text/plain;charset=utf-8`.
+pub(crate) fn essence(content_type: &str) -> String {
+  content_type
+    .split(';')
+    .next()
+    .unwrap_or_default()
+    .trim()
+    .to_lowercase()
+}
+
+/// A content type matches either its essence or its top-level type, so
+/// `text/plain;charset=utf-8` matches both `text/plain` and `text`.
+fn content_type_matches(actual: &str, expected: &str) -> bool {
+  let actual = essence(actual);
+  let expected = essence(expected);
+
+  if expected.contains('/') {
+    actual == expected
+  } else {
+    actual.split('/').next() == Some(expected.as_str())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn filter_matches() {
+    let mut charms = 0;
+    Charm::Cursed.set(&mut charms);
+
+    let entry = InscriptionEntry {
+      charms,
+      fee: 0,
+      height: 10,
+      id: inscription_id(1),
+      inscription_number: -1,
+      parents: vec![3],
+      sat: Some(Sat(0)),
+      sequence_number: 5,
+      timestamp: 0,
+    };
+
+    let content = ContentEntry {
+      content_length: Some(3),
+      content_type: Some("text/plain;charset=utf-8".into()),
+      metaprotocol: Some("foo".into()),
+    };
+
+    assert!(InscriptionFilter::default().matches(&entry, Some(&content), None));
+
+    assert!(InscriptionFilter {
+      charm: Some(Charm::Cursed),
+      content_type: Some("text/plain".into()),
+      cursed: Some(true),
+      max_height: Some(10),
+      media: Some("text".into()),
+      metaprotocol: Some("foo".into()),
+      min_height: Some(10),
+      rarity: Some(Rarity::Mythic),
+      ..default()
+    }
+    .matches(&entry, Some(&content), Some(3)));
+
+    assert!(InscriptionFilter {
+      content_type: Some("text".into()),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      min_height: Some(11),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      max_height: Some(9),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      charm: Some(Charm::Vindicated),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      cursed: Some(false),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      rarity: Some(Rarity::Common),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      content_type: Some("text/html".into()),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      content_type: Some("tex".into()),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      media: Some("image".into()),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter {
+      metaprotocol: Some("bar".into()),
+      ..default()
+    }
+    .matches(&entry, Some(&content), None));
+
+    assert!(!InscriptionFilter::default().matches(&entry, Some(&content), Some(4)));
+
+    assert!(InscriptionFilter {
+      charm: Some(Charm::Cursed),
+      ..default()
+    }
+    .matches(&entry, None, None));
+
+    assert!(!InscriptionFilter {
+      content_type: Some("text".into()),
+      ..default()
+    }
+    .matches(&entry, None, None));
+
+    assert!(!InscriptionFilter {
+      media: Some("text".into()),
+      ..default()
+    }
+    .matches(&entry, None, None));
+
+    assert!(!InscriptionFilter {
+      metaprotocol: Some("foo".into()),
+      ..default()
+    }
+    .matches(&entry, None, None));
+  }
+}
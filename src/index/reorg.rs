@@ -19,10 +19,6 @@ impl Display for Error {
 
 impl std::error::Error for Error {}
 
-const MAX_SAVEPOINTS: u32 = 2;
-const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 21;
-
 pub(crate) struct Reorg {}
 
 impl Reorg {
@@ -32,8 +28,13 @@ impl Reorg {
     match index.block_hash(height.checked_sub(1))? {
       Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
       Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
-        let max_recoverable_reorg_depth =
-          (MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
+        let max_recoverable_reorg_depth = match index.settings.undo_depth() {
+          Some(undo_depth) => undo_depth + 1,
+          None => {
+            let interval = index.settings.savepoint_interval();
+            (index.settings.max_savepoints() - 1) * interval + height % interval
+          }
+        };
 
         for depth in 1..max_recoverable_reorg_depth {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
@@ -43,6 +44,12 @@ impl Reorg {
             .into_option()?;
 
           if index_block_hash == bitcoind_block_hash {
+            if index.settings.undo_depth().is_some()
+              && UndoLog::oldest(index)?.map_or(true, |oldest| oldest > height - depth + 1)
+            {
+              break;
+            }
+
             return Err(anyhow!(reorg::Error::Recoverable { height, depth }));
           }
         }
@@ -56,16 +63,71 @@ impl Reorg {
   pub(crate) fn handle_reorg(index: &Index, height: u32, depth: u32) -> Result {
     log::info!("rolling back database after reorg of depth {depth} at height {height}");
 
-    if let redb::Durability::None = index.durability {
-      panic!("set index durability to `Durability::Immediate` to test reorg handling");
+    Self::roll_back(
+      index,
+      index
+        .settings
+        .undo_depth()
+        .is_some()
+        .then_some(height - depth + 1),
+    )
+  }
+
+  pub(crate) fn roll_back_to_height(index: &Index, height: u32) -> Result {
+    let block_count = index.block_count()?;
+
+    ensure!(
+      height < block_count,
+      "cannot roll back index to height {height}, which has not been indexed",
+    );
+
+    if height + 1 == block_count {
+      return Ok(());
     }
 
+    match UndoLog::oldest(index)? {
+      Some(oldest) if oldest <= height + 1 => {}
+      Some(oldest) => bail!(
+        "cannot roll back index to height {height}, undo log only reaches back to height {}",
+        oldest.saturating_sub(1),
+      ),
+      None => bail!(
+        "cannot roll back index without an undo log, update the index with `--undo-depth` to keep one"
+      ),
+    }
+
+    log::info!("rolling back database to height {height}");
+
+    Self::roll_back(index, Some(height + 1))
+  }
+
+  /// Roll back the database to `block_count` blocks using the undo log, or to
+  /// the oldest savepoint if `None`.
+  fn roll_back(index: &Index, block_count: Option<u32>) -> Result {
     let mut wtx = index.begin_write()?;
 
-    let oldest_savepoint =
-      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+    let logged = if Index::has_webhooks(&wtx)? {
+      Some(Self::logged_events(&wtx)?)
+    } else {
+      None
+    };
 
-    wtx.restore_savepoint(&oldest_savepoint)?;
+    if let Some(block_count) = block_count {
+      UndoLog::undo(&wtx, block_count)?;
+    } else {
+      if let redb::Durability::None = index.durability {
+        panic!("set index durability to `Durability::Immediate` to test reorg handling");
+      }
+
+      let oldest_savepoint =
+        wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+
+      wtx.restore_savepoint(&oldest_savepoint)?;
+    }
+
+    if let Some((events, webhooks)) = logged {
+      Self::roll_back_events(index, &wtx, events, webhooks)?;
+    }
 
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
@@ -83,7 +145,30 @@ impl Reorg {
       return Ok(());
     }
 
-    if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
+    // savepoints are superseded by the undo log
+    if index.settings.undo_depth().is_some() {
+      let wtx = index.begin_write()?;
+
+      let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+
+      if savepoints.is_empty() {
+        return Ok(());
+      }
+
+      for savepoint in savepoints {
+        wtx.delete_persistent_savepoint(savepoint)?;
+      }
+
+      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+      wtx.commit()?;
+
+      return Ok(());
+    }
+
+    let interval = index.settings.savepoint_interval();
+    let max_savepoints = index.settings.max_savepoints();
+
+    if (height < interval || height % interval == 0)
       && u32::try_from(
         index
           .settings
@@ -93,13 +178,13 @@ impl Reorg {
       )
       .unwrap()
       .saturating_sub(height)
-        <= CHAIN_TIP_DISTANCE
+        <= Self::chain_tip_distance(&index.settings)
     {
       let wtx = index.begin_write()?;
 
       let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
 
-      if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
+      if savepoints.len() >= max_savepoints.into_usize() {
         wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
       }
 
@@ -117,4 +202,121 @@ impl Reorg {
 
     Ok(())
   }
+
+  /// Savepoints are only created within this many blocks of the chain tip.
+  fn chain_tip_distance(settings: &Settings) -> u32 {
+    settings.max_savepoints() * settings.savepoint_interval() + 1
+  }
+
+  pub(crate) fn prune_events(index: &Index, wtx: &WriteTransaction, height: u32) -> Result {
+    let settings = &index.settings;
+
+    let oldest_retained_height = height.saturating_sub(
+      match settings.undo_depth() {
+        Some(undo_depth) => undo_depth,
+        None => settings.max_savepoints() * settings.savepoint_interval(),
+      } + Self::chain_tip_distance(settings),
+    );
+
+    let acknowledged = wtx
+      .open_table(WEBHOOK_TO_EVENT_NUMBER)?
+      .iter()?
+      .map(|result| result.map(|(_webhook, event_number)| event_number.value()))
+      .collect::<Result<Vec<u64>, StorageError>>()?
+      .into_iter()
+      .min()
+      .unwrap_or(u64::MAX);
+
+    let mut event_number_to_event = wtx.open_table(EVENT_NUMBER_TO_EVENT)?;
+
+    // the most recent event is always kept, since new events are numbered
+    // after it
+    let Some(last) = event_number_to_event
+      .last()?
+      .map(|(event_number, _event)| event_number.value())
+    else {
+      return Ok(());
+    };
+
+    let mut expired = Vec::new();
+
+    for result in event_number_to_event.iter()? {
+      let (event_number, event) = result?;
+
+      if event_number.value() >= acknowledged.min(last)
+        || serde_json::from_slice::<Event>(event.value())?.block_height() >= oldest_retained_height
+      {
+        break;
+      }
+
+      expired.push(event_number.value());
+    }
+
+    for event_number in expired {
+      event_number_to_event.remove(event_number)?;
+    }
+
+    Ok(())
+  }
+
+  fn logged_events(wtx: &WriteTransaction) -> Result<(Vec<(u64, Event)>, Vec<(String, u64)>)> {
+    let events = wtx
+      .open_table(EVENT_NUMBER_TO_EVENT)?
+      .iter()?
+      .map(|result| {
+        let (event_number, event) = result?;
+        Ok((event_number.value(), serde_json::from_slice(event.value())?))
+      })
+      .collect::<Result<Vec<(u64, Event)>>>()?;
+
+    let webhooks = wtx
+      .open_table(WEBHOOK_TO_EVENT_NUMBER)?
+      .iter()?
+      .map(|result| {
+        result.map(|(webhook, event_number)| (webhook.value().to_string(), event_number.value()))
+      })
+      .collect::<Result<Vec<(String, u64)>, StorageError>>()?;
+
+    Ok((events, webhooks))
+  }
+
+  fn roll_back_events(
+    index: &Index,
+    wtx: &WriteTransaction,
+    events: Vec<(u64, Event)>,
+    webhooks: Vec<(String, u64)>,
+  ) -> Result {
+    let mut event_number_to_event = wtx.open_table(EVENT_NUMBER_TO_EVENT)?;
+    let mut webhook_to_event_number = wtx.open_table(WEBHOOK_TO_EVENT_NUMBER)?;
+
+    let restored = event_number_to_event
+      .last()?
+      .map(|(event_number, _event)| event_number.value() + 1)
+      .unwrap_or_default();
+
+    // webhooks resume from where they were before the reorg, or from the
+    // first rolled back event, whichever is earlier
+    for (webhook, event_number) in webhooks {
+      webhook_to_event_number.insert(webhook.as_str(), event_number.min(restored))?;
+    }
+
+    let mut next = restored;
+
+    for (_, event) in events.into_iter().rev().filter(|(event_number, event)| {
+      *event_number >= restored && !matches!(event, Event::RolledBack { .. })
+    }) {
+      let event = Event::RolledBack {
+        event: Box::new(event),
+      };
+
+      event_number_to_event.insert(next, serde_json::to_vec(&event)?.as_slice())?;
+      next += 1;
+
+      if let Some(sender) = &index.event_sender {
+        sender.blocking_send(event)?;
+      }
+    }
+
+    Ok(())
+  }
 }
@@ -0,0 +1,183 @@
+use {super::*, ciborium::Value, std::str};
+
+/// The most index rows examined for a single page of search results, which
+/// bounds the cost of queries whose terms and filters rarely match.
+pub(crate) const MAX_CANDIDATES: usize = 10_000;
+
+const MAX_TERM_LENGTH: usize = 64;
+
+/// Split text into lowercase alphanumeric terms.
+pub(crate) fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+  text
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|term| !term.is_empty() && term.len() <= MAX_TERM_LENGTH)
+    .map(str::to_lowercase)
+}
+
+/// Content types are indexed under both their essence, `text/plain`, and
+/// their top-level type, `text`. Terms contain a colon, so they can't collide
+/// with terms produced by `terms`.
+pub(crate) fn content_type_term(content_type: &str) -> String {
+  format!("content_type:{}", listing::essence(content_type))
+}
+
+pub(crate) fn inscription_terms(inscription: &Inscription) -> BTreeSet<String> {
+  let mut terms = BTreeSet::new();
+
+  if let Some(content_type) = inscription.content_type() {
+    let term = content_type_term(content_type);
+
+    if let Some((top_level, _subtype)) = term.split_once('/') {
+      terms.insert(top_level.into());
+    }
+
+    terms.insert(term);
+  }
+
+  if inscription.content_encoding().is_none()
+    && matches!(
+      inscription.media(),
+      Media::Code(_) | Media::Iframe | Media::Markdown | Media::Text
+    )
+  {
+    if let Some(text) = inscription
+      .body()
+      .and_then(|body| str::from_utf8(body).ok())
+    {
+      terms.extend(self::terms(text));
+    }
+  }
+
+  if let Some(metadata) = inscription.metadata() {
+    metadata_terms(&metadata, &mut terms);
+  }
+
+  if let Some(metaprotocol) = inscription.metaprotocol() {
+    terms.extend(self::terms(metaprotocol));
+  }
+
+  terms
+}
+
+fn metadata_terms(value: &Value, terms: &mut BTreeSet<String>) {
+  match value {
+    Value::Array(values) => {
+      for value in values {
+        metadata_terms(value, terms);
+      }
+    }
+    Value::Integer(integer) => {
+      terms.insert(i128::from(*integer).to_string());
+    }
+    Value::Map(entries) => {
+      for (key, value) in entries {
+        metadata_terms(key, terms);
+        metadata_terms(value, terms);
+      }
+    }
+    Value::Tag(_tag, value) => metadata_terms(value, terms),
+    Value::Text(text) => terms.extend(self::terms(text)),
+    _ => {}
+  }
+}
+
+/// Runes are indexed under their name without spacers, as well as each
+/// spacer-separated part of their name.
+pub(crate) fn rune_terms(spaced_rune: SpacedRune) -> BTreeSet<String> {
+  let mut terms = self::terms(&spaced_rune.to_string()).collect::<BTreeSet<String>>();
+  terms.insert(spaced_rune.rune.to_string().to_lowercase());
+  terms
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn text_terms() {
+    assert_eq!(
+      terms("Hello, World! héllo 42 ").collect::<Vec<String>>(),
+      ["hello", "world", "héllo", "42"]
+    );
+
+    assert_eq!(terms(&"a".repeat(65)).count(), 0);
+  }
+
+  #[test]
+  fn text_inscription_terms() {
+    assert_eq!(
+      inscription_terms(&inscription("text/plain;charset=utf-8", "Foo bar foo")),
+      ["bar", "content_type:text", "content_type:text/plain", "foo"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    );
+  }
+
+  #[test]
+  fn bodies_of_binary_and_compressed_inscriptions_are_not_indexed() {
+    assert_eq!(
+      inscription_terms(&inscription("image/png", "foo")),
+      ["content_type:image", "content_type:image/png"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    );
+
+    assert_eq!(
+      inscription_terms(&Inscription {
+        content_encoding: Some("br".into()),
+        ..inscription("text/plain", "foo")
+      }),
+      ["content_type:text", "content_type:text/plain"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    );
+  }
+
+  #[test]
+  fn metadata_and_metaprotocol_terms() {
+    let mut metadata = Vec::new();
+
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (Value::Text("Name".into()), Value::Text("Quiet Cat".into())),
+        (
+          Value::Text("traits".into()),
+          Value::Array(vec![Value::Integer(7.into())]),
+        ),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    assert_eq!(
+      inscription_terms(&Inscription {
+        metadata: Some(metadata),
+        metaprotocol: Some("brc-20".into()),
+        ..default()
+      }),
+      ["20", "7", "brc", "cat", "name", "quiet", "traits"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    );
+  }
+
+  #[test]
+  fn spaced_rune_terms() {
+    assert_eq!(
+      rune_terms("UNCOMMON•GOODS".parse().unwrap()),
+      ["goods", "uncommon", "uncommongoods"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    );
+
+    assert_eq!(
+      rune_terms("AAAA".parse().unwrap()),
+      ["aaaa"].into_iter().map(String::from).collect()
+    );
+  }
+}
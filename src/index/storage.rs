@@ -0,0 +1,248 @@
+use {
+  super::*,
+  redb::{ReadTransaction, StorageBackend, TransactionError},
+  std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    sync::RwLock,
+  },
+};
+
+/// The database an index is stored in.
+///
+/// With snapshot reload, the index is a snapshot published by another
+/// process, usually `ord server --snapshot`, and is opened read-only. Since
+/// snapshots are published by replacing the snapshot file rather than
+/// modifying it, the snapshot is reopened whenever the file changes.
+///
+/// An index file which another process is still writing to cannot be opened
+/// this way, since redb reuses pages freed by earlier commits, which a reader
+/// in another process would not know were still in use.
+pub(crate) struct Storage {
+  cache_size: usize,
+  database: RwLock<Database>,
+  modified: Mutex<SystemTime>,
+  path: PathBuf,
+  reload: bool,
+}
+
+impl Storage {
+  pub(crate) fn new(
+    database: Database,
+    path: PathBuf,
+    cache_size: usize,
+    reload: bool,
+  ) -> Result<Self> {
+    Ok(Self {
+      cache_size,
+      database: RwLock::new(database),
+      modified: Mutex::new(Self::modified(&path)?),
+      path,
+      reload,
+    })
+  }
+
+  /// Open the snapshot at `path` without writing to it.
+  pub(crate) fn open_read_only(path: &Path, cache_size: usize) -> Result<Database> {
+    Ok(
+      Database::builder()
+        .set_cache_size(cache_size)
+        .create_with_backend(ReadOnlyBackend::open(path)?)?,
+    )
+  }
+
+  pub(crate) fn begin_read(&self) -> Result<ReadTransaction, TransactionError> {
+    self.database.read().unwrap().begin_read()
+  }
+
+  pub(crate) fn begin_write(&self) -> Result<WriteTransaction> {
+    ensure!(
+      !self.reload,
+      "index snapshot at `{}` is read-only",
+      self.path.display(),
+    );
+
+    Ok(self.database.read().unwrap().begin_write()?)
+  }
+
+  pub(crate) fn is_reloaded(&self) -> bool {
+    self.reload
+  }
+
+  /// Reopen the snapshot if it has been replaced since it was last opened,
+  /// returning whether it was reopened.
+  pub(crate) fn reload(&self) -> Result<bool> {
+    if !self.reload {
+      return Ok(false);
+    }
+
+    let mut modified = self.modified.lock().unwrap();
+
+    let current = Self::modified(&self.path)?;
+
+    if current == *modified {
+      return Ok(false);
+    }
+
+    let database = Self::open_read_only(&self.path, self.cache_size)
+      .with_context(|| format!("failed to reopen index snapshot `{}`", self.path.display()))?;
+
+    Index::check_schema(&database, &self.path)?;
+
+    *self.database.write().unwrap() = database;
+
+    *modified = current;
+
+    log::info!("reopened index snapshot `{}`", self.path.display());
+
+    Ok(true)
+  }
+
+  fn modified(path: &Path) -> Result<SystemTime> {
+    Ok(fs::metadata(path)?.modified()?)
+  }
+}
+
+/// A redb storage backend which reads from a file opened read-only. redb
+/// writes to the database header when opening and closing a database, so
+/// writes are kept in memory and never reach the file.
+#[derive(Debug)]
+struct ReadOnlyBackend {
+  file: Mutex<File>,
+  file_len: Mutex<u64>,
+  len: Mutex<u64>,
+  writes: Mutex<Vec<(u64, Vec<u8>)>>,
+}
+
+impl ReadOnlyBackend {
+  fn open(path: &Path) -> io::Result<Self> {
+    let file = File::open(path)?;
+
+    let len = file.metadata()?.len();
+
+    if len == 0 {
+      return Err(io::ErrorKind::InvalidData.into());
+    }
+
+    Ok(Self {
+      file: Mutex::new(file),
+      file_len: Mutex::new(len),
+      len: Mutex::new(len),
+      writes: Mutex::new(Vec::new()),
+    })
+  }
+}
+
+impl StorageBackend for ReadOnlyBackend {
+  fn len(&self) -> io::Result<u64> {
+    Ok(*self.len.lock().unwrap())
+  }
+
+  fn read(&self, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+    let mut buffer = vec![0; len];
+
+    let file_len = *self.file_len.lock().unwrap();
+
+    // bytes past the end of the file were added by `set_len`, and are zero
+    if offset < file_len {
+      let available = usize::try_from(file_len - offset)
+        .unwrap_or(usize::MAX)
+        .min(len);
+
+      let mut file = self.file.lock().unwrap();
+      file.seek(SeekFrom::Start(offset))?;
+      file.read_exact(&mut buffer[..available])?;
+    }
+
+    let end = offset + u64::try_from(len).unwrap();
+
+    for (write_offset, data) in self.writes.lock().unwrap().iter() {
+      let write_end = write_offset + u64::try_from(data.len()).unwrap();
+
+      if *write_offset >= end || write_end <= offset {
+        continue;
+      }
+
+      let start = offset.max(*write_offset);
+      let stop = end.min(write_end);
+
+      buffer[usize::try_from(start - offset).unwrap()..usize::try_from(stop - offset).unwrap()]
+        .copy_from_slice(
+          &data[usize::try_from(start - write_offset).unwrap()
+            ..usize::try_from(stop - write_offset).unwrap()],
+        );
+    }
+
+    Ok(buffer)
+  }
+
+  fn set_len(&self, len: u64) -> io::Result<()> {
+    let mut file_len = self.file_len.lock().unwrap();
+    *file_len = (*file_len).min(len);
+
+    // bytes past the new length are discarded, and read as zero if the
+    // backend grows again
+    let mut writes = self.writes.lock().unwrap();
+    writes.retain(|(offset, _)| *offset < len);
+    for (offset, data) in writes.iter_mut() {
+      data.truncate(usize::try_from(len - *offset).unwrap_or(usize::MAX));
+    }
+
+    *self.len.lock().unwrap() = len;
+
+    Ok(())
+  }
+
+  fn sync_data(&self, _eventual: bool) -> io::Result<()> {
+    Ok(())
+  }
+
+  fn write(&self, offset: u64, data: &[u8]) -> io::Result<()> {
+    let mut writes = self.writes.lock().unwrap();
+
+    let end = offset + u64::try_from(data.len()).unwrap();
+
+    // drop earlier writes which this one completely overwrites
+    writes.retain(|(write_offset, write)| {
+      *write_offset < offset || write_offset + u64::try_from(write.len()).unwrap() > end
+    });
+
+    writes.push((offset, data.to_vec()));
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn read_only_backend_does_not_write_to_file() {
+    let tempdir = TempDir::new().unwrap();
+
+    let path = tempdir.path().join("file");
+
+    fs::write(&path, [1, 2, 3, 4]).unwrap();
+
+    let backend = ReadOnlyBackend::open(&path).unwrap();
+
+    backend.write(1, &[5, 6]).unwrap();
+    backend.set_len(6).unwrap();
+    backend.write(4, &[7]).unwrap();
+
+    assert_eq!(backend.len().unwrap(), 6);
+    assert_eq!(backend.read(0, 6).unwrap(), [1, 5, 6, 4, 7, 0]);
+
+    backend.write(0, &[8, 8, 8]).unwrap();
+
+    assert_eq!(backend.read(0, 4).unwrap(), [8, 8, 8, 4]);
+
+    backend.set_len(3).unwrap();
+    backend.set_len(6).unwrap();
+
+    assert_eq!(backend.read(0, 6).unwrap(), [8, 8, 8, 0, 0, 0]);
+
+    assert_eq!(fs::read(&path).unwrap(), [1, 2, 3, 4]);
+  }
+}
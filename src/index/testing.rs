@@ -134,6 +134,35 @@ impl Context {
 
     pretty_assert_eq!(balances, self.index.get_rune_balances().unwrap());
 
+    let rtx = self.index.database.begin_read().unwrap();
+
+    let mut holders = rtx
+      .open_table(RUNE_ID_BALANCE_AND_OUTPOINT)
+      .unwrap()
+      .iter()
+      .unwrap()
+      .map(|result| {
+        let (key, _value) = result.unwrap();
+        let (id, balance, outpoint) = key.value();
+        (RuneId::load(id), balance, OutPoint::load(outpoint))
+      })
+      .collect::<Vec<(RuneId, u128, OutPoint)>>();
+
+    holders.sort();
+
+    let mut expected = balances
+      .iter()
+      .flat_map(|(outpoint, balances)| {
+        balances
+          .iter()
+          .map(|(id, balance)| (*id, *balance, *outpoint))
+      })
+      .collect::<Vec<(RuneId, u128, OutPoint)>>();
+
+    expected.sort();
+
+    pretty_assert_eq!(holders, expected);
+
     let mut outstanding: HashMap<RuneId, u128> = HashMap::new();
 
     for (_, balances) in balances {
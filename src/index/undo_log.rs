@@ -0,0 +1,453 @@
+use {
+  super::*,
+  redb::{AccessGuard, Key, TableError, Value},
+  std::{borrow::Borrow, cell::RefCell, ops::Deref, rc::Rc},
+};
+
+/// The previous contents of every key changed by indexing a block, in the order
+/// they were changed.
+#[derive(Default, Deserialize, Serialize)]
+struct Changes {
+  multimap_tables: BTreeMap<String, Vec<(Vec<u8>, Vec<u8>, bool)>>,
+  tables: BTreeMap<String, Vec<(Vec<u8>, Option<Vec<u8>>)>>,
+}
+
+impl Changes {
+  fn undo(self, wtx: &WriteTransaction) -> Result {
+    for (name, changes) in self.tables {
+      macro_rules! undo {
+        ($($table:ident),* $(,)?) => {
+          match name.as_str() {
+            $(name if name == $table.name() => Self::undo_table(wtx, $table, changes)?,)*
+            name => bail!("undo log contains changes to unknown table `{name}`"),
+          }
+        };
+      }
+
+      undo!(
+        ACTIVITY_NUMBER_TO_ACTIVITY,
+        CONTENT_LENGTH_AND_SEQUENCE_NUMBER,
+        EVENT_NUMBER_TO_EVENT,
+        FEE_AND_SEQUENCE_NUMBER,
+        HEIGHT_TO_BLOCK_HEADER,
+        HEIGHT_TO_LAST_SEQUENCE_NUMBER,
+        HOME_INSCRIPTIONS,
+        INSCRIPTION_ID_TO_SEQUENCE_NUMBER,
+        INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
+        OUTPOINT_TO_RUNE_BALANCES,
+        OUTPOINT_TO_UTXO_ENTRY,
+        RUNE_ID_AND_HEIGHT_TO_RUNE_STATS,
+        RUNE_ID_AND_SCRIPT_PUBKEY_TO_BALANCE,
+        RUNE_ID_BALANCE_AND_OUTPOINT,
+        RUNE_ID_BALANCE_AND_SCRIPT_PUBKEY,
+        RUNE_ID_SCRIPT_PUBKEY_AND_HEIGHT_TO_BALANCE,
+        RUNE_ID_TO_RUNE_ENTRY,
+        RUNE_TO_RUNE_ID,
+        SAT_TO_SATPOINT,
+        SEARCH_TERM_AND_RUNE_ID,
+        SEARCH_TERM_AND_SEQUENCE_NUMBER,
+        SEQUENCE_NUMBER_AND_CONTENT_TYPE_TO_CHILDREN,
+        SEQUENCE_NUMBER_AND_HEIGHT_TO_CHILDREN,
+        SEQUENCE_NUMBER_AND_SCRIPT_PUBKEY_TO_CHILDREN,
+        SEQUENCE_NUMBER_CHILDREN_AND_SCRIPT_PUBKEY,
+        SEQUENCE_NUMBER_TO_COLLECTION_STATS,
+        SEQUENCE_NUMBER_TO_CONTENT_ENTRY,
+        SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
+        SEQUENCE_NUMBER_TO_RUNE_ID,
+        SEQUENCE_NUMBER_TO_SATPOINT,
+        STATISTIC_TO_COUNT,
+        TRANSACTION_ID_TO_RUNE,
+        TRANSACTION_ID_TO_TRANSACTION,
+        TRANSFER_NUMBER_TO_TRANSFER,
+      );
+    }
+
+    for (name, changes) in self.multimap_tables {
+      macro_rules! undo {
+        ($($table:ident),* $(,)?) => {
+          match name.as_str() {
+            $(name if name == $table.name() => Self::undo_multimap_table(wtx, $table, changes)?,)*
+            name => bail!("undo log contains changes to unknown multimap table `{name}`"),
+          }
+        };
+      }
+
+      undo!(
+        RUNE_ID_TO_BALANCE_CHANGE,
+        SAT_TO_SEQUENCE_NUMBER,
+        SCRIPT_PUBKEY_TO_ACTIVITY_NUMBER,
+        SCRIPT_PUBKEY_TO_OUTPOINT,
+        SEQUENCE_NUMBER_TO_CHILDREN,
+        SEQUENCE_NUMBER_TO_TRANSFER_NUMBER,
+      );
+    }
+
+    Ok(())
+  }
+
+  fn undo_table<K: Key + 'static, V: Value + 'static>(
+    wtx: &WriteTransaction,
+    definition: TableDefinition<K, V>,
+    changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+  ) -> Result {
+    let mut table = wtx.open_table(definition)?;
+
+    for (key, old) in changes.iter().rev() {
+      match old {
+        Some(old) => {
+          table.insert(K::from_bytes(key), V::from_bytes(old))?;
+        }
+        None => {
+          table.remove(K::from_bytes(key))?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn undo_multimap_table<K: Key + 'static, V: Key + 'static>(
+    wtx: &WriteTransaction,
+    definition: MultimapTableDefinition<K, V>,
+    changes: Vec<(Vec<u8>, Vec<u8>, bool)>,
+  ) -> Result {
+    let mut table = wtx.open_multimap_table(definition)?;
+
+    for (key, value, present) in changes.iter().rev() {
+      if *present {
+        table.insert(K::from_bytes(key), V::from_bytes(value))?;
+      } else {
+        table.remove(K::from_bytes(key), V::from_bytes(value))?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Records the changes made by indexing the block at `height`, so that they can
+/// be undone if the block is reorged out. Logged write transactions index a
+/// single block, so the undo log has one entry per block.
+///
+/// Undo logs of consecutive blocks are kept until they are more than the undo
+/// depth below the tip. If a block isn't logged, which happens during initial
+/// sync or without an undo depth, the undo logs before it are useless, and are
+/// deleted.
+#[derive(Clone, Default)]
+pub(crate) struct UndoLog {
+  changes: Option<Rc<RefCell<Changes>>>,
+  depth: Option<u32>,
+  height: u32,
+}
+
+impl UndoLog {
+  pub(crate) fn new(height: u32, depth: Option<u32>, log: bool) -> Self {
+    Self {
+      changes: (depth.is_some() && log).then(Default::default),
+      depth,
+      height,
+    }
+  }
+
+  pub(crate) fn is_logged(&self) -> bool {
+    self.changes.is_some()
+  }
+
+  pub(crate) fn open_table<'txn, K: Key + 'static, V: Value + 'static>(
+    &self,
+    wtx: &'txn WriteTransaction,
+    definition: TableDefinition<K, V>,
+  ) -> Result<Table<'txn, K, V>, TableError> {
+    Ok(Table {
+      changes: self.changes.clone(),
+      name: definition.name().into(),
+      table: wtx.open_table(definition)?,
+    })
+  }
+
+  pub(crate) fn open_multimap_table<'txn, K: Key + 'static, V: Key + 'static>(
+    &self,
+    wtx: &'txn WriteTransaction,
+    definition: MultimapTableDefinition<K, V>,
+  ) -> Result<MultimapTable<'txn, K, V>, TableError> {
+    Ok(MultimapTable {
+      changes: self.changes.clone(),
+      name: definition.name().into(),
+      table: wtx.open_multimap_table(definition)?,
+    })
+  }
+
+  /// Save the undo log of the block before `height`, and delete undo logs
+  /// which are no longer needed.
+  pub(crate) fn save(&self, wtx: &WriteTransaction, height: u32) -> Result {
+    let mut height_to_undo_log = wtx.open_table(HEIGHT_TO_UNDO_LOG)?;
+
+    let (Some(changes), Some(depth)) = (&self.changes, self.depth) else {
+      height_to_undo_log.retain(|_, _| false)?;
+      return Ok(());
+    };
+
+    let mut buffer = Vec::new();
+    ciborium::into_writer(&*changes.borrow(), &mut buffer)?;
+    height_to_undo_log.insert(self.height, buffer.as_slice())?;
+
+    let heights = height_to_undo_log
+      .iter()?
+      .map(|result| result.map(|(height, _)| height.value()))
+      .collect::<Result<Vec<u32>, StorageError>>()?;
+
+    // an undo log is needed until its block is more than `depth` blocks below
+    // the tip
+    for block in heights {
+      if block + depth < height {
+        height_to_undo_log.remove(block)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// The height of the oldest block which the saved undo logs can roll the
+  /// index back to.
+  pub(crate) fn oldest(index: &Index) -> Result<Option<u32>> {
+    let rtx = index.database.begin_read()?;
+
+    let height_to_undo_log = match rtx.open_table(HEIGHT_TO_UNDO_LOG) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(height_to_undo_log.first()?.map(|(block, _)| block.value()))
+  }
+
+  /// Undo blocks, latest first, until `height` blocks remain in the index.
+  pub(crate) fn undo(wtx: &WriteTransaction, height: u32) -> Result {
+    loop {
+      let (block, changes) = {
+        let height_to_undo_log = wtx.open_table(HEIGHT_TO_UNDO_LOG)?;
+
+        let (block, changes) = height_to_undo_log
+          .last()?
+          .ok_or_else(|| anyhow!("no undo log to roll back index to height {height}"))?;
+
+        if block.value() < height {
+          return Ok(());
+        }
+
+        (
+          block.value(),
+          ciborium::from_reader::<Changes, _>(Cursor::new(changes.value()))?,
+        )
+      };
+
+      log::info!("undoing changes to index made by block {block}");
+
+      changes.undo(wtx)?;
+
+      wtx.open_table(HEIGHT_TO_UNDO_LOG)?.remove(block)?;
+
+      if block == height {
+        return Ok(());
+      }
+    }
+  }
+}
+
+fn record(changes: &Option<Rc<RefCell<Changes>>>, record: impl FnOnce(&mut Changes)) {
+  if let Some(changes) = changes {
+    record(&mut changes.borrow_mut());
+  }
+}
+
+/// A table which records the previous value of every key it changes in an
+/// undo log. Reads go directly to the underlying table.
+pub(crate) struct Table<'txn, K: Key + 'static, V: Value + 'static> {
+  changes: Option<Rc<RefCell<Changes>>>,
+  name: String,
+  table: redb::Table<'txn, K, V>,
+}
+
+impl<'txn, K: Key + 'static, V: Value + 'static> Deref for Table<'txn, K, V> {
+  type Target = redb::Table<'txn, K, V>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.table
+  }
+}
+
+impl<'txn, K: Key + 'static, V: Value + 'static> Table<'txn, K, V> {
+  pub(crate) fn insert<'k, 'v>(
+    &mut self,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<Option<AccessGuard<V>>, StorageError> {
+    let old = self.table.insert(key.borrow(), value)?;
+
+    record(&self.changes, |changes| {
+      changes.tables.entry(self.name.clone()).or_default().push((
+        K::as_bytes(key.borrow()).as_ref().to_vec(),
+        old
+          .as_ref()
+          .map(|old| V::as_bytes(&old.value()).as_ref().to_vec()),
+      ));
+    });
+
+    Ok(old)
+  }
+
+  pub(crate) fn remove<'k>(
+    &mut self,
+    key: impl Borrow<K::SelfType<'k>>,
+  ) -> Result<Option<AccessGuard<V>>, StorageError> {
+    let old = self.table.remove(key.borrow())?;
+
+    if let Some(old) = &old {
+      record(&self.changes, |changes| {
+        changes.tables.entry(self.name.clone()).or_default().push((
+          K::as_bytes(key.borrow()).as_ref().to_vec(),
+          Some(V::as_bytes(&old.value()).as_ref().to_vec()),
+        ));
+      });
+    }
+
+    Ok(old)
+  }
+
+  pub(crate) fn pop_first(
+    &mut self,
+  ) -> Result<Option<(AccessGuard<K>, AccessGuard<V>)>, StorageError> {
+    let first = self.table.pop_first()?;
+
+    if let Some((key, old)) = &first {
+      record(&self.changes, |changes| {
+        changes.tables.entry(self.name.clone()).or_default().push((
+          K::as_bytes(&key.value()).as_ref().to_vec(),
+          Some(V::as_bytes(&old.value()).as_ref().to_vec()),
+        ));
+      });
+    }
+
+    Ok(first)
+  }
+}
+
+/// A multimap table which records whether every key and value pair it
+/// inserts or removes was previously present in an undo log.
+pub(crate) struct MultimapTable<'txn, K: Key + 'static, V: Key + 'static> {
+  changes: Option<Rc<RefCell<Changes>>>,
+  name: String,
+  table: redb::MultimapTable<'txn, K, V>,
+}
+
+impl<'txn, K: Key + 'static, V: Key + 'static> Deref for MultimapTable<'txn, K, V> {
+  type Target = redb::MultimapTable<'txn, K, V>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.table
+  }
+}
+
+impl<'txn, K: Key + 'static, V: Key + 'static> MultimapTable<'txn, K, V> {
+  pub(crate) fn insert<'k, 'v>(
+    &mut self,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<bool, StorageError> {
+    let present = self.table.insert(key.borrow(), value.borrow())?;
+    self.record(key.borrow(), value.borrow(), present);
+    Ok(present)
+  }
+
+  pub(crate) fn remove<'k, 'v>(
+    &mut self,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<bool, StorageError> {
+    let present = self.table.remove(key.borrow(), value.borrow())?;
+    self.record(key.borrow(), value.borrow(), present);
+    Ok(present)
+  }
+
+  fn record(&self, key: &K::SelfType<'_>, value: &V::SelfType<'_>, present: bool) {
+    record(&self.changes, |changes| {
+      changes
+        .multimap_tables
+        .entry(self.name.clone())
+        .or_default()
+        .push((
+          K::as_bytes(key).as_ref().to_vec(),
+          V::as_bytes(value).as_ref().to_vec(),
+          present,
+        ));
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn changes_can_be_undone() {
+    let tempdir = TempDir::new().unwrap();
+
+    let database = Database::create(tempdir.path().join("index.redb")).unwrap();
+
+    {
+      let wtx = database.begin_write().unwrap();
+      let mut table = wtx.open_table(STATISTIC_TO_COUNT).unwrap();
+      table.insert(0, 0).unwrap();
+      table.insert(1, 1).unwrap();
+      drop(table);
+      wtx.commit().unwrap();
+    }
+
+    let undo_log = UndoLog::new(0, Some(10), true);
+
+    {
+      let wtx = database.begin_write().unwrap();
+      let mut table = undo_log.open_table(&wtx, STATISTIC_TO_COUNT).unwrap();
+      table.insert(0, 10).unwrap();
+      table.insert(0, 20).unwrap();
+      table.remove(1).unwrap();
+      table.insert(2, 2).unwrap();
+      let mut multimap_table = undo_log
+        .open_multimap_table(&wtx, SEQUENCE_NUMBER_TO_CHILDREN)
+        .unwrap();
+      multimap_table.insert(0, 0).unwrap();
+      multimap_table.insert(0, 1).unwrap();
+      multimap_table.remove(0, 0).unwrap();
+      drop(table);
+      drop(multimap_table);
+      undo_log.save(&wtx, 1).unwrap();
+      wtx.commit().unwrap();
+    }
+
+    let wtx = database.begin_write().unwrap();
+    UndoLog::undo(&wtx, 0).unwrap();
+
+    let table = wtx.open_table(STATISTIC_TO_COUNT).unwrap();
+    assert_eq!(
+      table
+        .iter()
+        .unwrap()
+        .map(|result| result.map(|(key, value)| (key.value(), value.value())))
+        .collect::<Result<Vec<(u64, u64)>, StorageError>>()
+        .unwrap(),
+      [(0, 0), (1, 1)],
+    );
+
+    assert!(wtx
+      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)
+      .unwrap()
+      .is_empty()
+      .unwrap());
+
+    assert!(wtx
+      .open_table(HEIGHT_TO_UNDO_LOG)
+      .unwrap()
+      .is_empty()
+      .unwrap());
+  }
+}
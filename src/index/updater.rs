@@ -1,25 +1,63 @@
 use {
-  self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
-  super::{fetcher::Fetcher, *},
-  futures::future::try_join_all,
-  tokio::sync::{
-    broadcast::{self, error::TryRecvError},
-    mpsc::{self},
+  self::{
+    address_history_updater::AddressHistoryUpdater, collection_updater::CollectionUpdater,
+    event_emitter::EventEmitter, inscription_updater::InscriptionUpdater,
+    rune_history_updater::RuneHistoryUpdater, rune_updater::RuneUpdater,
+  },
+  super::{
+    block_source::{BlockFileSource, BlockSource, TIP_DISTANCE},
+    fetcher::Fetcher,
+    undo_log::{MultimapTable, Table, UndoLog},
+    *,
   },
+  futures::future::try_join_all,
+  std::collections::VecDeque,
+  tokio::sync::mpsc,
 };
 
+mod address_history_updater;
+mod collection_updater;
+mod event_emitter;
 mod inscription_updater;
+mod rune_history_updater;
 mod rune_updater;
 
+type Prevouts = std::sync::mpsc::Receiver<Result<HashMap<OutPoint, TxOut>>>;
+
+type PrevoutRequest = (
+  Vec<OutPoint>,
+  std::sync::mpsc::SyncSender<Result<HashMap<OutPoint, TxOut>>>,
+);
+
 pub(crate) struct BlockData {
   pub(crate) header: Header,
   pub(crate) txdata: Vec<(Transaction, Txid)>,
+  /// Envelopes of each transaction, empty if inscriptions are not indexed at
+  /// this height
+  pub(crate) envelopes: Vec<Vec<ParsedEnvelope>>,
+  /// Runestone of each transaction, empty if runes are not indexed at this
+  /// height
+  pub(crate) artifacts: Vec<Option<Artifact>>,
 }
 
-impl From<Block> for BlockData {
-  fn from(block: Block) -> Self {
+impl BlockData {
+  fn parse(block: Block, envelopes: bool, runestones: bool) -> Self {
     BlockData {
       header: block.header,
+      envelopes: if envelopes {
+        block
+          .txdata
+          .iter()
+          .map(ParsedEnvelope::from_transaction)
+          .collect()
+      } else {
+        Vec::new()
+      },
+      artifacts: if runestones {
+        block.txdata.iter().map(Runestone::decipher).collect()
+      } else {
+        Vec::new()
+      },
       txdata: block
         .txdata
         .into_iter()
@@ -34,10 +72,12 @@ impl From<Block> for BlockData {
 
 pub(crate) struct Updater<'index> {
   pub(super) height: u32,
+  pub(super) height_limit: Option<u32>,
   pub(super) index: &'index Index,
   pub(super) outputs_cached: u64,
   pub(super) outputs_traversed: u64,
   pub(super) sat_ranges_since_flush: u64,
+  pub(super) undo_log: UndoLog,
 }
 
 impl<'index> Updater<'index> {
@@ -46,6 +86,8 @@ impl<'index> Updater<'index> {
     let starting_height = u32::try_from(self.index.client.get_block_count()?).unwrap() + 1;
     let starting_index_height = self.height;
 
+    self.undo_log = self.begin_undo_log(starting_height);
+
     wtx
       .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
       .insert(
@@ -71,20 +113,50 @@ impl<'index> Updater<'index> {
       Some(progress_bar)
     };
 
-    let rx = Self::fetch_blocks_from(self.index, self.height)?;
+    let rx = Self::parse_blocks(
+      self.index,
+      self.height,
+      Self::fetch_blocks_from(self.index, self.height, self.height_limit)?,
+    );
 
-    let (mut output_sender, mut txout_receiver) = Self::spawn_fetcher(self.index)?;
+    let prevout_sender = Self::spawn_fetcher(self.index)?;
 
+    let mut pending = VecDeque::new();
+    let mut pending_txids = HashSet::new();
     let mut uncommitted = 0;
     let mut utxo_cache = HashMap::new();
-    while let Ok(block) = rx.recv() {
-      self.index_block(
-        &mut output_sender,
-        &mut txout_receiver,
-        &mut wtx,
-        block,
-        &mut utxo_cache,
-      )?;
+    loop {
+      // Queue blocks ahead of the one being indexed, so their missing prevouts
+      // are fetched while earlier blocks are written. When caught up, don't
+      // wait for more blocks than are available.
+      while pending.len() < Self::LOOKAHEAD {
+        let block = if pending.is_empty() {
+          rx.recv().ok()
+        } else {
+          rx.try_recv().ok()
+        };
+
+        let Some(block) = block else {
+          break;
+        };
+
+        pending_txids.extend(block.txdata.iter().map(|(_, txid)| *txid));
+
+        let prevouts =
+          self.prefetch_prevouts(&block, &pending_txids, &utxo_cache, &wtx, &prevout_sender)?;
+
+        pending.push_back((block, prevouts));
+      }
+
+      let Some((block, prevouts)) = pending.pop_front() else {
+        break;
+      };
+
+      for (_, txid) in &block.txdata {
+        pending_txids.remove(txid);
+      }
+
+      self.index_block(&mut wtx, block, prevouts, &mut utxo_cache)?;
 
       if let Some(progress_bar) = &mut progress_bar {
         progress_bar.inc(1);
@@ -100,7 +172,13 @@ impl<'index> Updater<'index> {
 
       uncommitted += 1;
 
-      if uncommitted == self.index.settings.commit_interval() {
+      // logged write transactions index a single block, so that the undo log
+      // has an entry for every block, and unlogged write transactions end
+      // before the first block which must be logged
+      if uncommitted == self.index.settings.commit_interval()
+        || self.undo_log.is_logged()
+        || self.is_logged(starting_height)
+      {
         self.commit(wtx, utxo_cache)?;
         utxo_cache = HashMap::new();
         uncommitted = 0;
@@ -117,6 +195,7 @@ impl<'index> Updater<'index> {
           // write transaction
           break;
         }
+        self.undo_log = self.begin_undo_log(starting_height);
         wtx
           .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
           .insert(
@@ -150,18 +229,47 @@ impl<'index> Updater<'index> {
     Ok(())
   }
 
+  const LOOKAHEAD: usize = 32;
+
+  fn begin_undo_log(&self, starting_height: u32) -> UndoLog {
+    UndoLog::new(
+      self.height,
+      self.index.settings.undo_depth(),
+      self.is_logged(starting_height),
+    )
+  }
+
+  /// Only blocks within the undo depth of the chain tip are logged, so initial
+  /// sync isn't slowed down.
+  fn is_logged(&self, starting_height: u32) -> bool {
+    self
+      .index
+      .settings
+      .undo_depth()
+      .is_some_and(|depth| self.height.saturating_add(depth) >= starting_height)
+  }
+
   fn fetch_blocks_from(
     index: &Index,
     mut height: u32,
-  ) -> Result<std::sync::mpsc::Receiver<BlockData>> {
+    height_limit: Option<u32>,
+  ) -> Result<std::sync::mpsc::Receiver<Block>> {
     let (tx, rx) = std::sync::mpsc::sync_channel(32);
 
     let first_index_height = index.first_index_height;
 
-    let height_limit = index.height_limit;
-
     let client = index.settings.bitcoin_rpc_client(None)?;
 
+    // loading the block index is slow, so it is only loaded when there are
+    // blocks to read from the block files
+    let mut source: Box<dyn BlockSource> = if index.settings.read_block_files()
+      && client.get_block_count()? > u64::from(height.saturating_add(TIP_DISTANCE))
+    {
+      Box::new(BlockFileSource::open(index.settings.blocks_dir()?, client)?)
+    } else {
+      Box::new(client)
+    };
+
     thread::spawn(move || loop {
       if let Some(height_limit) = height_limit {
         if height >= height_limit {
@@ -169,9 +277,9 @@ impl<'index> Updater<'index> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, first_index_height) {
+      match Self::get_block_with_retries(source.as_mut(), height, first_index_height) {
         Ok(Some(block)) => {
-          if let Err(err) = tx.send(block.into()) {
+          if let Err(err) = tx.send(block) {
             log::info!("Block receiver disconnected: {err}");
             break;
           }
@@ -189,29 +297,26 @@ impl<'index> Updater<'index> {
   }
 
   fn get_block_with_retries(
-    client: &Client,
+    source: &mut dyn BlockSource,
     height: u32,
     first_index_height: u32,
   ) -> Result<Option<Block>> {
     let mut errors = 0;
     loop {
-      match client
-        .get_block_hash(height.into())
-        .into_option()
-        .and_then(|option| {
-          option
-            .map(|hash| {
-              if height >= first_index_height {
-                Ok(client.get_block(&hash)?)
-              } else {
-                Ok(Block {
-                  header: client.get_block_header(&hash)?,
-                  txdata: Vec::new(),
-                })
-              }
-            })
-            .transpose()
-        }) {
+      match source.block_hash(height).and_then(|option| {
+        option
+          .map(|hash| {
+            if height >= first_index_height {
+              source.block(height, hash)
+            } else {
+              Ok(Block {
+                header: source.header(hash)?,
+                txdata: Vec::new(),
+              })
+            }
+          })
+          .transpose()
+      }) {
         Err(err) => {
           if cfg!(test) {
             return Err(err);
@@ -233,18 +338,85 @@ impl<'index> Updater<'index> {
     }
   }
 
-  fn spawn_fetcher(index: &Index) -> Result<(mpsc::Sender<OutPoint>, broadcast::Receiver<TxOut>)> {
-    let fetcher = Fetcher::new(&index.settings)?;
+  /// Parse blocks on several threads, returning them in the order they were
+  /// received.
+  fn parse_blocks(
+    index: &Index,
+    mut height: u32,
+    blocks: std::sync::mpsc::Receiver<Block>,
+  ) -> std::sync::mpsc::Receiver<BlockData> {
+    let first_inscription_height = index
+      .index_inscriptions
+      .then(|| index.settings.first_inscription_height());
+
+    let first_rune_height = index
+      .index_runes
+      .then(|| index.settings.first_rune_height());
+
+    let threads = thread::available_parallelism()
+      .map(|threads| threads.get())
+      .unwrap_or(1)
+      .min(8);
+
+    let mut workers = Vec::new();
+    let mut parsed = Vec::new();
+
+    for _ in 0..threads {
+      let (block_sender, block_receiver) = std::sync::mpsc::sync_channel::<(u32, Block)>(4);
+      let (parsed_sender, parsed_receiver) = std::sync::mpsc::sync_channel(4);
+
+      thread::spawn(move || {
+        for (height, block) in block_receiver {
+          let block = BlockData::parse(
+            block,
+            first_inscription_height.is_some_and(|first| height >= first),
+            first_rune_height.is_some_and(|first| height >= first),
+          );
 
-    // A block probably has no more than 20k inputs
-    const CHANNEL_BUFFER_SIZE: usize = 20_000;
+          if parsed_sender.send(block).is_err() {
+            break;
+          }
+        }
+      });
+
+      workers.push(block_sender);
+      parsed.push(parsed_receiver);
+    }
+
+    thread::spawn(move || {
+      for (i, block) in blocks.into_iter().enumerate() {
+        if workers[i % threads].send((height, block)).is_err() {
+          break;
+        }
+        height += 1;
+      }
+    });
+
+    let (tx, rx) = std::sync::mpsc::sync_channel(32);
+
+    thread::spawn(move || {
+      for i in 0.. {
+        let Ok(block) = parsed[i % threads].recv() else {
+          break;
+        };
+
+        if let Err(err) = tx.send(block) {
+          log::info!("Block receiver disconnected: {err}");
+          break;
+        }
+      }
+    });
+
+    rx
+  }
+
+  fn spawn_fetcher(index: &Index) -> Result<mpsc::Sender<PrevoutRequest>> {
+    let fetcher = Fetcher::new(&index.settings)?;
 
     // Batch 2048 missing inputs at a time, arbitrarily chosen size
     const BATCH_SIZE: usize = 2048;
 
-    let (outpoint_sender, mut outpoint_receiver) = mpsc::channel::<OutPoint>(CHANNEL_BUFFER_SIZE);
-
-    let (txout_sender, txout_receiver) = broadcast::channel::<TxOut>(CHANNEL_BUFFER_SIZE);
+    let (request_sender, mut request_receiver) = mpsc::channel::<PrevoutRequest>(Self::LOOKAHEAD);
 
     // Default rpcworkqueue in bitcoind is 16, meaning more than 16 concurrent requests will be rejected.
     // Since we are already requesting blocks on a separate thread, and we don't want to break if anything
@@ -257,61 +429,109 @@ impl<'index> Updater<'index> {
         .build()
         .unwrap();
       rt.block_on(async move {
-        loop {
-          let Some(outpoint) = outpoint_receiver.recv().await else {
-            log::debug!("Outpoint channel closed");
-            return;
-          };
-
-          // There's no try_iter on tokio::sync::mpsc::Receiver like std::sync::mpsc::Receiver.
-          // So we just loop until BATCH_SIZE doing try_recv until it returns None.
-          let mut outpoints = vec![outpoint];
-          for _ in 0..BATCH_SIZE - 1 {
-            let Ok(outpoint) = outpoint_receiver.try_recv() else {
-              break;
+        while let Some((outpoints, prevout_sender)) = request_receiver.recv().await {
+          let mut prevouts = HashMap::new();
+
+          let mut result = Ok(());
+
+          for batch in outpoints.chunks(BATCH_SIZE) {
+            // Break outputs into chunks for parallel requests
+            let chunk_size = (batch.len() / parallel_requests) + 1;
+            let mut futs = Vec::with_capacity(parallel_requests);
+            for chunk in batch.chunks(chunk_size) {
+              let txids = chunk.iter().map(|outpoint| outpoint.txid).collect();
+              let fut = fetcher.get_transactions(txids);
+              futs.push(fut);
+            }
+
+            let txs = match try_join_all(futs).await {
+              Ok(txs) => txs,
+              Err(e) => {
+                result = Err(e);
+                break;
+              }
             };
-            outpoints.push(outpoint);
-          }
 
-          // Break outputs into chunks for parallel requests
-          let chunk_size = (outpoints.len() / parallel_requests) + 1;
-          let mut futs = Vec::with_capacity(parallel_requests);
-          for chunk in outpoints.chunks(chunk_size) {
-            let txids = chunk.iter().map(|outpoint| outpoint.txid).collect();
-            let fut = fetcher.get_transactions(txids);
-            futs.push(fut);
+            for (outpoint, tx) in batch.iter().zip(txs.iter().flatten()) {
+              prevouts.insert(
+                *outpoint,
+                tx.output[usize::try_from(outpoint.vout).unwrap()].clone(),
+              );
+            }
           }
 
-          let txs = match try_join_all(futs).await {
-            Ok(txs) => txs,
-            Err(e) => {
-              log::error!("Couldn't receive txs {e}");
-              return;
-            }
-          };
-
-          // Send all tx outputs back in order
-          for (i, tx) in txs.iter().flatten().enumerate() {
-            let Ok(_) =
-              txout_sender.send(tx.output[usize::try_from(outpoints[i].vout).unwrap()].clone())
-            else {
-              log::error!("Value channel closed unexpectedly");
-              return;
-            };
+          if prevout_sender.send(result.map(|()| prevouts)).is_err() {
+            log::debug!("Prevout receiver disconnected");
           }
         }
+
+        log::debug!("Prevout request channel closed");
       })
     });
 
-    Ok((outpoint_sender, txout_receiver))
+    Ok(request_sender)
+  }
+
+  /// Request any prevouts spent by `block` which are not in the UTXO cache or
+  /// database, and are not created by `block` or a block queued before it.
+  fn prefetch_prevouts(
+    &self,
+    block: &BlockData,
+    pending_txids: &HashSet<Txid>,
+    utxo_cache: &HashMap<OutPoint, UtxoEntryBuf>,
+    wtx: &WriteTransaction,
+    prevout_sender: &mpsc::Sender<PrevoutRequest>,
+  ) -> Result<Option<Prevouts>> {
+    if self.index.have_full_utxo_index()
+      || !(self.index.index_inscriptions || self.index.index_addresses || self.index.index_sats)
+    {
+      return Ok(None);
+    }
+
+    let outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+
+    let mut missing = Vec::new();
+
+    for (tx, _) in &block.txdata {
+      for input in &tx.input {
+        let prev_output = input.previous_output;
+        // We don't need coinbase inputs
+        if prev_output.is_null() {
+          continue;
+        }
+        // We don't need inputs from txs in queued blocks, since they'll be
+        // added to cache when the tx is indexed
+        if pending_txids.contains(&prev_output.txid) {
+          continue;
+        }
+        // We don't need inputs we already have in our cache from earlier blocks
+        if utxo_cache.contains_key(&prev_output) {
+          continue;
+        }
+        // We don't need inputs we already have in our database
+        if outpoint_to_utxo_entry.get(&prev_output.store())?.is_some() {
+          continue;
+        }
+        missing.push(prev_output);
+      }
+    }
+
+    if missing.is_empty() {
+      return Ok(None);
+    }
+
+    let (tx, rx) = std::sync::mpsc::sync_channel(1);
+
+    prevout_sender.blocking_send((missing, tx))?;
+
+    Ok(Some(rx))
   }
 
   fn index_block(
     &mut self,
-    output_sender: &mut mpsc::Sender<OutPoint>,
-    txout_receiver: &mut broadcast::Receiver<TxOut>,
     wtx: &mut WriteTransaction,
     block: BlockData,
+    prevouts: Option<Prevouts>,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
   ) -> Result<()> {
     Reorg::detect_reorg(&block, self.height, self.index)?;
@@ -327,18 +547,33 @@ impl<'index> Updater<'index> {
       block.txdata.len()
     );
 
-    let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
-    let mut inscription_id_to_sequence_number =
-      wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
-    let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+    let mut height_to_block_header = self.undo_log.open_table(wtx, HEIGHT_TO_BLOCK_HEADER)?;
+    let mut inscription_id_to_sequence_number = self
+      .undo_log
+      .open_table(wtx, INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+    let mut statistic_to_count = self.undo_log.open_table(wtx, STATISTIC_TO_COUNT)?;
+    let mut event_emitter = EventEmitter::new(self.index, wtx, &self.undo_log)?;
+    let mut address_history_updater = self
+      .index
+      .index_address_history
+      .then(AddressHistoryUpdater::default);
+
+    let index_runes =
+      self.index.index_runes && self.height >= self.index.settings.first_rune_height();
+
+    // rune holders are indexed by script pubkey, which for spent outputs is
+    // only available from their UTXO entries
+    let mut spent_script_pubkeys = (index_runes && self.index.index_addresses).then(HashMap::new);
 
     if self.index.index_inscriptions || self.index.index_addresses || self.index.index_sats {
       self.index_utxo_entries(
         &block,
-        txout_receiver,
-        output_sender,
+        prevouts,
         utxo_cache,
         wtx,
+        &mut event_emitter,
+        address_history_updater.as_mut(),
+        spent_script_pubkeys.as_mut(),
         &mut inscription_id_to_sequence_number,
         &mut statistic_to_count,
         &mut sat_ranges_written,
@@ -346,24 +581,47 @@ impl<'index> Updater<'index> {
       )?;
     }
 
-    if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
-      let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-      let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-      let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
-      let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
-      let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+    if index_runes {
+      let mut outpoint_to_rune_balances =
+        self.undo_log.open_table(wtx, OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut rune_id_and_script_pubkey_to_balance = self
+        .undo_log
+        .open_table(wtx, RUNE_ID_AND_SCRIPT_PUBKEY_TO_BALANCE)?;
+      let mut rune_id_balance_and_outpoint = self
+        .undo_log
+        .open_table(wtx, RUNE_ID_BALANCE_AND_OUTPOINT)?;
+      let mut rune_id_balance_and_script_pubkey = self
+        .undo_log
+        .open_table(wtx, RUNE_ID_BALANCE_AND_SCRIPT_PUBKEY)?;
+      let mut rune_id_to_rune_entry = self.undo_log.open_table(wtx, RUNE_ID_TO_RUNE_ENTRY)?;
+      let mut rune_to_rune_id = self.undo_log.open_table(wtx, RUNE_TO_RUNE_ID)?;
+      let mut search_term_and_rune_id = self.undo_log.open_table(wtx, SEARCH_TERM_AND_RUNE_ID)?;
+      let mut sequence_number_to_rune_id =
+        self.undo_log.open_table(wtx, SEQUENCE_NUMBER_TO_RUNE_ID)?;
+      let mut transaction_id_to_rune = self.undo_log.open_table(wtx, TRANSACTION_ID_TO_RUNE)?;
 
       let runes = statistic_to_count
         .get(&Statistic::Runes.into())?
         .map(|x| x.value())
         .unwrap_or(0);
 
+      let mut rune_history_updater = self
+        .index
+        .index_rune_history
+        .then(RuneHistoryUpdater::default);
+
       let mut rune_updater = RuneUpdater {
-        event_sender: self.index.event_sender.as_ref(),
+        address_history_updater: address_history_updater.as_mut(),
         block_time: block.header.time,
         burned: HashMap::new(),
         client: &self.index.client,
+        event_emitter: &mut event_emitter,
         height: self.height,
+        id_and_script_pubkey_to_balance: self.index.index_addresses.then_some((
+          &mut rune_id_and_script_pubkey_to_balance,
+          &mut rune_id_balance_and_script_pubkey,
+        )),
+        id_balance_and_outpoint: &mut rune_id_balance_and_outpoint,
         id_to_entry: &mut rune_id_to_rune_entry,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
         minimum: Rune::minimum_at_height(
@@ -371,18 +629,56 @@ impl<'index> Updater<'index> {
           Height(self.height),
         ),
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        rune_history_updater: rune_history_updater.as_mut(),
         rune_to_id: &mut rune_to_rune_id,
         runes,
+        search_term_and_rune_id: self
+          .index
+          .index_search
+          .then_some(&mut search_term_and_rune_id),
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
+        spent_script_pubkeys: spent_script_pubkeys.unwrap_or_default(),
         statistic_to_count: &mut statistic_to_count,
         transaction_id_to_rune: &mut transaction_id_to_rune,
       };
 
       for (i, (tx, txid)) in block.txdata.iter().enumerate() {
-        rune_updater.index_runes(u32::try_from(i).unwrap(), tx, *txid)?;
+        rune_updater.index_runes(
+          u32::try_from(i).unwrap(),
+          tx,
+          *txid,
+          block.artifacts[i].as_ref(),
+        )?;
       }
 
       rune_updater.update()?;
+
+      if let Some(rune_history_updater) = rune_history_updater {
+        rune_history_updater.update(
+          self.height,
+          &rune_id_to_rune_entry,
+          &rune_id_and_script_pubkey_to_balance,
+          &mut self
+            .undo_log
+            .open_table(wtx, RUNE_ID_SCRIPT_PUBKEY_AND_HEIGHT_TO_BALANCE)?,
+          &mut self
+            .undo_log
+            .open_multimap_table(wtx, RUNE_ID_TO_BALANCE_CHANGE)?,
+          &mut self
+            .undo_log
+            .open_table(wtx, RUNE_ID_AND_HEIGHT_TO_RUNE_STATS)?,
+        )?;
+      }
+    }
+
+    if let Some(address_history_updater) = address_history_updater {
+      address_history_updater.update(
+        self.height,
+        &mut self.undo_log.open_table(wtx, ACTIVITY_NUMBER_TO_ACTIVITY)?,
+        &mut self
+          .undo_log
+          .open_multimap_table(wtx, SCRIPT_PUBKEY_TO_ACTIVITY_NUMBER)?,
+      )?;
     }
 
     height_to_block_header.insert(&self.height, &block.header.store())?;
@@ -401,73 +697,46 @@ impl<'index> Updater<'index> {
   fn index_utxo_entries<'wtx>(
     &mut self,
     block: &BlockData,
-    txout_receiver: &mut broadcast::Receiver<TxOut>,
-    output_sender: &mut mpsc::Sender<OutPoint>,
+    mut prevouts: Option<Prevouts>,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
     wtx: &'wtx WriteTransaction,
+    event_emitter: &mut EventEmitter<'wtx>,
+    mut address_history_updater: Option<&mut AddressHistoryUpdater>,
+    mut spent_script_pubkeys: Option<&mut HashMap<OutPoint, Vec<u8>>>,
     inscription_id_to_sequence_number: &mut Table<'wtx, (u128, u128, u32), u32>,
     statistic_to_count: &mut Table<'wtx, u64, u64>,
     sat_ranges_written: &mut u64,
     outputs_in_block: &mut u64,
   ) -> Result<(), Error> {
-    let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
-    let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
-    let mut inscription_number_to_sequence_number =
-      wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
-    let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
-    let mut sat_to_satpoint = wtx.open_table(SAT_TO_SATPOINT)?;
-    let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
-    let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
-    let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
-    let mut sequence_number_to_inscription_entry =
-      wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-    let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
+    let mut height_to_last_sequence_number = self
+      .undo_log
+      .open_table(wtx, HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let mut home_inscriptions = self.undo_log.open_table(wtx, HOME_INSCRIPTIONS)?;
+    let mut inscription_number_to_sequence_number = self
+      .undo_log
+      .open_table(wtx, INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let mut outpoint_to_utxo_entry = self.undo_log.open_table(wtx, OUTPOINT_TO_UTXO_ENTRY)?;
+    let mut sat_to_satpoint = self.undo_log.open_table(wtx, SAT_TO_SATPOINT)?;
+    let mut sat_to_sequence_number = self
+      .undo_log
+      .open_multimap_table(wtx, SAT_TO_SEQUENCE_NUMBER)?;
+    let mut script_pubkey_to_outpoint = self
+      .undo_log
+      .open_multimap_table(wtx, SCRIPT_PUBKEY_TO_OUTPOINT)?;
+    let mut sequence_number_to_children = self
+      .undo_log
+      .open_multimap_table(wtx, SEQUENCE_NUMBER_TO_CHILDREN)?;
+    let mut sequence_number_to_inscription_entry = self
+      .undo_log
+      .open_table(wtx, SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let mut transaction_id_to_transaction = self
+      .undo_log
+      .open_table(wtx, TRANSACTION_ID_TO_TRANSACTION)?;
 
     let index_inscriptions = self.height >= self.index.settings.first_inscription_height()
       && self.index.index_inscriptions;
 
-    // If the receiver still has inputs something went wrong in the last
-    // block and we shouldn't recover from this and commit the last block
-    if index_inscriptions {
-      assert!(
-        matches!(txout_receiver.try_recv(), Err(TryRecvError::Empty)),
-        "Previous block did not consume all inputs"
-      );
-    }
-
-    if !self.index.have_full_utxo_index() {
-      // Send all missing input outpoints to be fetched
-      let txids = block
-        .txdata
-        .iter()
-        .map(|(_, txid)| txid)
-        .collect::<HashSet<_>>();
-
-      for (tx, _) in &block.txdata {
-        for input in &tx.input {
-          let prev_output = input.previous_output;
-          // We don't need coinbase inputs
-          if prev_output.is_null() {
-            continue;
-          }
-          // We don't need inputs from txs earlier in the block, since
-          // they'll be added to cache when the tx is indexed
-          if txids.contains(&prev_output.txid) {
-            continue;
-          }
-          // We don't need inputs we already have in our cache from earlier blocks
-          if utxo_cache.contains_key(&prev_output) {
-            continue;
-          }
-          // We don't need inputs we already have in our database
-          if outpoint_to_utxo_entry.get(&prev_output.store())?.is_some() {
-            continue;
-          }
-          // Send this outpoint to background thread to be fetched
-          output_sender.blocking_send(prev_output)?;
-        }
-      }
-    }
+    let mut fetched_prevouts = None;
 
     let mut lost_sats = statistic_to_count
       .get(&Statistic::LostSats.key())?
@@ -500,7 +769,10 @@ impl<'index> Updater<'index> {
 
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
+      collections: CollectionUpdater::default(),
+      contents: Vec::new(),
       cursed_inscription_count,
+      event_emitter,
       flotsam: Vec::new(),
       height: self.height,
       home_inscription_count,
@@ -511,11 +783,13 @@ impl<'index> Updater<'index> {
       next_sequence_number,
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
+      search_terms: Vec::new(),
       sequence_number_to_children: &mut sequence_number_to_children,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
       timestamp: block.header.time,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
+      transfers: Vec::new(),
       unbound_inscriptions,
     };
 
@@ -540,6 +814,12 @@ impl<'index> Updater<'index> {
     {
       log::trace!("Indexing transaction {tx_offset}…");
 
+      let hogex = mweb::is_hogex(tx, tx_offset, block.txdata.len());
+
+      if hogex {
+        statistic_to_count.insert(&Statistic::MwebSats.key(), &tx.output[0].value)?;
+      }
+
       let input_utxo_entries = if tx_offset == 0 {
         Vec::new()
       } else {
@@ -562,12 +842,23 @@ impl<'index> Updater<'index> {
               entry.value().to_buf()
             } else {
               assert!(!self.index.have_full_utxo_index());
-              let txout = txout_receiver.blocking_recv().map_err(|err| {
-                anyhow!(
-                  "failed to get transaction for {}: {err}",
-                  input.previous_output
-                )
-              })?;
+
+              if fetched_prevouts.is_none() {
+                fetched_prevouts = Some(match prevouts.take() {
+                  Some(prevouts) => prevouts
+                    .recv()
+                    .map_err(|err| anyhow!("failed to receive prevouts: {err}"))??,
+                  None => HashMap::new(),
+                });
+              }
+
+              let txout = fetched_prevouts
+                .as_mut()
+                .unwrap()
+                .remove(&input.previous_output)
+                .ok_or_else(|| {
+                  anyhow!("failed to get transaction for {}", input.previous_output)
+                })?;
 
               let mut entry = UtxoEntryBuf::new();
               entry.push_value(txout.value, self.index);
@@ -588,6 +879,18 @@ impl<'index> Updater<'index> {
         .map(|entry| entry.parse(self.index))
         .collect::<Vec<ParsedUtxoEntry>>();
 
+      if let Some(address_history_updater) = address_history_updater.as_deref_mut() {
+        for (input, entry) in tx.input.iter().zip(&input_utxo_entries) {
+          address_history_updater.spend(tx_offset, *txid, input.previous_output, entry, self.index);
+        }
+      }
+
+      if let Some(spent_script_pubkeys) = spent_script_pubkeys.as_deref_mut() {
+        for (input, entry) in tx.input.iter().zip(&input_utxo_entries) {
+          spent_script_pubkeys.insert(input.previous_output, entry.script_pubkey().to_vec());
+        }
+      }
+
       let mut output_utxo_entries = tx
         .output
         .iter()
@@ -614,6 +917,7 @@ impl<'index> Updater<'index> {
         self.index_transaction_sats(
           tx,
           *txid,
+          hogex,
           &mut sat_to_satpoint,
           &mut output_utxo_entries,
           input_sat_ranges.as_ref().unwrap(),
@@ -637,6 +941,8 @@ impl<'index> Updater<'index> {
         inscription_updater.index_inscriptions(
           tx,
           *txid,
+          &block.envelopes[tx_offset],
+          hogex,
           &input_utxo_entries,
           &mut output_utxo_entries,
           utxo_cache,
@@ -647,7 +953,19 @@ impl<'index> Updater<'index> {
 
       for (vout, output_utxo_entry) in output_utxo_entries.into_iter().enumerate() {
         let vout = u32::try_from(vout).unwrap();
-        utxo_cache.insert(OutPoint { txid: *txid, vout }, output_utxo_entry);
+        let outpoint = OutPoint { txid: *txid, vout };
+
+        if let Some(address_history_updater) = address_history_updater.as_deref_mut() {
+          address_history_updater.receive(
+            tx_offset,
+            *txid,
+            outpoint,
+            &output_utxo_entry.parse(self.index),
+            self.index,
+          );
+        }
+
+        utxo_cache.insert(outpoint, output_utxo_entry);
       }
     }
 
@@ -713,6 +1031,77 @@ impl<'index> Updater<'index> {
       &inscription_updater.unbound_inscriptions,
     )?;
 
+    if !inscription_updater.transfers.is_empty() {
+      let mut sequence_number_to_transfer_number = self
+        .undo_log
+        .open_multimap_table(wtx, SEQUENCE_NUMBER_TO_TRANSFER_NUMBER)?;
+      let mut transfer_number_to_transfer =
+        self.undo_log.open_table(wtx, TRANSFER_NUMBER_TO_TRANSFER)?;
+
+      let mut next_transfer_number = transfer_number_to_transfer
+        .last()?
+        .map(|(transfer_number, _transfer)| transfer_number.value() + 1)
+        .unwrap_or_default();
+
+      for (sequence_number, transfer) in inscription_updater.transfers {
+        transfer_number_to_transfer.insert(next_transfer_number, transfer.store())?;
+        sequence_number_to_transfer_number.insert(sequence_number, next_transfer_number)?;
+        next_transfer_number += 1;
+      }
+    }
+
+    if !inscription_updater.contents.is_empty() {
+      let mut content_length_and_sequence_number = self
+        .undo_log
+        .open_table(wtx, CONTENT_LENGTH_AND_SEQUENCE_NUMBER)?;
+      let mut fee_and_sequence_number = self.undo_log.open_table(wtx, FEE_AND_SEQUENCE_NUMBER)?;
+      let mut sequence_number_to_content_entry = self
+        .undo_log
+        .open_table(wtx, SEQUENCE_NUMBER_TO_CONTENT_ENTRY)?;
+
+      for (sequence_number, fee, content) in inscription_updater.contents {
+        content_length_and_sequence_number.insert(
+          (content.content_length.unwrap_or_default(), sequence_number),
+          (),
+        )?;
+        fee_and_sequence_number.insert((fee, sequence_number), ())?;
+        sequence_number_to_content_entry.insert(sequence_number, content.store())?;
+      }
+    }
+
+    if !inscription_updater.collections.is_empty() {
+      inscription_updater.collections.update(
+        self.height,
+        &mut self
+          .undo_log
+          .open_table(wtx, SEQUENCE_NUMBER_TO_COLLECTION_STATS)?,
+        &mut self
+          .undo_log
+          .open_table(wtx, SEQUENCE_NUMBER_AND_CONTENT_TYPE_TO_CHILDREN)?,
+        &mut self
+          .undo_log
+          .open_table(wtx, SEQUENCE_NUMBER_AND_HEIGHT_TO_CHILDREN)?,
+        &mut self
+          .undo_log
+          .open_table(wtx, SEQUENCE_NUMBER_AND_SCRIPT_PUBKEY_TO_CHILDREN)?,
+        &mut self
+          .undo_log
+          .open_table(wtx, SEQUENCE_NUMBER_CHILDREN_AND_SCRIPT_PUBKEY)?,
+      )?;
+    }
+
+    if !inscription_updater.search_terms.is_empty() {
+      let mut search_term_and_sequence_number = self
+        .undo_log
+        .open_table(wtx, SEARCH_TERM_AND_SEQUENCE_NUMBER)?;
+
+      for (sequence_number, terms) in inscription_updater.search_terms {
+        for term in terms {
+          search_term_and_sequence_number.insert((term.as_str(), sequence_number), ())?;
+        }
+      }
+    }
+
     Ok(())
   }
 
@@ -730,6 +1119,7 @@ impl<'index> Updater<'index> {
     &mut self,
     tx: &Transaction,
     txid: Txid,
+    hogex: bool,
     sat_to_satpoint: &mut Table<u64, &SatPointValue>,
     output_utxo_entries: &mut [UtxoEntryBuf],
     input_sat_ranges: &[&[u8]],
@@ -752,7 +1142,9 @@ impl<'index> Updater<'index> {
         .sum::<usize>(),
     );
 
-    for (vout, output) in tx.output.iter().enumerate() {
+    for vout in mweb::output_order(tx, hogex) {
+      let output = &tx.output[vout];
+
       let outpoint = OutPoint {
         vout: vout.try_into().unwrap(),
         txid,
@@ -827,9 +1219,13 @@ impl<'index> Updater<'index> {
     );
 
     {
-      let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
-      let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
-      let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+      let mut outpoint_to_utxo_entry = self.undo_log.open_table(&wtx, OUTPOINT_TO_UTXO_ENTRY)?;
+      let mut script_pubkey_to_outpoint = self
+        .undo_log
+        .open_multimap_table(&wtx, SCRIPT_PUBKEY_TO_OUTPOINT)?;
+      let mut sequence_number_to_satpoint = self
+        .undo_log
+        .open_table(&wtx, SEQUENCE_NUMBER_TO_SATPOINT)?;
 
       for (outpoint, mut utxo_entry) in utxo_cache {
         if Index::is_special_outpoint(outpoint) {
@@ -859,6 +1255,11 @@ impl<'index> Updater<'index> {
     self.outputs_traversed = 0;
     Index::increment_statistic(&wtx, Statistic::SatRanges, self.sat_ranges_since_flush)?;
     self.sat_ranges_since_flush = 0;
+
+    Reorg::prune_events(self.index, &wtx, self.height)?;
+
+    self.undo_log.save(&wtx, self.height)?;
+
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
@@ -0,0 +1,153 @@
+use super::*;
+
+struct Transfer {
+  inscriptions: Vec<u32>,
+  outpoint: OutPoint,
+  received: bool,
+  script_pubkey: Vec<u8>,
+  value: u64,
+}
+
+/// Collects the outputs spent and created by each transaction in a block, and
+/// the rune balances they hold, and once the block has been indexed, records
+/// what each script pubkey received and sent in each transaction.
+#[derive(Default)]
+pub(super) struct AddressHistoryUpdater {
+  rune_balances: HashMap<OutPoint, Vec<(RuneId, u128)>>,
+  transactions: BTreeMap<usize, (Txid, Vec<Transfer>)>,
+}
+
+impl AddressHistoryUpdater {
+  pub(super) fn spend(
+    &mut self,
+    tx_offset: usize,
+    txid: Txid,
+    outpoint: OutPoint,
+    entry: &ParsedUtxoEntry,
+    index: &Index,
+  ) {
+    self.transfer(tx_offset, txid, outpoint, entry, false, index);
+  }
+
+  pub(super) fn receive(
+    &mut self,
+    tx_offset: usize,
+    txid: Txid,
+    outpoint: OutPoint,
+    entry: &ParsedUtxoEntry,
+    index: &Index,
+  ) {
+    self.transfer(tx_offset, txid, outpoint, entry, true, index);
+  }
+
+  fn transfer(
+    &mut self,
+    tx_offset: usize,
+    txid: Txid,
+    outpoint: OutPoint,
+    entry: &ParsedUtxoEntry,
+    received: bool,
+    index: &Index,
+  ) {
+    let script_pubkey = entry.script_pubkey();
+
+    if script_pubkey.is_empty() || Script::from_bytes(script_pubkey).is_op_return() {
+      return;
+    }
+
+    self
+      .transactions
+      .entry(tx_offset)
+      .or_insert_with(|| (txid, Vec::new()))
+      .1
+      .push(Transfer {
+        inscriptions: if index.index_inscriptions {
+          entry
+            .parse_inscriptions()
+            .into_iter()
+            .map(|(sequence_number, _offset)| sequence_number)
+            .collect()
+        } else {
+          Vec::new()
+        },
+        outpoint,
+        received,
+        script_pubkey: script_pubkey.to_vec(),
+        value: entry.total_value(),
+      });
+  }
+
+  /// Record the rune balances of an output created or spent in this block.
+  pub(super) fn rune_balances(&mut self, outpoint: OutPoint, balances: Vec<(RuneId, u128)>) {
+    self.rune_balances.insert(outpoint, balances);
+  }
+
+  pub(super) fn update(
+    self,
+    height: u32,
+    activity_number_to_activity: &mut Table<'_, u64, ActivityEntryValue>,
+    script_pubkey_to_activity_number: &mut MultimapTable<'_, &'static [u8], u64>,
+  ) -> Result {
+    let mut next_activity_number = activity_number_to_activity
+      .last()?
+      .map(|(activity_number, _activity)| activity_number.value() + 1)
+      .unwrap_or_default();
+
+    for (_tx_offset, (txid, transfers)) in self.transactions {
+      let mut activity = BTreeMap::<Vec<u8>, ActivityEntry>::new();
+
+      for transfer in transfers {
+        let entry = activity
+          .entry(transfer.script_pubkey)
+          .or_insert_with(|| ActivityEntry {
+            height,
+            inscriptions_received: Vec::new(),
+            inscriptions_sent: Vec::new(),
+            received: 0,
+            runes_received: Vec::new(),
+            runes_sent: Vec::new(),
+            sent: 0,
+            txid,
+          });
+
+        let (value, inscriptions, runes) = if transfer.received {
+          (
+            &mut entry.received,
+            &mut entry.inscriptions_received,
+            &mut entry.runes_received,
+          )
+        } else {
+          (
+            &mut entry.sent,
+            &mut entry.inscriptions_sent,
+            &mut entry.runes_sent,
+          )
+        };
+
+        *value += transfer.value;
+
+        inscriptions.extend(transfer.inscriptions);
+
+        for (id, amount) in self
+          .rune_balances
+          .get(&transfer.outpoint)
+          .into_iter()
+          .flatten()
+        {
+          match runes.iter_mut().find(|(rune_id, _amount)| rune_id == id) {
+            Some((_id, total)) => *total += amount,
+            None => runes.push((*id, *amount)),
+          }
+        }
+      }
+
+      for (script_pubkey, entry) in activity {
+        activity_number_to_activity.insert(next_activity_number, entry.store())?;
+        script_pubkey_to_activity_number.insert(script_pubkey.as_slice(), next_activity_number)?;
+        next_activity_number += 1;
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,148 @@
+use super::*;
+
+/// Collects the children inscribed in a block, and, with the address index,
+/// the children moved from one script pubkey to another, and once the block
+/// has been indexed, updates the statistics of each affected collection, so
+/// collection statistics never need to be computed by scanning children.
+#[derive(Default)]
+pub(super) struct CollectionUpdater {
+  children: BTreeMap<u32, u64>,
+  content_types: BTreeMap<(u32, String), u64>,
+  holders: BTreeMap<(u32, Vec<u8>), i64>,
+}
+
+impl CollectionUpdater {
+  pub(super) fn is_empty(&self) -> bool {
+    self.children.is_empty() && self.holders.is_empty()
+  }
+
+  /// Record that a child of `parents` was inscribed, with `content_type`, in
+  /// an output with `script_pubkey`.
+  pub(super) fn inscribe(
+    &mut self,
+    parents: &[u32],
+    content_type: Option<&str>,
+    script_pubkey: Option<&[u8]>,
+  ) {
+    let content_type = content_type.map(listing::essence).unwrap_or_default();
+
+    for &parent in parents {
+      *self.children.entry(parent).or_default() += 1;
+
+      *self
+        .content_types
+        .entry((parent, content_type.clone()))
+        .or_default() += 1;
+    }
+
+    self.transfer(parents, None, script_pubkey);
+  }
+
+  /// Record that a child of `parents` moved from an output with script pubkey
+  /// `from` to an output with script pubkey `to`.
+  pub(super) fn transfer(&mut self, parents: &[u32], from: Option<&[u8]>, to: Option<&[u8]>) {
+    if from == to {
+      return;
+    }
+
+    for &parent in parents {
+      if let Some(from) = from {
+        *self.holders.entry((parent, from.to_vec())).or_default() -= 1;
+      }
+
+      if let Some(to) = to {
+        *self.holders.entry((parent, to.to_vec())).or_default() += 1;
+      }
+    }
+  }
+
+  pub(super) fn update(
+    self,
+    height: u32,
+    sequence_number_to_collection_stats: &mut Table<'_, u32, (u64, u64)>,
+    sequence_number_and_content_type_to_children: &mut Table<'_, (u32, &'static str), u64>,
+    sequence_number_and_height_to_children: &mut Table<'_, (u32, u32), u64>,
+    sequence_number_and_script_pubkey_to_children: &mut Table<'_, (u32, &'static [u8]), u64>,
+    sequence_number_children_and_script_pubkey: &mut Table<'_, (u32, u64, &'static [u8]), ()>,
+  ) -> Result {
+    for ((parent, content_type), children) in self.content_types {
+      let old = sequence_number_and_content_type_to_children
+        .get((parent, content_type.as_str()))?
+        .map(|children| children.value())
+        .unwrap_or_default();
+
+      sequence_number_and_content_type_to_children
+        .insert((parent, content_type.as_str()), old + children)?;
+    }
+
+    let mut holders = BTreeMap::<u32, i64>::new();
+
+    for ((parent, script_pubkey), change) in self.holders {
+      if change == 0 {
+        continue;
+      }
+
+      let old = sequence_number_and_script_pubkey_to_children
+        .get((parent, script_pubkey.as_slice()))?
+        .map(|children| children.value())
+        .unwrap_or_default();
+
+      let new = old.checked_add_signed(change).unwrap();
+
+      if old > 0 {
+        sequence_number_children_and_script_pubkey.remove((
+          parent,
+          old,
+          script_pubkey.as_slice(),
+        ))?;
+      }
+
+      if new > 0 {
+        sequence_number_and_script_pubkey_to_children
+          .insert((parent, script_pubkey.as_slice()), new)?;
+        sequence_number_children_and_script_pubkey
+          .insert((parent, new, script_pubkey.as_slice()), ())?;
+      } else {
+        sequence_number_and_script_pubkey_to_children.remove((parent, script_pubkey.as_slice()))?;
+      }
+
+      if old == 0 {
+        *holders.entry(parent).or_default() += 1;
+      } else if new == 0 {
+        *holders.entry(parent).or_default() -= 1;
+      }
+    }
+
+    let parents = self
+      .children
+      .keys()
+      .chain(holders.keys())
+      .copied()
+      .collect::<BTreeSet<u32>>();
+
+    for parent in parents {
+      let (old_children, old_holders) = sequence_number_to_collection_stats
+        .get(parent)?
+        .map(|stats| stats.value())
+        .unwrap_or_default();
+
+      let children = self.children.get(&parent).copied().unwrap_or_default();
+
+      if children > 0 {
+        sequence_number_and_height_to_children.insert((parent, height), children)?;
+      }
+
+      sequence_number_to_collection_stats.insert(
+        parent,
+        (
+          old_children + children,
+          old_holders
+            .checked_add_signed(holders.get(&parent).copied().unwrap_or_default())
+            .unwrap(),
+        ),
+      )?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+pub(super) struct EventEmitter<'tx> {
+  event_number_to_event: Option<Table<'tx, u64, &'static [u8]>>,
+  next_event_number: u64,
+  sender: Option<mpsc::Sender<Event>>,
+}
+
+impl<'tx> EventEmitter<'tx> {
+  pub(super) fn new(index: &Index, wtx: &'tx WriteTransaction, undo_log: &UndoLog) -> Result<Self> {
+    let event_number_to_event = if Index::has_webhooks(wtx)? {
+      Some(undo_log.open_table(wtx, EVENT_NUMBER_TO_EVENT)?)
+    } else {
+      None
+    };
+
+    let next_event_number = match &event_number_to_event {
+      Some(event_number_to_event) => event_number_to_event
+        .last()?
+        .map(|(number, _)| number.value() + 1)
+        .unwrap_or_default(),
+      None => 0,
+    };
+
+    Ok(Self {
+      event_number_to_event,
+      next_event_number,
+      sender: index.event_sender.clone(),
+    })
+  }
+
+  pub(super) fn emit(&mut self, event: Event) -> Result {
+    if let Some(event_number_to_event) = &mut self.event_number_to_event {
+      event_number_to_event.insert(
+        self.next_event_number,
+        serde_json::to_vec(&event)?.as_slice(),
+      )?;
+      self.next_event_number += 1;
+    }
+
+    if let Some(sender) = &self.sender {
+      sender.blocking_send(event)?;
+    }
+
+    Ok(())
+  }
+}
@@ -23,23 +23,29 @@ pub(super) struct Flotsam {
 #[derive(Debug, Clone)]
 enum Origin {
   New {
+    content: ContentEntry,
     cursed: bool,
     fee: u64,
     hidden: bool,
     parents: Vec<InscriptionId>,
     reinscription: bool,
+    search_terms: BTreeSet<String>,
     unbound: bool,
     vindicated: bool,
   },
   Old {
     sequence_number: u32,
     old_satpoint: SatPoint,
+    old_script_pubkey: Option<Vec<u8>>,
   },
 }
 
 pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) blessed_inscription_count: u64,
+  pub(super) collections: CollectionUpdater,
+  pub(super) contents: Vec<(u32, u64, ContentEntry)>,
   pub(super) cursed_inscription_count: u64,
+  pub(super) event_emitter: &'a mut EventEmitter<'tx>,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
   pub(super) home_inscription_count: u64,
@@ -49,8 +55,10 @@ pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) lost_sats: u64,
   pub(super) next_sequence_number: u32,
   pub(super) reward: u64,
+  pub(super) search_terms: Vec<(u32, BTreeSet<String>)>,
   pub(super) transaction_buffer: Vec<u8>,
   pub(super) transaction_id_to_transaction: &'a mut Table<'tx, &'static TxidValue, &'static [u8]>,
+  pub(super) transfers: Vec<(u32, TransferEntry)>,
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
@@ -63,6 +71,8 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     &mut self,
     tx: &Transaction,
     txid: Txid,
+    envelopes: &[ParsedEnvelope],
+    hogex: bool,
     input_utxo_entries: &[ParsedUtxoEntry],
     output_utxo_entries: &mut [UtxoEntryBuf],
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
@@ -76,9 +86,8 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     let mut total_input_value = 0;
     let total_output_value = tx.output.iter().map(|txout| txout.value).sum::<u64>();
 
-    let envelopes = ParsedEnvelope::from_transaction(tx);
     let has_new_inscriptions = !envelopes.is_empty();
-    let mut envelopes = envelopes.into_iter().peekable();
+    let mut envelopes = envelopes.iter().peekable();
 
     for (input_index, txin) in tx.input.iter().enumerate() {
       // skip subsidy since no inscriptions possible
@@ -113,6 +122,9 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           origin: Origin::Old {
             sequence_number,
             old_satpoint,
+            old_script_pubkey: index
+              .index_addresses
+              .then(|| input_utxo_entries[input_index].script_pubkey().to_vec()),
           },
         });
 
@@ -192,11 +204,17 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           inscription_id,
           offset,
           origin: Origin::New {
+            content: ContentEntry::new(&inscription.payload),
             cursed: curse.is_some() && !jubilant,
             fee: 0,
             hidden: inscription.payload.hidden(),
             parents: inscription.payload.parents(),
             reinscription: inscribed_offsets.contains_key(&offset),
+            search_terms: if index.index_search {
+              search::inscription_terms(&inscription.payload)
+            } else {
+              BTreeSet::new()
+            },
             unbound: input_value == 0
               || curse == Some(Curse::UnrecognizedEvenField)
               || inscription.payload.unrecognized_even_field,
@@ -271,7 +289,8 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
 
     let mut new_locations = Vec::new();
     let mut output_value = 0;
-    for (vout, txout) in tx.output.iter().enumerate() {
+    for vout in mweb::output_order(tx, hogex) {
+      let txout = &tx.output[vout];
       let end = output_value + txout.value;
 
       while let Some(flotsam) = inscriptions.peek() {
@@ -305,7 +324,9 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
         input_sat_ranges,
         flotsam,
         new_satpoint,
+        txid,
         op_return,
+        Some(&tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()].script_pubkey),
         Some(output_utxo_entry),
         utxo_cache,
         index,
@@ -322,8 +343,10 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           input_sat_ranges,
           flotsam,
           new_satpoint,
+          txid,
           false,
           None,
+          None,
           utxo_cache,
           index,
         )?;
@@ -365,18 +388,25 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     input_sat_ranges: Option<&Vec<&[u8]>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
+    txid: Txid,
     op_return: bool,
+    script_pubkey: Option<&Script>,
     mut normal_output_utxo_entry: Option<&mut UtxoEntryBuf>,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
     index: &Index,
   ) -> Result {
     let inscription_id = flotsam.inscription_id;
+    let old_satpoint = match flotsam.origin {
+      Origin::Old { old_satpoint, .. } => Some(old_satpoint),
+      Origin::New { .. } => None,
+    };
     let (unbound, sequence_number) = match flotsam.origin {
       Origin::Old {
         sequence_number,
         old_satpoint,
+        old_script_pubkey,
       } => {
-        if op_return {
+        if op_return || old_script_pubkey.is_some() {
           let entry = InscriptionEntry::load(
             self
               .sequence_number_to_entry
@@ -385,33 +415,43 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
               .value(),
           );
 
-          let mut charms = entry.charms;
-          Charm::Burned.set(&mut charms);
+          if let Some(old_script_pubkey) = old_script_pubkey {
+            self.collections.transfer(
+              &entry.parents,
+              Some(old_script_pubkey.as_slice()),
+              script_pubkey.filter(|_| !op_return).map(Script::as_bytes),
+            );
+          }
 
-          self.sequence_number_to_entry.insert(
-            sequence_number,
-            &InscriptionEntry { charms, ..entry }.store(),
-          )?;
-        }
+          if op_return {
+            let mut charms = entry.charms;
+            Charm::Burned.set(&mut charms);
 
-        if let Some(ref sender) = index.event_sender {
-          sender.blocking_send(Event::InscriptionTransferred {
-            block_height: self.height,
-            inscription_id,
-            new_location: new_satpoint,
-            old_location: old_satpoint,
-            sequence_number,
-          })?;
+            self.sequence_number_to_entry.insert(
+              sequence_number,
+              &InscriptionEntry { charms, ..entry }.store(),
+            )?;
+          }
         }
 
+        self.event_emitter.emit(Event::InscriptionTransferred {
+          block_height: self.height,
+          inscription_id,
+          new_location: new_satpoint,
+          old_location: old_satpoint,
+          sequence_number,
+        })?;
+
         (false, sequence_number)
       }
       Origin::New {
+        content,
         cursed,
         fee,
         hidden,
         parents,
         reinscription,
+        search_terms,
         unbound,
         vindicated,
       } => {
@@ -489,17 +529,25 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           })
           .collect::<Result<Vec<u32>>>()?;
 
-        if let Some(ref sender) = index.event_sender {
-          sender.blocking_send(Event::InscriptionCreated {
-            block_height: self.height,
-            charms,
-            inscription_id,
-            location: (!unbound).then_some(new_satpoint),
-            parent_inscription_ids: parents,
-            sequence_number,
-          })?;
+        if !parent_sequence_numbers.is_empty() {
+          self.collections.inscribe(
+            &parent_sequence_numbers,
+            content.content_type.as_deref(),
+            script_pubkey
+              .filter(|_| index.index_addresses && !op_return && !unbound)
+              .map(Script::as_bytes),
+          );
         }
 
+        self.event_emitter.emit(Event::InscriptionCreated {
+          block_height: self.height,
+          charms,
+          inscription_id,
+          location: (!unbound).then_some(new_satpoint),
+          parent_inscription_ids: parents,
+          sequence_number,
+        })?;
+
         self.sequence_number_to_entry.insert(
           sequence_number,
           &InscriptionEntry {
@@ -520,6 +568,14 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           .id_to_sequence_number
           .insert(&inscription_id.store(), sequence_number)?;
 
+        if index.index_inscription_details {
+          self.contents.push((sequence_number, fee, content));
+        }
+
+        if !search_terms.is_empty() {
+          self.search_terms.push((sequence_number, search_terms));
+        }
+
         if !hidden {
           self
             .home_inscriptions
@@ -561,6 +617,20 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
 
     output_utxo_entry.push_inscription(sequence_number, satpoint.offset, index);
 
+    if index.index_inscription_history {
+      self.transfers.push((
+        sequence_number,
+        TransferEntry {
+          height: self.height,
+          new_satpoint: satpoint,
+          old_satpoint,
+          script_pubkey: script_pubkey.filter(|_| !unbound).map(Script::to_owned),
+          timestamp: self.timestamp,
+          txid,
+        },
+      ));
+    }
+
     Ok(())
   }
 }
@@ -0,0 +1,137 @@
+use super::*;
+
+/// Collects the rune balances received and spent by outputs in a block, the
+/// script pubkeys whose balances changed, and the runes etched, minted and
+/// burned, and once the block has been indexed, records the balance changes,
+/// the balance of each affected script pubkey, and the state of each affected
+/// rune at the end of the block.
+#[derive(Default)]
+pub(super) struct RuneHistoryUpdater {
+  changes: Vec<(RuneId, RuneBalanceChange)>,
+  holders: BTreeSet<(RuneId, Vec<u8>)>,
+  runes: BTreeSet<RuneId>,
+}
+
+impl RuneHistoryUpdater {
+  pub(super) fn receive(
+    &mut self,
+    height: u32,
+    outpoint: OutPoint,
+    script_pubkey: &[u8],
+    balances: impl IntoIterator<Item = (RuneId, u128)>,
+  ) {
+    self.change(height, outpoint, script_pubkey, balances, false);
+  }
+
+  pub(super) fn spend(
+    &mut self,
+    height: u32,
+    outpoint: OutPoint,
+    script_pubkey: &[u8],
+    balances: impl IntoIterator<Item = (RuneId, u128)>,
+  ) {
+    self.change(height, outpoint, script_pubkey, balances, true);
+  }
+
+  fn change(
+    &mut self,
+    height: u32,
+    outpoint: OutPoint,
+    script_pubkey: &[u8],
+    balances: impl IntoIterator<Item = (RuneId, u128)>,
+    spent: bool,
+  ) {
+    for (id, amount) in balances {
+      self.runes.insert(id);
+      self.holders.insert((id, script_pubkey.to_vec()));
+      self.changes.push((
+        id,
+        RuneBalanceChange {
+          amount,
+          height,
+          outpoint,
+          spent,
+        },
+      ));
+    }
+  }
+
+  /// Record that a rune was etched, minted or burned in this block.
+  pub(super) fn touch(&mut self, id: RuneId) {
+    self.runes.insert(id);
+  }
+
+  pub(super) fn update(
+    self,
+    height: u32,
+    id_to_entry: &Table<'_, RuneIdValue, RuneEntryValue>,
+    rune_id_and_script_pubkey_to_balance: &Table<'_, (RuneIdValue, &'static [u8]), u128>,
+    rune_id_script_pubkey_and_height_to_balance: &mut Table<
+      '_,
+      (RuneIdValue, &'static [u8], u32),
+      u128,
+    >,
+    rune_id_to_balance_change: &mut MultimapTable<'_, RuneIdValue, RuneBalanceChangeValue>,
+    rune_id_and_height_to_rune_stats: &mut Table<'_, (RuneIdValue, u32), RuneStatsEntryValue>,
+  ) -> Result {
+    for (id, change) in self.changes {
+      rune_id_to_balance_change.insert(id.store(), change.store())?;
+    }
+
+    let mut holders = HashMap::<RuneId, i64>::new();
+
+    for (id, script_pubkey) in self.holders {
+      let old = rune_id_script_pubkey_and_height_to_balance
+        .range(
+          (id.store(), script_pubkey.as_slice(), 0)..(id.store(), script_pubkey.as_slice(), height),
+        )?
+        .next_back()
+        .transpose()?
+        .map(|(_key, balance)| balance.value())
+        .unwrap_or_default();
+
+      let new = rune_id_and_script_pubkey_to_balance
+        .get((id.store(), script_pubkey.as_slice()))?
+        .map(|balance| balance.value())
+        .unwrap_or_default();
+
+      if old == new {
+        continue;
+      }
+
+      rune_id_script_pubkey_and_height_to_balance
+        .insert((id.store(), script_pubkey.as_slice(), height), new)?;
+
+      if old == 0 {
+        *holders.entry(id).or_default() += 1;
+      } else if new == 0 {
+        *holders.entry(id).or_default() -= 1;
+      }
+    }
+
+    for id in self.runes {
+      let previous = rune_id_and_height_to_rune_stats
+        .range((id.store(), 0)..(id.store(), height))?
+        .next_back()
+        .transpose()?
+        .map(|(_key, stats)| RuneStatsEntry::load(stats.value()))
+        .unwrap_or_default();
+
+      let entry = RuneEntry::load(id_to_entry.get(id.store())?.unwrap().value());
+
+      let stats = RuneStatsEntry {
+        burned: entry.burned,
+        holders: previous
+          .holders
+          .checked_add_signed(holders.get(&id).copied().unwrap_or_default())
+          .unwrap(),
+        mints: entry.mints,
+        supply: entry.supply(),
+      };
+
+      rune_id_and_height_to_rune_stats.insert((id.store(), height), stats.store())?;
+    }
+
+    Ok(())
+  }
+}
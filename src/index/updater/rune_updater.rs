@@ -1,43 +1,58 @@
 use super::*;
 
 pub(super) struct RuneUpdater<'a, 'tx, 'client> {
+  pub(super) address_history_updater: Option<&'a mut AddressHistoryUpdater>,
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
   pub(super) client: &'client Client,
-  pub(super) event_sender: Option<&'a mpsc::Sender<Event>>,
+  pub(super) event_emitter: &'a mut EventEmitter<'tx>,
   pub(super) height: u32,
+  pub(super) id_and_script_pubkey_to_balance: Option<(
+    &'a mut Table<'tx, (RuneIdValue, &'static [u8]), u128>,
+    &'a mut Table<'tx, (RuneIdValue, u128, &'static [u8]), ()>,
+  )>,
+  pub(super) id_balance_and_outpoint: &'a mut Table<'tx, (RuneIdValue, u128, OutPointValue), ()>,
   pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) rune_history_updater: Option<&'a mut RuneHistoryUpdater>,
   pub(super) rune_to_id: &'a mut Table<'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
+  pub(super) search_term_and_rune_id: Option<&'a mut Table<'tx, (&'static str, RuneIdValue), ()>>,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'tx, u32, RuneIdValue>,
+  pub(super) spent_script_pubkeys: HashMap<OutPoint, Vec<u8>>,
   pub(super) statistic_to_count: &'a mut Table<'tx, u64, u64>,
   pub(super) transaction_id_to_rune: &'a mut Table<'tx, &'static TxidValue, u128>,
 }
 
 impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
-  pub(super) fn index_runes(&mut self, tx_index: u32, tx: &Transaction, txid: Txid) -> Result<()> {
-    let artifact = Runestone::decipher(tx);
-
+  pub(super) fn index_runes(
+    &mut self,
+    tx_index: u32,
+    tx: &Transaction,
+    txid: Txid,
+    artifact: Option<&Artifact>,
+  ) -> Result<()> {
     let mut unallocated = self.unallocated(tx)?;
 
     let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
 
-    if let Some(artifact) = &artifact {
+    if let Some(artifact) = artifact {
       if let Some(id) = artifact.mint() {
         if let Some(amount) = self.mint(id)? {
           *unallocated.entry(id).or_default() += amount;
 
-          if let Some(sender) = self.event_sender {
-            sender.blocking_send(Event::RuneMinted {
-              block_height: self.height,
-              txid,
-              rune_id: id,
-              amount: amount.n(),
-            })?;
+          if let Some(rune_history_updater) = self.rune_history_updater.as_deref_mut() {
+            rune_history_updater.touch(id);
           }
+
+          self.event_emitter.emit(Event::RuneMinted {
+            block_height: self.height,
+            txid,
+            rune_id: id,
+            amount: amount.n(),
+          })?;
         }
       }
 
@@ -195,18 +210,50 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
         vout: vout.try_into().unwrap(),
       };
 
+      if let Some(address_history_updater) = self.address_history_updater.as_deref_mut() {
+        address_history_updater.rune_balances(
+          outpoint,
+          balances
+            .iter()
+            .map(|(id, balance)| (*id, balance.n()))
+            .collect(),
+        );
+      }
+
+      if let Some(rune_history_updater) = self.rune_history_updater.as_deref_mut() {
+        rune_history_updater.receive(
+          self.height,
+          outpoint,
+          tx.output[vout].script_pubkey.as_bytes(),
+          balances.iter().map(|(id, balance)| (*id, balance.n())),
+        );
+      }
+
       for (id, balance) in balances {
         Index::encode_rune_balance(id, balance.n(), &mut buffer);
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::RuneTransferred {
-            outpoint,
-            block_height: self.height,
-            txid,
-            rune_id: id,
-            amount: balance.0,
-          })?;
+        self
+          .id_balance_and_outpoint
+          .insert((id.store(), balance.n(), outpoint.store()), ())?;
+
+        if let Some((to_balance, by_balance)) = self.id_and_script_pubkey_to_balance.as_mut() {
+          Self::update_script_pubkey_balance(
+            to_balance,
+            by_balance,
+            id,
+            tx.output[vout].script_pubkey.as_bytes(),
+            balance.n(),
+            true,
+          )?;
         }
+
+        self.event_emitter.emit(Event::RuneTransferred {
+          outpoint,
+          block_height: self.height,
+          txid,
+          rune_id: id,
+          amount: balance.0,
+        })?;
       }
 
       self
@@ -218,14 +265,16 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
     for (id, amount) in burned {
       *self.burned.entry(id).or_default() += amount;
 
-      if let Some(sender) = self.event_sender {
-        sender.blocking_send(Event::RuneBurned {
-          block_height: self.height,
-          txid,
-          rune_id: id,
-          amount: amount.n(),
-        })?;
+      if let Some(rune_history_updater) = self.rune_history_updater.as_deref_mut() {
+        rune_history_updater.touch(id);
       }
+
+      self.event_emitter.emit(Event::RuneBurned {
+        block_height: self.height,
+        txid,
+        rune_id: id,
+        amount: amount.n(),
+      })?;
     }
 
     Ok(())
@@ -308,14 +357,22 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
     self.id_to_entry.insert(id.store(), entry.store())?;
 
-    if let Some(sender) = self.event_sender {
-      sender.blocking_send(Event::RuneEtched {
-        block_height: self.height,
-        txid,
-        rune_id: id,
-      })?;
+    if let Some(rune_history_updater) = self.rune_history_updater.as_deref_mut() {
+      rune_history_updater.touch(id);
+    }
+
+    if let Some(search_term_and_rune_id) = self.search_term_and_rune_id.as_deref_mut() {
+      for term in search::rune_terms(entry.spaced_rune) {
+        search_term_and_rune_id.insert((term.as_str(), id.store()), ())?;
+      }
     }
 
+    self.event_emitter.emit(Event::RuneEtched {
+      block_height: self.height,
+      txid,
+      rune_id: id,
+    })?;
+
     let inscription_id = InscriptionId { txid, index: 0 };
 
     if let Some(sequence_number) = self
@@ -466,6 +523,41 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
     Ok(false)
   }
 
+  fn update_script_pubkey_balance(
+    to_balance: &mut Table<'tx, (RuneIdValue, &'static [u8]), u128>,
+    by_balance: &mut Table<'tx, (RuneIdValue, u128, &'static [u8]), ()>,
+    id: RuneId,
+    script_pubkey: &[u8],
+    amount: u128,
+    received: bool,
+  ) -> Result {
+    let key = (id.store(), script_pubkey);
+
+    let old = to_balance
+      .get(key)?
+      .map(|balance| balance.value())
+      .unwrap_or(0);
+
+    let new = if received {
+      old.checked_add(amount).unwrap()
+    } else {
+      old.checked_sub(amount).unwrap()
+    };
+
+    if old > 0 {
+      by_balance.remove((id.store(), old, script_pubkey))?;
+    }
+
+    if new == 0 {
+      to_balance.remove(key)?;
+    } else {
+      to_balance.insert(key, new)?;
+      by_balance.insert((id.store(), new, script_pubkey), ())?;
+    }
+
+    Ok(())
+  }
+
   fn unallocated(&mut self, tx: &Transaction) -> Result<HashMap<RuneId, Lot>> {
     // map of rune ID to un-allocated balance of that rune
     let mut unallocated: HashMap<RuneId, Lot> = HashMap::new();
@@ -477,11 +569,50 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
         .remove(&input.previous_output.store())?
       {
         let buffer = guard.value();
+        let mut balances = Vec::new();
         let mut i = 0;
         while i < buffer.len() {
           let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
           i += len;
           *unallocated.entry(id).or_default() += balance;
+          balances.push((id, balance));
+
+          self.id_balance_and_outpoint.remove((
+            id.store(),
+            balance,
+            input.previous_output.store(),
+          ))?;
+        }
+
+        if let Some((to_balance, by_balance)) = self.id_and_script_pubkey_to_balance.as_mut() {
+          let script_pubkey = self
+            .spent_script_pubkeys
+            .remove(&input.previous_output)
+            .ok_or_else(|| anyhow!("script pubkey of {} not found", input.previous_output))?;
+
+          for (id, balance) in &balances {
+            Self::update_script_pubkey_balance(
+              to_balance,
+              by_balance,
+              *id,
+              &script_pubkey,
+              *balance,
+              false,
+            )?;
+          }
+
+          if let Some(rune_history_updater) = self.rune_history_updater.as_deref_mut() {
+            rune_history_updater.spend(
+              self.height,
+              input.previous_output,
+              &script_pubkey,
+              balances.iter().copied(),
+            );
+          }
+        }
+
+        if let Some(address_history_updater) = self.address_history_updater.as_deref_mut() {
+          address_history_updater.rune_balances(input.previous_output, balances);
         }
       }
     }
@@ -109,6 +109,27 @@ impl Media {
     ("video/webm",                  GENERIC, Video,            &["webm"]),
   ];
 
+  pub(crate) const KINDS: &'static [&'static str] = &[
+    "audio", "code", "font", "iframe", "image", "markdown", "model", "pdf", "text", "unknown",
+    "video",
+  ];
+
+  pub(crate) fn kind(self) -> &'static str {
+    match self {
+      Audio => "audio",
+      Code(_) => "code",
+      Font => "font",
+      Iframe => "iframe",
+      Image(_) => "image",
+      Markdown => "markdown",
+      Model => "model",
+      Pdf => "pdf",
+      Text => "text",
+      Unknown => "unknown",
+      Video => "video",
+    }
+  }
+
   pub(crate) fn content_type_for_path(
     path: &Path,
   ) -> Result<(&'static str, BrotliEncoderMode), Error> {
@@ -220,6 +241,13 @@ mod tests {
     assert!(Media::check_mp4_codec(Path::new("examples/av1.mp4")).is_err(),);
   }
 
+  #[test]
+  fn kinds() {
+    for (_, _, media, _) in Media::TABLE {
+      assert!(Media::KINDS.contains(&media.kind()));
+    }
+  }
+
   #[test]
   fn no_duplicate_extensions() {
     let mut set = HashSet::new();
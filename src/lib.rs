@@ -28,6 +28,7 @@ use {
     settings::Settings,
     subcommand::{OutputFormat, Subcommand, SubcommandResult},
     tally::Tally,
+    transfer::Transfer,
   },
   anyhow::{anyhow, bail, ensure, Context, Error},
   bip39::Mnemonic,
@@ -114,6 +115,7 @@ pub mod index;
 mod inscriptions;
 mod into_usize;
 mod macros;
+mod mweb;
 mod object;
 pub mod options;
 pub mod outgoing;
@@ -124,6 +126,7 @@ pub mod settings;
 pub mod subcommand;
 mod tally;
 pub mod templates;
+mod transfer;
 pub mod wallet;
 
 type Result<T = (), E = Error> = std::result::Result<T, E>;
@@ -140,6 +143,7 @@ fn fund_raw_transaction(
   client: &Client,
   fee_rate: FeeRate,
   unfunded_transaction: &Transaction,
+  change_address: Option<Address>,
 ) -> Result<Vec<u8>> {
   let mut buffer = Vec::new();
 
@@ -162,6 +166,7 @@ fn fund_raw_transaction(
           // by 1000.
           fee_rate: Some(Amount::from_sat((fee_rate.n() * 1000.0).ceil() as u64)),
           change_position: Some(unfunded_transaction.output.len().try_into()?),
+          change_address,
           ..default()
         }),
         Some(false),
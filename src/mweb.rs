@@ -0,0 +1,140 @@
+//! Recognition of MimbleWimble Extension Block (MWEB) transactions.
+//!
+//! Every block with MWEB activity ends with an integrating transaction, the
+//! HogEx, whose first output is the HogAddr that holds the value of every coin
+//! inside MWEB. The HogEx spends the previous HogAddr and any peg-in outputs
+//! created earlier in the block, and pays any peg-outs after the HogAddr.
+//!
+//! MWEB amounts are confidential, so sats cannot be followed through it. They
+//! are tracked as sitting on the HogAddr while they are inside MWEB, and leave
+//! it first-in-first-out when coins are pegged out.
+
+use {
+  super::*,
+  bitcoin::opcodes::{
+    self,
+    all::{OP_PUSHBYTES_32, OP_PUSHNUM_8, OP_PUSHNUM_9},
+  },
+};
+
+const ADDRESS_PREFIXES: [&str; 2] = ["ltcmweb1", "tmweb1"];
+
+fn is_program(script: &Script, version: opcodes::All) -> bool {
+  let bytes = script.as_bytes();
+  bytes.len() == 34 && bytes[0] == version.to_u8() && bytes[1] == OP_PUSHBYTES_32.to_u8()
+}
+
+pub(crate) fn is_hog_addr(script: &Script) -> bool {
+  is_program(script, OP_PUSHNUM_8)
+}
+
+pub(crate) fn is_peg_in(script: &Script) -> bool {
+  is_program(script, OP_PUSHNUM_9)
+}
+
+/// Returns true if sats sent to `script` enter MWEB.
+pub(crate) fn is_mweb_script(script: &Script) -> bool {
+  is_hog_addr(script) || is_peg_in(script)
+}
+
+/// Returns true if `tx`, at `tx_offset` in a block of `tx_count`
+/// transactions, is the HogEx.
+pub(crate) fn is_hogex(tx: &Transaction, tx_offset: usize, tx_count: usize) -> bool {
+  tx_offset > 0
+    && tx_offset + 1 == tx_count
+    && tx
+      .output
+      .first()
+      .map(|output| is_hog_addr(&output.script_pubkey))
+      .unwrap_or_default()
+}
+
+/// The order in which sats are assigned to the outputs of a transaction.
+///
+/// The HogEx pays peg-outs before the HogAddr, so that peg-outs receive the
+/// sats that have been in MWEB the longest, instead of the sats that were
+/// pegged in earlier in the same block.
+pub(crate) fn output_order(tx: &Transaction, hogex: bool) -> impl Iterator<Item = usize> {
+  let outputs = tx.output.len();
+  let hog_addr = usize::from(hogex && outputs > 0);
+  (hog_addr..outputs).chain(0..hog_addr)
+}
+
+pub(crate) fn is_mweb_address(address: &str) -> bool {
+  let address = address.to_lowercase();
+  ADDRESS_PREFIXES
+    .iter()
+    .any(|prefix| address.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn program(version: u8) -> ScriptBuf {
+    let mut bytes = vec![version, OP_PUSHBYTES_32.to_u8()];
+    bytes.extend([0; 32]);
+    ScriptBuf::from_bytes(bytes)
+  }
+
+  fn tx(script_pubkey: ScriptBuf) -> Transaction {
+    Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: vec![
+        TxOut {
+          value: 1,
+          script_pubkey,
+        },
+        TxOut {
+          value: 1,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 1,
+          script_pubkey: ScriptBuf::new(),
+        },
+      ],
+    }
+  }
+
+  #[test]
+  fn scripts() {
+    assert!(is_hog_addr(&program(OP_PUSHNUM_8.to_u8())));
+    assert!(!is_hog_addr(&program(OP_PUSHNUM_9.to_u8())));
+    assert!(is_peg_in(&program(OP_PUSHNUM_9.to_u8())));
+    assert!(!is_peg_in(&program(OP_PUSHNUM_8.to_u8())));
+    assert!(!is_mweb_script(&program(0x51)));
+    assert!(!is_mweb_script(&ScriptBuf::new()));
+
+    let mut truncated = program(OP_PUSHNUM_8.to_u8()).into_bytes();
+    truncated.pop();
+    assert!(!is_hog_addr(&ScriptBuf::from_bytes(truncated)));
+  }
+
+  #[test]
+  fn hogex_must_be_last_non_coinbase_transaction() {
+    let hogex = tx(program(OP_PUSHNUM_8.to_u8()));
+    assert!(is_hogex(&hogex, 2, 3));
+    assert!(!is_hogex(&hogex, 1, 3));
+    assert!(!is_hogex(&hogex, 0, 1));
+    assert!(!is_hogex(&tx(program(OP_PUSHNUM_9.to_u8())), 2, 3));
+  }
+
+  #[test]
+  fn hogex_pays_hog_addr_last() {
+    let tx = tx(program(OP_PUSHNUM_8.to_u8()));
+    assert_eq!(output_order(&tx, false).collect::<Vec<usize>>(), [0, 1, 2]);
+    assert_eq!(output_order(&tx, true).collect::<Vec<usize>>(), [1, 2, 0]);
+  }
+
+  #[test]
+  fn mweb_addresses() {
+    assert!(is_mweb_address("ltcmweb1qq0000"));
+    assert!(is_mweb_address("LTCMWEB1QQ0000"));
+    assert!(is_mweb_address("tmweb1qq0000"));
+    assert!(!is_mweb_address("ltc1qq0000"));
+    assert!(!is_mweb_address("tltc1qq0000"));
+  }
+}
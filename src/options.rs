@@ -42,6 +42,11 @@ pub struct Options {
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Track transaction history of addresses, including spent outputs. Implies --index-addresses."
+  )]
+  pub(crate) index_address_history: bool,
   #[arg(long, help = "Track unspent output addresses.")]
   pub(crate) index_addresses: bool,
   #[arg(
@@ -49,6 +54,18 @@ pub struct Options {
     help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
   )]
   pub(crate) index_cache_size: Option<usize>,
+  #[arg(
+    long,
+    help = "Track inscription content types, metaprotocols, sizes and fees, for filtering and sorting inscriptions. Implied by --index-search."
+  )]
+  pub(crate) index_inscription_details: bool,
+  #[arg(long, help = "Track every transfer of every inscription.")]
+  pub(crate) index_inscription_history: bool,
+  #[arg(
+    long,
+    help = "Track rune balance changes, for holder snapshots at past heights. Requires --index-runes. Implies --index-addresses."
+  )]
+  pub(crate) index_rune_history: bool,
   #[arg(
     long,
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
@@ -56,10 +73,20 @@ pub struct Options {
   pub(crate) index_runes: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
+  #[arg(
+    long,
+    help = "Index inscription text, metadata and metaprotocols, and rune names, for search. Implies --index-inscription-details."
+  )]
+  pub(crate) index_search: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
   #[arg(long, help = "Run in integration test mode.")]
   pub(crate) integration_test: bool,
+  #[arg(
+    long,
+    help = "Keep <MAX_SAVEPOINTS> savepoints near the chain tip to recover from reorgs. [default: 2]"
+  )]
+  pub(crate) max_savepoints: Option<u32>,
   #[clap(long, short, long, help = "Specify output format. [default: json]")]
   pub(crate) format: Option<OutputFormat>,
   #[arg(
@@ -69,6 +96,16 @@ pub struct Options {
     help = "Do not index inscriptions."
   )]
   pub(crate) no_index_inscriptions: bool,
+  #[arg(
+    long,
+    help = "Read blocks directly from Litecoin Core's block files in <LITECOIN_DATA_DIR>, located using its block index, instead of over RPC, except near the chain tip."
+  )]
+  pub(crate) read_block_files: bool,
+  #[arg(
+    long,
+    help = "Create savepoints every <SAVEPOINT_INTERVAL> blocks. [default: 10]"
+  )]
+  pub(crate) savepoint_interval: Option<u32>,
   #[arg(
     long,
     help = "Require basic HTTP authentication with <SERVER_PASSWORD>. Credentials are sent in cleartext. Consider using authentication in conjunction with HTTPS."
@@ -79,6 +116,16 @@ pub struct Options {
     help = "Require basic HTTP authentication with <SERVER_USERNAME>. Credentials are sent in cleartext. Consider using authentication in conjunction with HTTPS."
   )]
   pub(crate) server_username: Option<String>,
+  #[arg(
+    long,
+    help = "Open the index read-only as a published snapshot, and reload it whenever the snapshot is replaced, for example by `ord server --snapshot` in a separate indexer."
+  )]
+  pub(crate) snapshot_reload: bool,
+  #[arg(
+    long,
+    help = "Recover from reorgs up to <UNDO_DEPTH> blocks deep by keeping a log of changes to the index, instead of using savepoints."
+  )]
+  pub(crate) undo_depth: Option<u32>,
   #[arg(long, short, help = "Use regtest. Equivalent to `--chain regtest`.")]
   pub(crate) regtest: bool,
   #[arg(long, short, help = "Use signet. Equivalent to `--chain signet`.")]
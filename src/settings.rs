@@ -18,16 +18,27 @@ pub struct Settings {
   hidden: Option<HashSet<InscriptionId>>,
   http_port: Option<u16>,
   index: Option<PathBuf>,
+  index_address_history: bool,
   index_addresses: bool,
   index_cache_size: Option<usize>,
+  index_inscription_details: bool,
+  index_inscription_history: bool,
+  index_rune_history: bool,
   index_runes: bool,
   index_sats: bool,
+  index_search: bool,
   index_transactions: bool,
   integration_test: bool,
+  max_savepoints: Option<u32>,
   no_index_inscriptions: bool,
+  read_block_files: bool,
+  savepoint_interval: Option<u32>,
   server_password: Option<String>,
   server_url: Option<String>,
   server_username: Option<String>,
+  snapshot_reload: bool,
+  undo_depth: Option<u32>,
+  webhooks: Option<Vec<String>>,
 }
 
 impl Settings {
@@ -131,16 +142,27 @@ impl Settings {
       ),
       http_port: self.http_port.or(source.http_port),
       index: self.index.or(source.index),
+      index_address_history: self.index_address_history || source.index_address_history,
       index_addresses: self.index_addresses || source.index_addresses,
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
+      index_inscription_details: self.index_inscription_details || source.index_inscription_details,
+      index_inscription_history: self.index_inscription_history || source.index_inscription_history,
+      index_rune_history: self.index_rune_history || source.index_rune_history,
       index_runes: self.index_runes || source.index_runes,
       index_sats: self.index_sats || source.index_sats,
+      index_search: self.index_search || source.index_search,
       index_transactions: self.index_transactions || source.index_transactions,
       integration_test: self.integration_test || source.integration_test,
+      max_savepoints: self.max_savepoints.or(source.max_savepoints),
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
+      read_block_files: self.read_block_files || source.read_block_files,
+      savepoint_interval: self.savepoint_interval.or(source.savepoint_interval),
       server_password: self.server_password.or(source.server_password),
       server_url: self.server_url.or(source.server_url),
       server_username: self.server_username.or(source.server_username),
+      snapshot_reload: self.snapshot_reload || source.snapshot_reload,
+      undo_depth: self.undo_depth.or(source.undo_depth),
+      webhooks: self.webhooks.or(source.webhooks),
     }
   }
 
@@ -166,16 +188,27 @@ impl Settings {
       hidden: None,
       http_port: None,
       index: options.index,
+      index_address_history: options.index_address_history,
       index_addresses: options.index_addresses,
       index_cache_size: options.index_cache_size,
+      index_inscription_details: options.index_inscription_details,
+      index_inscription_history: options.index_inscription_history,
+      index_rune_history: options.index_rune_history,
       index_runes: options.index_runes,
       index_sats: options.index_sats,
+      index_search: options.index_search,
       index_transactions: options.index_transactions,
       integration_test: options.integration_test,
+      max_savepoints: options.max_savepoints,
       no_index_inscriptions: options.no_index_inscriptions,
+      read_block_files: options.read_block_files,
+      savepoint_interval: options.savepoint_interval,
       server_password: options.server_password,
       server_url: None,
       server_username: options.server_username,
+      snapshot_reload: options.snapshot_reload,
+      undo_depth: options.undo_depth,
+      webhooks: None,
     }
   }
 
@@ -214,6 +247,15 @@ impl Settings {
         })
     };
 
+    let urls = |key| {
+      env.get(key).map(|urls| {
+        urls
+          .split_whitespace()
+          .map(str::to_string)
+          .collect::<Vec<String>>()
+      })
+    };
+
     let get_u16 = |key| {
       env
         .get(key)
@@ -254,16 +296,27 @@ impl Settings {
       hidden: inscriptions("HIDDEN")?,
       http_port: get_u16("HTTP_PORT")?,
       index: get_path("INDEX"),
+      index_address_history: get_bool("INDEX_ADDRESS_HISTORY"),
       index_addresses: get_bool("INDEX_ADDRESSES"),
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
+      index_inscription_details: get_bool("INDEX_INSCRIPTION_DETAILS"),
+      index_inscription_history: get_bool("INDEX_INSCRIPTION_HISTORY"),
+      index_rune_history: get_bool("INDEX_RUNE_HISTORY"),
       index_runes: get_bool("INDEX_RUNES"),
       index_sats: get_bool("INDEX_SATS"),
+      index_search: get_bool("INDEX_SEARCH"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
       integration_test: get_bool("INTEGRATION_TEST"),
+      max_savepoints: get_u32("MAX_SAVEPOINTS")?,
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
+      read_block_files: get_bool("READ_BLOCK_FILES"),
+      savepoint_interval: get_u32("SAVEPOINT_INTERVAL")?,
       server_password: get_string("SERVER_PASSWORD"),
       server_url: get_string("SERVER_URL"),
       server_username: get_string("SERVER_USERNAME"),
+      snapshot_reload: get_bool("SNAPSHOT_RELOAD"),
+      undo_depth: get_u32("UNDO_DEPTH")?,
+      webhooks: urls("WEBHOOKS"),
     })
   }
 
@@ -284,22 +337,43 @@ impl Settings {
       hidden: None,
       http_port: None,
       index: None,
+      index_address_history: false,
       index_addresses: true,
       index_cache_size: None,
+      index_inscription_details: false,
+      index_inscription_history: false,
+      index_rune_history: false,
       index_runes: true,
       index_sats: true,
+      index_search: false,
       index_transactions: false,
       integration_test: false,
+      max_savepoints: None,
       no_index_inscriptions: false,
+      read_block_files: false,
+      savepoint_interval: None,
       server_password: None,
       server_url: Some(server_url.into()),
       server_username: None,
+      snapshot_reload: false,
+      undo_depth: None,
+      webhooks: None,
     }
   }
 
   pub fn or_defaults(self) -> Result<Self> {
     let chain = self.chain.unwrap_or_default();
 
+    ensure!(
+      self.max_savepoints != Some(0),
+      "`--max-savepoints` must be at least 1"
+    );
+
+    ensure!(
+      self.savepoint_interval != Some(0),
+      "`--savepoint-interval` must be at least 1"
+    );
+
     let bitcoin_data_dir = match &self.litecoin_data_dir {
       Some(bitcoin_data_dir) => bitcoin_data_dir.clone(),
       None => {
@@ -351,6 +425,7 @@ impl Settings {
       hidden: self.hidden,
       http_port: self.http_port,
       index: Some(index),
+      index_address_history: self.index_address_history,
       index_addresses: self.index_addresses,
       index_cache_size: Some(match self.index_cache_size {
         Some(index_cache_size) => index_cache_size,
@@ -360,14 +435,24 @@ impl Settings {
           usize::try_from(sys.total_memory() / 4)?
         }
       }),
+      index_inscription_details: self.index_inscription_details,
+      index_inscription_history: self.index_inscription_history,
+      index_rune_history: self.index_rune_history,
       index_runes: self.index_runes,
       index_sats: self.index_sats,
+      index_search: self.index_search,
       index_transactions: self.index_transactions,
       integration_test: self.integration_test,
+      max_savepoints: Some(self.max_savepoints.unwrap_or(2)),
       no_index_inscriptions: self.no_index_inscriptions,
+      read_block_files: self.read_block_files,
+      savepoint_interval: Some(self.savepoint_interval.unwrap_or(10)),
       server_password: self.server_password,
       server_url: self.server_url,
       server_username: self.server_username,
+      snapshot_reload: self.snapshot_reload,
+      undo_depth: self.undo_depth,
+      webhooks: self.webhooks,
     })
   }
 
@@ -473,21 +558,37 @@ impl Settings {
       return Ok(cookie_file.clone());
     }
 
+    Ok(
+      self
+        .litecoin_chain_dir()
+        .context("failed to get cookie file path")?
+        .join(".cookie"),
+    )
+  }
+
+  pub fn blocks_dir(&self) -> Result<PathBuf> {
+    Ok(
+      self
+        .litecoin_chain_dir()
+        .context("failed to get blocks directory path")?
+        .join("blocks"),
+    )
+  }
+
+  fn litecoin_chain_dir(&self) -> Result<PathBuf> {
     let path = if let Some(bitcoin_data_dir) = &self.litecoin_data_dir {
       bitcoin_data_dir.clone()
     } else if cfg!(target_os = "linux") {
       dirs::home_dir()
-        .ok_or_else(|| anyhow!("failed to get cookie file path: could not get home dir"))?
+        .ok_or_else(|| anyhow!("could not get home dir"))?
         .join(".litecoin")
     } else {
       dirs::data_dir()
-        .ok_or_else(|| anyhow!("failed to get cookie file path: could not get data dir"))?
+        .ok_or_else(|| anyhow!("could not get data dir"))?
         .join("Litecoin")
     };
 
-    let path = self.chain().join_with_data_dir(path);
-
-    Ok(path.join(".cookie"))
+    Ok(self.chain().join_with_data_dir(path))
   }
 
   pub fn credentials(&self) -> Option<(&str, &str)> {
@@ -525,14 +626,30 @@ impl Settings {
     self.index.as_ref().unwrap()
   }
 
+  pub fn index_address_history_raw(&self) -> bool {
+    self.index_address_history
+  }
+
   pub fn index_addresses_raw(&self) -> bool {
-    self.index_addresses
+    self.index_addresses || self.index_address_history || self.index_rune_history
+  }
+
+  pub fn index_inscription_details_raw(&self) -> bool {
+    self.index_inscription_details || self.index_search
+  }
+
+  pub fn index_inscription_history_raw(&self) -> bool {
+    self.index_inscription_history
   }
 
   pub fn index_inscriptions_raw(&self) -> bool {
     !self.no_index_inscriptions
   }
 
+  pub fn index_rune_history_raw(&self) -> bool {
+    self.index_rune_history
+  }
+
   pub fn index_runes_raw(&self) -> bool {
     self.index_runes
   }
@@ -545,6 +662,10 @@ impl Settings {
     self.index_sats
   }
 
+  pub fn index_search_raw(&self) -> bool {
+    self.index_search
+  }
+
   pub fn index_transactions_raw(&self) -> bool {
     self.index_transactions
   }
@@ -573,9 +694,42 @@ impl Settings {
     self.litecoin_rpc_limit.unwrap()
   }
 
+  pub fn max_savepoints(&self) -> u32 {
+    self.max_savepoints.unwrap()
+  }
+
+  pub fn read_block_files(&self) -> bool {
+    self.read_block_files
+  }
+
+  pub fn savepoint_interval(&self) -> u32 {
+    self.savepoint_interval.unwrap()
+  }
+
   pub fn server_url(&self) -> Option<&str> {
     self.server_url.as_deref()
   }
+
+  pub fn snapshot_reload(&self) -> bool {
+    self.snapshot_reload
+  }
+
+  pub fn undo_depth(&self) -> Option<u32> {
+    self.undo_depth
+  }
+
+  pub fn webhooks(&self) -> Result<Vec<Url>> {
+    self
+      .webhooks
+      .iter()
+      .flatten()
+      .map(|webhook| {
+        webhook
+          .parse()
+          .with_context(|| format!("invalid webhook URL `{webhook}`"))
+      })
+      .collect()
+  }
 }
 
 #[cfg(test)]
@@ -903,6 +1057,25 @@ mod tests {
     );
   }
 
+  #[test]
+  fn savepoint_settings_must_be_positive() {
+    assert_eq!(
+      Settings::from_options(Options::try_parse_from(["ord", "--max-savepoints=0"]).unwrap())
+        .or_defaults()
+        .unwrap_err()
+        .to_string(),
+      "`--max-savepoints` must be at least 1",
+    );
+
+    assert_eq!(
+      Settings::from_options(Options::try_parse_from(["ord", "--savepoint-interval=0"]).unwrap())
+        .or_defaults()
+        .unwrap_err()
+        .to_string(),
+      "`--savepoint-interval` must be at least 1",
+    );
+  }
+
   #[test]
   fn setting_commit_interval() {
     let arguments =
@@ -1018,15 +1191,26 @@ mod tests {
     ("HTTP_PORT", "8080"),
       ("INDEX", "index"),
       ("INDEX_CACHE_SIZE", "4"),
+      ("INDEX_ADDRESS_HISTORY", "1"),
       ("INDEX_ADDRESSES", "1"),
+      ("INDEX_INSCRIPTION_DETAILS", "1"),
+      ("INDEX_INSCRIPTION_HISTORY", "1"),
+      ("INDEX_RUNE_HISTORY", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
+      ("INDEX_SEARCH", "1"),
       ("INDEX_TRANSACTIONS", "1"),
       ("INTEGRATION_TEST", "1"),
+      ("MAX_SAVEPOINTS", "5"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
+      ("READ_BLOCK_FILES", "1"),
+      ("SAVEPOINT_INTERVAL", "6"),
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
+      ("SNAPSHOT_RELOAD", "1"),
+      ("UNDO_DEPTH", "7"),
+      ("WEBHOOKS", "http://localhost:9000 http://localhost:9001"),
     ]
     .into_iter()
     .map(|(key, value)| (key.into(), value.into()))
@@ -1061,16 +1245,30 @@ mod tests {
         ),
         http_port: Some(8080),
         index: Some("index".into()),
+        index_address_history: true,
         index_addresses: true,
         index_cache_size: Some(4),
+        index_inscription_details: true,
+        index_inscription_history: true,
+        index_rune_history: true,
         index_runes: true,
         index_sats: true,
+        index_search: true,
         index_transactions: true,
         integration_test: true,
+        max_savepoints: Some(5),
         no_index_inscriptions: true,
+        read_block_files: true,
+        savepoint_interval: Some(6),
         server_password: Some("server password".into()),
         server_url: Some("server url".into()),
         server_username: Some("server username".into()),
+        snapshot_reload: true,
+        undo_depth: Some(7),
+        webhooks: Some(vec![
+          "http://localhost:9000".into(),
+          "http://localhost:9001".into(),
+        ]),
       }
     );
   }
@@ -1093,16 +1291,26 @@ mod tests {
           "--cookie-file=cookie file",
           "--datadir=/data/dir",
           "--height-limit=3",
+          "--index-address-history",
           "--index-addresses",
           "--index-cache-size=4",
+          "--index-inscription-details",
+          "--index-inscription-history",
+          "--index-rune-history",
           "--index-runes",
           "--index-sats",
+          "--index-search",
           "--index-transactions",
           "--index=index",
           "--integration-test",
+          "--max-savepoints=5",
           "--no-index-inscriptions",
+          "--read-block-files",
+          "--savepoint-interval=6",
           "--server-password=server password",
           "--server-username=server username",
+          "--snapshot-reload",
+          "--undo-depth=7",
         ])
         .unwrap()
       ),
@@ -1122,16 +1330,27 @@ mod tests {
         hidden: None,
         http_port: None,
         index: Some("index".into()),
+        index_address_history: true,
         index_addresses: true,
         index_cache_size: Some(4),
+        index_inscription_details: true,
+        index_inscription_history: true,
+        index_rune_history: true,
         index_runes: true,
         index_sats: true,
+        index_search: true,
         index_transactions: true,
         integration_test: true,
+        max_savepoints: Some(5),
         no_index_inscriptions: true,
+        read_block_files: true,
+        savepoint_interval: Some(6),
         server_password: Some("server password".into()),
         server_url: None,
         server_username: Some("server username".into()),
+        snapshot_reload: true,
+        undo_depth: Some(7),
+        webhooks: None,
       }
     );
   }
@@ -5,12 +5,14 @@ pub mod decode;
 pub mod env;
 pub mod epochs;
 pub mod find;
+pub mod history;
 pub mod index;
 pub mod list;
 pub mod parse;
 pub mod runes;
 pub(crate) mod server;
 mod settings;
+pub mod simulate;
 pub mod subsidy;
 pub mod supply;
 pub mod teleburn;
@@ -30,6 +32,8 @@ pub(crate) enum Subcommand {
   Epochs,
   #[command(about = "Find a litoshi's current location")]
   Find(find::Find),
+  #[command(about = "Display an inscription's transfer history")]
+  History(history::History),
   #[command(subcommand, about = "Index commands")]
   Index(index::IndexSubcommand),
   #[command(about = "List the litoshi in an output")]
@@ -37,11 +41,13 @@ pub(crate) enum Subcommand {
   #[command(about = "Parse a litoshi from ordinal notation")]
   Parse(parse::Parse),
   #[command(about = "List all runes")]
-  Runes,
+  Runes(runes::Runes),
   #[command(about = "Run the explorer server")]
   Server(server::Server),
   #[command(about = "Display settings")]
   Settings,
+  #[command(about = "Show what a transaction would do to inscriptions and runes")]
+  Simulate(simulate::Simulate),
   #[command(about = "Display information about a block's subsidy")]
   Subsidy(subsidy::Subsidy),
   #[command(about = "Display Litecoin supply information")]
@@ -64,17 +70,19 @@ impl Subcommand {
       Self::Env(env) => env.run(),
       Self::Epochs => epochs::run(),
       Self::Find(find) => find.run(settings),
+      Self::History(history) => history.run(settings),
       Self::Index(index) => index.run(settings),
       Self::List(list) => list.run(settings),
       Self::Parse(parse) => parse.run(),
-      Self::Runes => runes::run(settings),
+      Self::Runes(runes) => runes.run(settings),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&settings)?);
+        let (index, event_receiver) = server.open_index(&settings)?;
         let handle = axum_server::Handle::new();
         LISTENERS.lock().unwrap().push(handle.clone());
-        server.run(settings, index, handle)
+        server.run(settings, index, handle, event_receiver)
       }
       Self::Settings => settings::run(settings),
+      Self::Simulate(simulate) => simulate.run(settings),
       Self::Subsidy(subsidy) => subsidy.run(),
       Self::Supply => supply::run(),
       Self::Teleburn(teleburn) => teleburn.run(),
@@ -0,0 +1,39 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct History {
+  #[arg(help = "Show transfers of <INSCRIPTION_ID>.")]
+  inscription_id: InscriptionId,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub id: InscriptionId,
+  pub number: i32,
+  pub transfers: Vec<api::InscriptionTransfer>,
+}
+
+impl History {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    if !index.has_inscription_history_index() {
+      bail!("history requires index created with `--index-inscription-history` flag");
+    }
+
+    index.update()?;
+
+    let entry = index
+      .get_inscription_entry(self.inscription_id)?
+      .ok_or_else(|| anyhow!("inscription {} not found", self.inscription_id))?;
+
+    let (transfers, _more) =
+      index.get_inscription_history_paginated(entry.sequence_number, usize::MAX, 0)?;
+
+    Ok(Some(Box::new(Output {
+      id: self.inscription_id,
+      number: entry.inscription_number,
+      transfers,
+    })))
+  }
+}
@@ -2,6 +2,9 @@ use super::*;
 
 mod export;
 pub mod info;
+pub mod restore;
+pub mod rollback;
+pub mod snapshot;
 mod update;
 
 #[derive(Debug, Parser)]
@@ -10,6 +13,12 @@ pub(crate) enum IndexSubcommand {
   Export(export::Export),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(about = "Restore the index from a snapshot")]
+  Restore(restore::Restore),
+  #[command(about = "Roll back the index to an earlier height")]
+  Rollback(rollback::Rollback),
+  #[command(about = "Write a snapshot of the index")]
+  Snapshot(snapshot::Snapshot),
   #[command(about = "Update the index", alias = "run")]
   Update,
 }
@@ -19,6 +28,9 @@ impl IndexSubcommand {
     match self {
       Self::Export(export) => export.run(settings),
       Self::Info(info) => info.run(settings),
+      Self::Restore(restore) => restore.run(settings),
+      Self::Rollback(rollback) => rollback.run(settings),
+      Self::Snapshot(snapshot) => snapshot.run(settings),
       Self::Update => update::run(settings),
     }
   }
@@ -1,11 +1,278 @@
-use super::*;
+use {
+  super::*,
+  crate::index::entry::{ContentEntry, InscriptionEntry},
+  clap::ValueEnum,
+  std::io::{BufWriter, Write},
+};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Export {
   #[arg(long, help = "Include addresses in export")]
   include_addresses: bool,
-  #[arg(long, help = "Write export to <TSV>")]
-  tsv: String,
+  #[arg(
+    long,
+    required_unless_present = "output_dir",
+    conflicts_with = "output_dir",
+    help = "Write export to <TSV>"
+  )]
+  tsv: Option<String>,
+  #[arg(long, help = "Write datasets to files in <OUTPUT_DIR>.")]
+  output_dir: Option<PathBuf>,
+  #[arg(
+    long,
+    value_enum,
+    default_value = "jsonl",
+    help = "Write datasets as <FORMAT>."
+  )]
+  format: Format,
+  #[arg(
+    long,
+    value_enum,
+    help = "Only export <DATASET>. May be given more than once. [default: all datasets]"
+  )]
+  dataset: Vec<Dataset>,
+  #[arg(
+    long,
+    default_value = "0",
+    help = "Export inscriptions, runes, rune balances and blocks from blocks starting at <START_HEIGHT>."
+  )]
+  start_height: u32,
+  #[arg(
+    long,
+    help = "Export inscriptions, runes, rune balances and blocks from blocks before <END_HEIGHT>. [default: index height + 1]"
+  )]
+  end_height: Option<u32>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+  Csv,
+  Jsonl,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Dataset {
+  Blocks,
+  Inscriptions,
+  RuneBalances,
+  Runes,
+}
+
+impl Dataset {
+  const ALL: [Self; 4] = [
+    Self::Blocks,
+    Self::Inscriptions,
+    Self::RuneBalances,
+    Self::Runes,
+  ];
+
+  fn name(self) -> &'static str {
+    match self {
+      Self::Blocks => "blocks",
+      Self::Inscriptions => "inscriptions",
+      Self::RuneBalances => "rune-balances",
+      Self::Runes => "runes",
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub start_height: u32,
+  pub end_height: u32,
+  pub files: Vec<File>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct File {
+  pub dataset: Dataset,
+  pub path: PathBuf,
+  pub rows: u64,
+}
+
+#[derive(Serialize)]
+struct BlockRow {
+  height: u32,
+  hash: BlockHash,
+  previous_blockhash: BlockHash,
+  timestamp: u32,
+  inscriptions: u32,
+  runes: u64,
+}
+
+#[derive(Serialize)]
+struct InscriptionRow {
+  number: i32,
+  id: InscriptionId,
+  sequence_number: u32,
+  height: u32,
+  timestamp: u32,
+  fee: u64,
+  sat: Option<Sat>,
+  satpoint: SatPoint,
+  address: Option<String>,
+  content_type: Option<String>,
+  content_length: Option<u64>,
+  charms: Vec<Charm>,
+  parents: Vec<InscriptionId>,
+}
+
+#[derive(Serialize)]
+struct RuneBalanceRow {
+  outpoint: OutPoint,
+  rune_id: RuneId,
+  amount: String,
+}
+
+#[derive(Serialize)]
+struct RuneRow {
+  id: RuneId,
+  rune: SpacedRune,
+  number: u64,
+  block: u64,
+  etching: Txid,
+  timestamp: u64,
+  divisibility: u8,
+  symbol: Option<char>,
+  premine: String,
+  mints: String,
+  burned: String,
+  cap: Option<String>,
+  amount: Option<String>,
+  height_start: Option<u64>,
+  height_end: Option<u64>,
+  offset_start: Option<u64>,
+  offset_end: Option<u64>,
+  turbo: bool,
+}
+
+/// The datasets written so far by an export, saved in the output directory
+/// after each dataset is written, so that an interrupted export can be
+/// resumed by running it again with the same format and height range.
+#[derive(Debug, Serialize, Deserialize)]
+struct Checkpoint {
+  format: Format,
+  start_height: u32,
+  end_height: u32,
+  files: Vec<File>,
+}
+
+impl Checkpoint {
+  const FILE_NAME: &'static str = "export-checkpoint.json";
+
+  fn load(path: &Path) -> Result<Option<Self>> {
+    match fs::read(path) {
+      Ok(json) => Ok(Some(serde_json::from_slice(&json).with_context(|| {
+        format!("failed to deserialize checkpoint `{}`", path.display())
+      })?)),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  fn save(&self, path: &Path) -> Result {
+    fs::write(path, serde_json::to_vec(self)?)?;
+    Ok(())
+  }
+}
+
+/// Writes rows as JSON lines, or as CSV with a header taken from the fields
+/// of the first row. Amounts which may not fit in a 64-bit integer are written
+/// as strings. Rows are written to a temporary file, which is renamed into
+/// place when the dataset is finished, so that incomplete datasets are never
+/// mistaken for complete ones.
+struct Writer {
+  format: Format,
+  header: bool,
+  partial: PathBuf,
+  path: PathBuf,
+  rows: u64,
+  writer: BufWriter<fs::File>,
+}
+
+impl Writer {
+  fn create(path: &Path, format: Format) -> Result<Self> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    Ok(Self {
+      format,
+      header: false,
+      path: path.into(),
+      rows: 0,
+      writer: BufWriter::new(
+        fs::File::create(&partial)
+          .with_context(|| format!("failed to create `{}`", partial.display()))?,
+      ),
+      partial,
+    })
+  }
+
+  fn write(&mut self, row: impl Serialize) -> Result {
+    match self.format {
+      Format::Jsonl => {
+        serde_json::to_writer(&mut self.writer, &row)?;
+        writeln!(self.writer)?;
+      }
+      Format::Csv => {
+        let serde_json::Value::Object(fields) = serde_json::to_value(row)? else {
+          bail!("export row is not an object");
+        };
+
+        if !self.header {
+          self.write_csv_record(fields.keys().cloned())?;
+          self.header = true;
+        }
+
+        self.write_csv_record(fields.into_iter().map(|(_, value)| Self::csv_cell(value)))?;
+      }
+    }
+
+    self.rows += 1;
+
+    Ok(())
+  }
+
+  fn csv_cell(value: serde_json::Value) -> String {
+    match value {
+      serde_json::Value::Null => String::new(),
+      serde_json::Value::String(string) => string,
+      serde_json::Value::Array(values) => values
+        .into_iter()
+        .map(Self::csv_cell)
+        .collect::<Vec<String>>()
+        .join(" "),
+      value => value.to_string(),
+    }
+  }
+
+  fn write_csv_record(&mut self, cells: impl Iterator<Item = String>) -> Result {
+    for (i, cell) in cells.enumerate() {
+      if i > 0 {
+        write!(self.writer, ",")?;
+      }
+
+      if cell.contains([',', '"', '\n', '\r']) {
+        write!(self.writer, "\"{}\"", cell.replace('"', "\"\""))?;
+      } else {
+        write!(self.writer, "{cell}")?;
+      }
+    }
+
+    writeln!(self.writer)?;
+
+    Ok(())
+  }
+
+  fn finish(mut self) -> Result<u64> {
+    self.writer.flush()?;
+    self.writer.get_ref().sync_all()?;
+    fs::rename(&self.partial, &self.path)?;
+    Ok(self.rows)
+  }
 }
 
 impl Export {
@@ -13,8 +280,200 @@ impl Export {
     let index = Index::open(&settings)?;
 
     index.update()?;
-    index.export(&self.tsv, self.include_addresses)?;
 
-    Ok(None)
+    let Some(output_dir) = &self.output_dir else {
+      index.export(self.tsv.as_ref().unwrap(), self.include_addresses)?;
+      return Ok(None);
+    };
+
+    let block_count = index.block_count()?;
+
+    let end_height = self.end_height.unwrap_or(block_count).min(block_count);
+
+    ensure!(
+      self.start_height <= end_height,
+      "--start-height {} is greater than --end-height {end_height}",
+      self.start_height,
+    );
+
+    let heights = self.start_height..end_height;
+
+    let datasets = if self.dataset.is_empty() {
+      Dataset::ALL.to_vec()
+    } else {
+      self.dataset.clone()
+    };
+
+    ensure!(
+      !datasets.contains(&Dataset::Inscriptions) || index.has_inscription_details_index(),
+      "exporting inscriptions requires index created with `--index-inscription-details` flag",
+    );
+
+    fs::create_dir_all(output_dir)?;
+
+    let checkpoint_path = output_dir.join(Checkpoint::FILE_NAME);
+
+    let mut checkpoint = Checkpoint::load(&checkpoint_path)?
+      .filter(|checkpoint| {
+        checkpoint.format == self.format
+          && checkpoint.start_height == heights.start
+          && checkpoint.end_height == heights.end
+      })
+      .unwrap_or(Checkpoint {
+        format: self.format,
+        start_height: heights.start,
+        end_height: heights.end,
+        files: Vec::new(),
+      });
+
+    let mut files = Vec::new();
+
+    for dataset in datasets {
+      if let Some(file) = checkpoint.files.iter().find(|file| file.dataset == dataset) {
+        log::info!(
+          "skipping {}, which has already been exported",
+          dataset.name()
+        );
+        files.push(file.clone());
+        continue;
+      }
+
+      let path = output_dir.join(format!(
+        "{}.{}",
+        dataset.name(),
+        match self.format {
+          Format::Csv => "csv",
+          Format::Jsonl => "jsonl",
+        }
+      ));
+
+      log::info!("exporting {} to {}", dataset.name(), path.display());
+
+      let mut writer = Writer::create(&path, self.format)?;
+
+      match dataset {
+        Dataset::Blocks => {
+          index.export_blocks(heights.clone(), |height, header, inscriptions, runes| {
+            writer.write(BlockRow {
+              height,
+              hash: header.block_hash(),
+              previous_blockhash: header.prev_blockhash,
+              timestamp: header.time,
+              inscriptions,
+              runes,
+            })
+          })?;
+        }
+        Dataset::Inscriptions => {
+          index.export_inscriptions(
+            heights.clone(),
+            self.include_addresses,
+            |entry, parents, satpoint, content, script_pubkey| {
+              writer.write(Self::inscription_row(
+                &settings,
+                entry,
+                parents,
+                satpoint,
+                content,
+                script_pubkey,
+              ))
+            },
+          )?;
+        }
+        Dataset::RuneBalances => {
+          index.export_rune_balances(heights.clone(), |outpoint, balances| {
+            for (rune_id, amount) in balances {
+              writer.write(RuneBalanceRow {
+                outpoint,
+                rune_id,
+                amount: amount.to_string(),
+              })?;
+            }
+            Ok(())
+          })?;
+        }
+        Dataset::Runes => {
+          index.export_runes(heights.clone(), |id, entry| {
+            let terms = entry.terms.unwrap_or_default();
+            writer.write(RuneRow {
+              id,
+              rune: entry.spaced_rune,
+              number: entry.number,
+              block: entry.block,
+              etching: entry.etching,
+              timestamp: entry.timestamp,
+              divisibility: entry.divisibility,
+              symbol: entry.symbol,
+              premine: entry.premine.to_string(),
+              mints: entry.mints.to_string(),
+              burned: entry.burned.to_string(),
+              cap: terms.cap.map(|cap| cap.to_string()),
+              amount: terms.amount.map(|amount| amount.to_string()),
+              height_start: terms.height.0,
+              height_end: terms.height.1,
+              offset_start: terms.offset.0,
+              offset_end: terms.offset.1,
+              turbo: entry.turbo,
+            })
+          })?;
+        }
+      }
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        bail!("export interrupted, run the same command again to resume it");
+      }
+
+      let file = File {
+        dataset,
+        path,
+        rows: writer.finish()?,
+      };
+
+      checkpoint.files.push(file.clone());
+      checkpoint.save(&checkpoint_path)?;
+
+      files.push(file);
+    }
+
+    fs::remove_file(&checkpoint_path)?;
+
+    Ok(Some(Box::new(Output {
+      start_height: heights.start,
+      end_height: heights.end,
+      files,
+    })))
+  }
+
+  fn inscription_row(
+    settings: &Settings,
+    entry: InscriptionEntry,
+    parents: Vec<InscriptionId>,
+    satpoint: SatPoint,
+    content: Option<ContentEntry>,
+    script_pubkey: Option<ScriptBuf>,
+  ) -> InscriptionRow {
+    let content = content.unwrap_or_default();
+
+    InscriptionRow {
+      number: entry.inscription_number,
+      id: entry.id,
+      sequence_number: entry.sequence_number,
+      height: entry.height,
+      timestamp: entry.timestamp,
+      fee: entry.fee,
+      sat: entry.sat,
+      satpoint,
+      address: script_pubkey.and_then(|script_pubkey| {
+        settings
+          .chain()
+          .address_from_script(&script_pubkey)
+          .ok()
+          .map(|address| address.to_string())
+      }),
+      content_type: content.content_type,
+      content_length: content.content_length,
+      charms: Charm::charms(entry.charms),
+      parents,
+    }
   }
 }
@@ -0,0 +1,67 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Restore {
+  #[arg(help = "Restore index from snapshot at <PATH>.")]
+  path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub height: u32,
+  pub hash: BlockHash,
+}
+
+impl Restore {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index_path = settings.index().to_owned();
+
+    ensure!(
+      !index_path.exists(),
+      "index already exists at `{}`, delete it before restoring a snapshot",
+      index_path.display(),
+    );
+
+    if let Some(parent) = index_path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    let partial = index_path.with_extension("partial");
+
+    fs::copy(&self.path, &partial)
+      .with_context(|| format!("failed to copy snapshot `{}`", self.path.display()))?;
+
+    fs::rename(&partial, &index_path)?;
+
+    match Self::verify(&settings) {
+      Ok(output) => Ok(Some(Box::new(output))),
+      Err(err) => {
+        fs::remove_file(&index_path)?;
+        Err(err)
+      }
+    }
+  }
+
+  /// Check that the last block in the restored index is in the node's chain,
+  /// and so, since each block commits to the one before it, every block is.
+  fn verify(settings: &Settings) -> Result<Output> {
+    let index = Index::open(settings)?;
+
+    let height = index
+      .block_count()?
+      .checked_sub(1)
+      .ok_or_else(|| anyhow!("snapshot contains no blocks"))?;
+
+    let hash = index.block_hash(Some(height))?.unwrap();
+
+    match settings
+      .bitcoin_rpc_client(None)?
+      .get_block_hash(height.into())
+      .into_option()?
+    {
+      Some(node_hash) if node_hash == hash => Ok(Output { height, hash }),
+      Some(_) => bail!("snapshot block {hash} at height {height} is not in the node's chain"),
+      None => bail!("node has not yet synced to snapshot height {height}"),
+    }
+  }
+}
@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Rollback {
+  #[arg(
+    long,
+    help = "Roll back index so that block <HEIGHT> is the last block indexed."
+  )]
+  height: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub height: u32,
+  pub hash: BlockHash,
+}
+
+impl Rollback {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.roll_back(self.height)?;
+
+    Ok(Some(Box::new(Output {
+      height: self.height,
+      hash: index.block_hash(Some(self.height))?.unwrap(),
+    })))
+  }
+}
@@ -0,0 +1,28 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Snapshot {
+  #[arg(help = "Write snapshot of index to <PATH>.")]
+  path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub path: PathBuf,
+  pub block_count: u32,
+}
+
+impl Snapshot {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.update()?;
+
+    let block_count = index.snapshot(&self.path)?;
+
+    Ok(Some(Box::new(Output {
+      path: self.path,
+      block_count,
+    })))
+  }
+}
@@ -1,5 +1,28 @@
 use super::*;
 
+mod snapshot;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Runes {
+  #[command(subcommand)]
+  subcommand: Option<RunesSubcommand>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) enum RunesSubcommand {
+  #[command(about = "List the holders of a rune at a block height")]
+  Snapshot(snapshot::Snapshot),
+}
+
+impl Runes {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    match self.subcommand {
+      Some(RunesSubcommand::Snapshot(snapshot)) => snapshot.run(settings),
+      None => run(settings),
+    }
+  }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
   pub runes: BTreeMap<Rune, RuneInfo>,
@@ -24,7 +47,7 @@ pub struct RuneInfo {
   pub tx: u32,
 }
 
-pub(crate) fn run(settings: Settings) -> SubcommandResult {
+fn run(settings: Settings) -> SubcommandResult {
   let index = Index::open(&settings)?;
 
   ensure!(
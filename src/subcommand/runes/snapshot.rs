@@ -0,0 +1,48 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Snapshot {
+  #[arg(help = "List holders of <RUNE>.")]
+  rune: SpacedRune,
+  #[arg(
+    long,
+    help = "List holders at the end of block <HEIGHT>. [default: index height]"
+  )]
+  height: Option<u32>,
+}
+
+impl Snapshot {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    ensure!(
+      index.has_rune_index(),
+      "`ord runes snapshot` requires index created with `--index-runes` flag",
+    );
+
+    index.update()?;
+
+    let block_height = index.block_height()?.unwrap_or(Height(0)).n();
+
+    let height = match self.height {
+      Some(height) if height > block_height => {
+        bail!("height {height} is greater than index height {block_height}")
+      }
+      Some(height) if height < block_height => {
+        ensure!(
+          index.has_rune_history_index(),
+          "`ord runes snapshot --height` requires index created with `--index-rune-history` flag",
+        );
+
+        Some(height)
+      }
+      _ => None,
+    };
+
+    let holders = index
+      .get_rune_holders(self.rune.rune, height, usize::MAX, 0)?
+      .ok_or_else(|| anyhow!("rune {} not found", self.rune))?;
+
+    Ok(Some(Box::new(holders)))
+  }
+}
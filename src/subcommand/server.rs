@@ -2,21 +2,33 @@ use {
   self::{
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
+    byte_range::ByteRange,
     error::{OptionExt, ServerError, ServerResult},
+    event_filter::EventFilter,
+    webhook::Webhook,
   },
   super::*,
+  crate::index::{
+    event::Event,
+    listing::{InscriptionFilter, InscriptionSort, SortOrder},
+  },
   crate::templates::{
-    AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml,
-    InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent,
-    PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
-    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
-    PreviewVideoHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, TransactionHtml,
+    AddressHistoryHtml, AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg,
+    CollectionHoldersHtml, CollectionHtml, CollectionsHtml, HomeHtml, InputHtml,
+    InscriptionHistoryHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml,
+    PageContent, PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml,
+    PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml,
+    PreviewUnknownHtml, PreviewVideoHtml, RareTxt, RuneHistoryHtml, RuneHoldersHtml, RuneHtml,
+    RunesHtml, SatHtml, SearchHtml, TransactionHtml,
   },
   axum::{
     body,
-    extract::{DefaultBodyLimit, Extension, Json, Path, Query},
+    extract::{DefaultBodyLimit, Extension, Json, Path, Query, RawQuery},
     http::{header, HeaderValue, StatusCode, Uri},
-    response::{IntoResponse, Redirect, Response},
+    response::{
+      sse::{Event as SseEvent, KeepAlive, Sse},
+      IntoResponse, Redirect, Response,
+    },
     routing::{get, post},
     Router,
   },
@@ -30,7 +42,8 @@ use {
     AcmeConfig,
   },
   std::{str, sync::Arc},
-  tokio_stream::StreamExt,
+  tokio::sync::{broadcast, mpsc},
+  tokio_stream::{Stream, StreamExt},
   tower_http::{
     compression::CompressionLayer,
     cors::{Any, CorsLayer},
@@ -43,9 +56,15 @@ pub(crate) use server_config::ServerConfig;
 
 mod accept_encoding;
 mod accept_json;
+mod byte_range;
 mod error;
+mod event_filter;
+mod graphql;
 pub mod query;
 mod server_config;
+mod webhook;
+
+const EVENT_STREAM_CAPACITY: usize = 1024;
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -53,11 +72,48 @@ enum SpawnConfig {
   Redirect(String),
 }
 
+#[derive(Deserialize)]
+struct InscriptionsQuery {
+  charm: Option<Charm>,
+  content_type: Option<String>,
+  cursed: Option<bool>,
+  cursor: Option<u32>,
+  max_height: Option<u32>,
+  media: Option<String>,
+  metaprotocol: Option<String>,
+  min_height: Option<u32>,
+  order: Option<SortOrder>,
+  parent: Option<InscriptionId>,
+  rarity: Option<Rarity>,
+  sort: Option<InscriptionSort>,
+}
+
+#[derive(Deserialize)]
+struct RuneHoldersQuery {
+  height: Option<u32>,
+}
+
 #[derive(Deserialize)]
 struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct SearchQuery {
+  charm: Option<Charm>,
+  content_type: Option<String>,
+  cursed: Option<bool>,
+  cursor: Option<u32>,
+  max_height: Option<u32>,
+  media: Option<String>,
+  metaprotocol: Option<String>,
+  min_height: Option<u32>,
+  parent: Option<InscriptionId>,
+  q: Option<String>,
+  query: Option<String>,
+  rarity: Option<Rarity>,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -86,6 +142,11 @@ pub struct Server {
   pub(crate) decompress: bool,
   #[arg(long, help = "Disable JSON API.")]
   pub(crate) disable_json_api: bool,
+  #[arg(
+    long,
+    help = "Stream inscription and rune events to clients of `/events` as they are indexed."
+  )]
+  pub(crate) events: bool,
   #[arg(
     long,
     help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]"
@@ -120,13 +181,112 @@ pub struct Server {
     help = "Poll Bitcoin Core every <POLLING_INTERVAL>."
   )]
   pub(crate) polling_interval: humantime::Duration,
+  #[arg(
+    long,
+    help = "Write a snapshot of the index to <SNAPSHOT> every <SNAPSHOT_INTERVAL> blocks, which can be restored with `ord index restore`."
+  )]
+  pub(crate) snapshot: Option<PathBuf>,
+  #[arg(
+    long,
+    default_value = "1000",
+    value_parser = clap::value_parser!(u32).range(1..),
+    help = "Write snapshots every <SNAPSHOT_INTERVAL> blocks."
+  )]
+  pub(crate) snapshot_interval: u32,
 }
 
 impl Server {
-  pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+  /// Write a snapshot if none has been written yet, or if the index has
+  /// crossed a multiple of `interval` blocks since the last one.
+  fn write_snapshot(
+    index: &Index,
+    path: &Path,
+    interval: u32,
+    snapshot_block_count: &mut Option<u32>,
+  ) -> Result {
+    let block_count = index.block_count()?;
+
+    if snapshot_block_count.is_some_and(|last| block_count / interval <= last / interval) {
+      return Ok(());
+    }
+
+    *snapshot_block_count = Some(index.snapshot(path)?);
+
+    Ok(())
+  }
+
+  pub fn open_index(
+    &self,
+    settings: &Settings,
+  ) -> Result<(Arc<Index>, Option<mpsc::Receiver<Event>>)> {
+    if self.events {
+      let (sender, receiver) = mpsc::channel(EVENT_STREAM_CAPACITY);
+      let index = Index::open_with_event_sender(settings, Some(sender))?;
+      Ok((Arc::new(index), Some(receiver)))
+    } else {
+      Ok((Arc::new(Index::open(settings)?), None))
+    }
+  }
+
+  pub fn run(
+    self,
+    settings: Settings,
+    index: Arc<Index>,
+    handle: Handle,
+    event_receiver: Option<mpsc::Receiver<Event>>,
+  ) -> SubcommandResult {
     Runtime::new()?.block_on(async {
+      let events = event_receiver.map(|mut receiver| {
+        let (sender, _) = broadcast::channel(EVENT_STREAM_CAPACITY);
+
+        let forwarder = sender.clone();
+        tokio::spawn(async move {
+          while let Some(event) = receiver.recv().await {
+            forwarder.send(event).ok();
+          }
+        });
+
+        sender
+      });
+
+      let webhooks = settings.webhooks()?;
+
+      // unregister webhooks which are no longer configured, so that events
+      // stop being logged for them
+      index.register_webhooks(&webhooks)?;
+
+      for url in webhooks {
+        Webhook::spawn(url, self.polling_interval.into(), index.clone());
+      }
+
       let index_clone = index.clone();
-      let integration_test = settings.integration_test();
+
+      let polling_interval = if settings.integration_test() {
+        Duration::from_millis(100)
+      } else {
+        self.polling_interval.into()
+      };
+
+      // snapshots are written on their own thread, so that writing one
+      // doesn't hold up index updates
+      if let Some(snapshot) = self.snapshot.clone() {
+        let index = index.clone();
+        let interval = self.snapshot_interval;
+
+        thread::spawn(move || {
+          let mut snapshot_block_count = None;
+
+          while !SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            thread::sleep(polling_interval);
+
+            if let Err(error) =
+              Self::write_snapshot(&index, &snapshot, interval, &mut snapshot_block_count)
+            {
+              log::warn!("Writing snapshot: {error}");
+            }
+          }
+        });
+      }
 
       let index_thread = thread::spawn(move || loop {
         if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
@@ -139,11 +299,7 @@ impl Server {
           }
         }
 
-        thread::sleep(if integration_test {
-          Duration::from_millis(100)
-        } else {
-          self.polling_interval.into()
-        });
+        thread::sleep(polling_interval);
       });
 
       INDEXER.lock().unwrap().replace(index_thread);
@@ -156,6 +312,7 @@ impl Server {
         csp_origin: self.csp_origin.clone(),
         decompress: self.decompress,
         domain: acme_domains.first().cloned(),
+        events,
         index_sats: index.has_sat_index(),
         json_api_enabled: !self.disable_json_api,
         proxy: self.proxy.clone(),
@@ -164,6 +321,11 @@ impl Server {
       let router = Router::new()
         .route("/", get(Self::home))
         .route("/address/:address", get(Self::address))
+        .route("/address/:address/history", get(Self::address_history))
+        .route(
+          "/address/:address/history/:page",
+          get(Self::address_history_paginated),
+        )
         .route("/block/:query", get(Self::block))
         .route("/blockcount", get(Self::block_count))
         .route("/blockhash", get(Self::block_hash))
@@ -178,9 +340,19 @@ impl Server {
           get(Self::children_paginated),
         )
         .route("/clock", get(Self::clock))
+        .route("/collection/:inscription_id", get(Self::collection))
+        .route(
+          "/collection/:inscription_id/holders",
+          get(Self::collection_holders),
+        )
+        .route(
+          "/collection/:inscription_id/holders/:page",
+          get(Self::collection_holders_paginated),
+        )
         .route("/collections", get(Self::collections))
         .route("/collections/:page", get(Self::collections_paginated))
         .route("/content/:inscription_id", get(Self::content))
+        .route("/events", get(Self::events))
         .route("/faq", get(Self::faq))
         .route("/favicon.ico", get(Self::favicon))
         .route("/feed.xml", get(Self::feed))
@@ -190,6 +362,14 @@ impl Server {
           "/inscription/:inscription_query/:child",
           get(Self::inscription_child),
         )
+        .route(
+          "/inscription/:inscription_query/history",
+          get(Self::inscription_history),
+        )
+        .route(
+          "/inscription/:inscription_query/history/:page",
+          get(Self::inscription_history_paginated),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route("/inscriptions", post(Self::inscriptions_json))
         .route("/inscriptions/:page", get(Self::inscriptions_paginated))
@@ -236,6 +416,18 @@ impl Server {
           "/r/children/:inscription_id/inscriptions/:page",
           get(Self::child_inscriptions_recursive_paginated),
         )
+        .route(
+          "/r/collection/:inscription_id",
+          get(Self::collection_recursive),
+        )
+        .route(
+          "/r/collection/:inscription_id/holders",
+          get(Self::collection_holders_recursive),
+        )
+        .route(
+          "/r/collection/:inscription_id/holders/:page",
+          get(Self::collection_holders_recursive_paginated),
+        )
         .route("/r/metadata/:inscription_id", get(Self::metadata))
         .route("/r/parents/:inscription_id", get(Self::parents_recursive))
         .route(
@@ -253,18 +445,35 @@ impl Server {
         )
         .route("/rare.txt", get(Self::rare_txt))
         .route("/rune/:rune", get(Self::rune))
+        .route("/rune/:rune/history", get(Self::rune_history))
+        .route("/rune/:rune/holders", get(Self::rune_holders))
+        .route(
+          "/rune/:rune/holders/:page",
+          get(Self::rune_holders_paginated),
+        )
         .route("/runes", get(Self::runes))
         .route("/runes/:page", get(Self::runes_paginated))
         .route("/runes/balances", get(Self::runes_balances))
         .route("/sat/:sat", get(Self::sat))
         .route("/satpoint/:satpoint", get(Self::satpoint))
         .route("/search", get(Self::search_by_query))
+        .route("/simulate", post(Self::simulate))
         .route("/search/*query", get(Self::search_by_path))
         .route("/static/*path", get(Self::static_asset))
         .route("/status", get(Self::status))
         .route("/tx/:txid", get(Self::transaction))
         .route("/decode/:txid", get(Self::decode))
-        .route("/update", get(Self::update))
+        .route("/update", get(Self::update));
+
+      let router = if server_config.json_api_enabled {
+        router
+          .route("/graphql", post(Self::graphql))
+          .layer(Extension(graphql::schema(index.clone(), settings.chain())))
+      } else {
+        router
+      };
+
+      let router = router
         .fallback(Self::fallback)
         .layer(Extension(index))
         .layer(Extension(server_config.clone()))
@@ -667,6 +876,18 @@ impl Server {
     task::block_in_place(|| Ok(RareTxt(index.rare_sat_satpoints()?)))
   }
 
+  fn rune_from_query(index: &Index, rune_query: query::Rune) -> ServerResult<Rune> {
+    Ok(match rune_query {
+      query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+      query::Rune::Id(rune_id) => index
+        .get_rune_by_id(rune_id)?
+        .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      query::Rune::Number(number) => index
+        .get_rune_by_number(usize::try_from(number).unwrap())?
+        .ok_or_not_found(|| format!("rune number {number}"))?,
+    })
+  }
+
   async fn rune(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -680,15 +901,7 @@ impl Server {
         ));
       }
 
-      let rune = match rune_query {
-        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
-        query::Rune::Id(rune_id) => index
-          .get_rune_by_id(rune_id)?
-          .ok_or_not_found(|| format!("rune {rune_id}"))?,
-        query::Rune::Number(number) => index
-          .get_rune_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("rune number {number}"))?,
-      };
+      let rune = Self::rune_from_query(&index, rune_query)?;
 
       let (id, entry, parent) = index
         .rune(rune)?
@@ -719,6 +932,107 @@ impl Server {
     })
   }
 
+  async fn rune_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune history index".to_string(),
+        ));
+      }
+
+      let rune = Self::rune_from_query(&index, rune_query)?;
+
+      let history = index
+        .get_rune_history(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      Ok(if accept_json {
+        Json(history).into_response()
+      } else {
+        RuneHistoryHtml { history }
+          .page(server_config)
+          .into_response()
+      })
+    })
+  }
+
+  async fn rune_holders(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(rune_query): Path<DeserializeFromStr<query::Rune>>,
+    query: Query<RuneHoldersQuery>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::rune_holders_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((rune_query, 0)),
+      query,
+      accept_json,
+    )
+    .await
+  }
+
+  async fn rune_holders_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(rune_query), page)): Path<(DeserializeFromStr<query::Rune>, usize)>,
+    Query(query): Query<RuneHoldersQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let rune = Self::rune_from_query(&index, rune_query)?;
+
+      let block_height = index.block_height()?.unwrap_or(Height(0)).n();
+
+      let height = match query.height {
+        Some(height) if height > block_height => {
+          return Err(ServerError::BadRequest(format!(
+            "height {height} is greater than index height {block_height}"
+          )));
+        }
+        Some(height) if height < block_height => {
+          if !index.has_rune_history_index() {
+            return Err(ServerError::NotFound(
+              "this server has no rune history index".to_string(),
+            ));
+          }
+
+          Some(height)
+        }
+        _ => None,
+      };
+
+      let holders = index
+        .get_rune_holders(rune, height, 100, page)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      Ok(if accept_json {
+        Json(holders).into_response()
+      } else {
+        RuneHoldersHtml {
+          next_page: holders.more.then_some(page + 1),
+          prev_page: page.checked_sub(1),
+          query_height: query.height,
+          holders,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn runes(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -887,6 +1201,60 @@ impl Server {
     })
   }
 
+  async fn address_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::address_history_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((address, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn address_history_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((address, page)): Path<(Address<NetworkUnchecked>, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_address_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no address history index".to_string(),
+        ));
+      }
+
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let (activity, more) = index.get_address_history_paginated(&address, 100, page)?;
+
+      Ok(if accept_json {
+        Json(api::AddressHistory {
+          activity,
+          more,
+          page,
+        })
+        .into_response()
+      } else {
+        AddressHistoryHtml {
+          address,
+          activity,
+          prev_page: page.checked_sub(1),
+          next_page: more.then_some(page + 1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn block(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1004,6 +1372,34 @@ impl Server {
     })
   }
 
+  async fn simulate(
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    transaction: String,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let (transaction, prevouts) = simulate::decode(&transaction)
+        .map_err(|err| ServerError::BadRequest(format!("invalid transaction: {err}")))?;
+
+      let simulation = index
+        .simulate(&transaction, &prevouts)
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      Ok(Json(simulation).into_response())
+    })
+  }
+
+  async fn graphql(
+    Extension(schema): Extension<graphql::Schema>,
+    Json(request): Json<async_graphql::Request>,
+  ) -> ServerResult {
+    Ok(Json(schema.execute(request).await).into_response())
+  }
+
   async fn update(
     Extension(settings): Extension<Arc<Settings>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1018,6 +1414,42 @@ impl Server {
     })
   }
 
+  async fn events(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Query(filter): Query<EventFilter>,
+  ) -> ServerResult<Sse<impl Stream<Item = Result<SseEvent, axum::Error>>>> {
+    let receiver = server_config
+      .events
+      .as_ref()
+      .ok_or_not_found(|| "event stream")?
+      .subscribe();
+
+    let events = futures::stream::unfold(receiver, |mut receiver| async move {
+      loop {
+        match receiver.recv().await {
+          Ok(event) => return Some((event, receiver)),
+          Err(broadcast::error::RecvError::Lagged(skipped)) => {
+            log::warn!("Event stream client lagged, dropped {skipped} events");
+          }
+          Err(broadcast::error::RecvError::Closed) => return None,
+        }
+      }
+    })
+    .filter_map(
+      move |event| match task::block_in_place(|| filter.matches(&index, &event)) {
+        Ok(true) => Some(SseEvent::default().event(event.kind()).json_data(&event)),
+        Ok(false) => None,
+        Err(err) => {
+          log::error!("Failed to filter event: {err}");
+          None
+        }
+      },
+    );
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+  }
+
   async fn metadata(
     Extension(index): Extension<Arc<Index>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
@@ -1132,10 +1564,77 @@ impl Server {
   }
 
   async fn search_by_query(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Query(search): Query<Search>,
-  ) -> ServerResult<Redirect> {
-    Self::search(index, search.query).await
+    Query(search): Query<SearchQuery>,
+    RawQuery(raw_query): RawQuery,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    if let Some(query) = search.query {
+      return Ok(Self::search(index, query).await?.into_response());
+    }
+
+    let Some(query) = search.q else {
+      return Err(ServerError::BadRequest(
+        "missing `q` or `query` parameter".into(),
+      ));
+    };
+
+    task::block_in_place(|| {
+      if !index.has_search_index() {
+        return Err(ServerError::NotFound(
+          "this server has no search index".into(),
+        ));
+      }
+
+      let filter = InscriptionFilter {
+        charm: search.charm,
+        content_type: search.content_type,
+        cursed: search.cursed,
+        max_height: search.max_height,
+        media: search.media,
+        metaprotocol: search.metaprotocol,
+        min_height: search.min_height,
+        parent: search.parent,
+        rarity: search.rarity,
+      };
+
+      Self::check_inscription_filter(&index, &filter)?;
+
+      let (inscriptions, next_cursor) =
+        index.search_inscriptions(&query, &filter, search.cursor, 100)?;
+
+      let runes = if search.cursor.is_none() && filter.is_empty() {
+        index.search_runes(&query, 100)?
+      } else {
+        Vec::new()
+      };
+
+      let search = api::Search {
+        cursor: search.cursor,
+        inscriptions,
+        more: next_cursor.is_some(),
+        next_cursor,
+        query,
+        runes,
+      };
+
+      Ok(if accept_json {
+        Json(search).into_response()
+      } else {
+        SearchHtml {
+          parameters: raw_query
+            .unwrap_or_default()
+            .split('&')
+            .filter(|parameter| !parameter.is_empty() && !parameter.starts_with("cursor="))
+            .collect::<Vec<&str>>()
+            .join("&"),
+          search,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
   }
 
   async fn search_by_path(
@@ -1462,6 +1961,7 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
+    request_headers: HeaderMap,
   ) -> ServerResult {
     task::block_in_place(|| {
       if settings.is_hidden(inscription_id) {
@@ -1479,21 +1979,132 @@ impl Server {
         };
       };
 
+      let mut content_id = inscription_id;
+
       if let Some(delegate) = inscription.delegate() {
         inscription = index
           .get_inscription_by_id(delegate)?
-          .ok_or_not_found(|| format!("delegate {inscription_id}"))?
+          .ok_or_not_found(|| format!("delegate {inscription_id}"))?;
+        content_id = delegate;
       }
 
-      Ok(
-        Self::content_response(inscription, accept_encoding, &server_config)?
-          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-          .into_response(),
-      )
-    })
-  }
+      let content_encoding = inscription
+        .content_encoding()
+        .filter(|content_encoding| accept_encoding.is_acceptable(content_encoding));
 
-  fn content_response(
+      let etag = Self::content_etag(content_id, content_encoding.as_ref())?;
+
+      // the body served depends on `Accept-Encoding` whenever content is
+      // encoded, or whenever it may be compressed by the compression layer
+      let vary = (header::VARY, HeaderValue::from_static("accept-encoding"));
+
+      if request_headers
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|if_none_match| Self::etag_matches(if_none_match, &etag))
+      {
+        return Ok(
+          (
+            StatusCode::NOT_MODIFIED,
+            [
+              (header::ETAG, etag),
+              (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("public, max-age=1209600, immutable"),
+              ),
+              vary,
+            ],
+          )
+            .into_response(),
+        );
+      }
+
+      let (mut headers, body) =
+        Self::content_response(inscription, accept_encoding, &server_config)?
+          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+      headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+      headers.insert(header::ETAG, etag.clone());
+      headers.insert(vary.0.clone(), vary.1.clone());
+
+      let range = request_headers
+        .get(header::RANGE)
+        .filter(|_| {
+          request_headers
+            .get(header::IF_RANGE)
+            .map_or(true, |if_range| *if_range == etag)
+        })
+        .and_then(|range| ByteRange::parse(range, body.len()));
+
+      Ok(match range {
+        None => (headers, body).into_response(),
+        Some(ByteRange::Satisfiable(range)) => {
+          headers.insert(
+            header::CONTENT_RANGE,
+            HeaderValue::from_str(&format!(
+              "bytes {}-{}/{}",
+              range.start,
+              range.end - 1,
+              body.len()
+            ))
+            .map_err(|err| ServerError::Internal(Error::from(err)))?,
+          );
+
+          (StatusCode::PARTIAL_CONTENT, headers, body[range].to_vec()).into_response()
+        }
+        Some(ByteRange::Unsatisfiable) => (
+          StatusCode::RANGE_NOT_SATISFIABLE,
+          [
+            (header::ACCEPT_RANGES, HeaderValue::from_static("bytes")),
+            (
+              header::CONTENT_RANGE,
+              HeaderValue::from_str(&format!("bytes */{}", body.len()))
+                .map_err(|err| ServerError::Internal(Error::from(err)))?,
+            ),
+            (header::ETAG, etag),
+            vary,
+          ],
+        )
+          .into_response(),
+      })
+    })
+  }
+
+  /// Content served with a content encoding is a different representation
+  /// than the same content decompressed, so it gets a different entity tag,
+  /// suffixed with the encoding, for example `"<INSCRIPTION_ID>-br"`.
+  fn content_etag(
+    content_id: InscriptionId,
+    content_encoding: Option<&HeaderValue>,
+  ) -> ServerResult<HeaderValue> {
+    let etag = match content_encoding {
+      None => format!("\"{content_id}\""),
+      Some(content_encoding) => match content_encoding.to_str() {
+        Ok(encoding) if encoding.chars().all(|c| c.is_ascii_alphanumeric()) => {
+          format!("\"{content_id}-{encoding}\"")
+        }
+        _ => format!(
+          "\"{content_id}-{}\"",
+          hex::encode(content_encoding.as_bytes())
+        ),
+      },
+    };
+
+    HeaderValue::from_str(&etag).map_err(|err| ServerError::Internal(Error::from(err)))
+  }
+
+  /// `If-None-Match` uses weak comparison, so `W/` prefixes are ignored.
+  fn etag_matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let Ok(if_none_match) = if_none_match.to_str() else {
+      return false;
+    };
+
+    if_none_match
+      .split(',')
+      .map(str::trim)
+      .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag).as_bytes() == etag.as_bytes())
+  }
+
+  fn content_response(
     inscription: Inscription,
     accept_encoding: AcceptEncoding,
     server_config: &ServerConfig,
@@ -1667,6 +2278,63 @@ impl Server {
     Self::inscription_inner(server_config, &index, accept_json, query, Some(child)).await
   }
 
+  async fn inscription_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::inscription_history_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((inscription_id, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn inscription_history_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((inscription_id, page)): Path<(InscriptionId, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_inscription_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no inscription history index".to_string(),
+        ));
+      }
+
+      let entry = index
+        .get_inscription_entry(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+      let (transfers, more) =
+        index.get_inscription_history_paginated(entry.sequence_number, 100, page)?;
+
+      Ok(if accept_json {
+        Json(api::InscriptionHistory {
+          id: inscription_id,
+          more,
+          page,
+          transfers,
+        })
+        .into_response()
+      } else {
+        InscriptionHistoryHtml {
+          id: inscription_id,
+          number: entry.inscription_number,
+          transfers,
+          prev_page: page.checked_sub(1),
+          next_page: more.then_some(page + 1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn inscription_inner(
     server_config: Arc<ServerConfig>,
     index: &Index,
@@ -1739,6 +2407,128 @@ impl Server {
     })
   }
 
+  async fn collection(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let collection = index
+        .get_collection(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+      Ok(if accept_json {
+        Json(collection).into_response()
+      } else {
+        CollectionHtml { collection }
+          .page(server_config)
+          .into_response()
+      })
+    })
+  }
+
+  async fn collection_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      Ok(
+        Json(
+          index
+            .get_collection(inscription_id)?
+            .ok_or_not_found(|| format!("inscription {inscription_id}"))?,
+        )
+        .into_response(),
+      )
+    })
+  }
+
+  async fn collection_holders(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::collection_holders_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((inscription_id, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn collection_holders_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((parent, page)): Path<(InscriptionId, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (number, holders) = Self::collection_holders_page(&index, parent, page)?;
+
+      Ok(if accept_json {
+        Json(holders).into_response()
+      } else {
+        CollectionHoldersHtml {
+          next_page: holders.more.then_some(page + 1),
+          number,
+          prev_page: page.checked_sub(1),
+          holders,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn collection_holders_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult {
+    Self::collection_holders_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
+  }
+
+  async fn collection_holders_recursive_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((parent, page)): Path<(InscriptionId, usize)>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (_number, holders) = Self::collection_holders_page(&index, parent, page)?;
+
+      Ok(Json(holders).into_response())
+    })
+  }
+
+  fn collection_holders_page(
+    index: &Index,
+    parent: InscriptionId,
+    page: usize,
+  ) -> ServerResult<(i32, api::CollectionHolders)> {
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let entry = index
+      .get_inscription_entry(parent)?
+      .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+    let (holders, more) = index.get_collection_holders(entry.sequence_number, 100, page)?;
+
+    Ok((
+      entry.inscription_number,
+      api::CollectionHolders {
+        holders,
+        id: parent,
+        more,
+        page,
+      },
+    ))
+  }
+
   async fn collections(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1917,13 +2707,25 @@ impl Server {
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    accept_json: AcceptJson,
+    Query(query): Query<InscriptionsQuery>,
+    RawQuery(raw_query): RawQuery,
+    AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
+    if accept_json {
+      return Self::inscription_listing(index, query).await;
+    }
+
+    if raw_query.is_some_and(|raw_query| !raw_query.is_empty()) {
+      return Err(ServerError::BadRequest(
+        "filtering, sorting and cursors are only supported by the JSON API".into(),
+      ));
+    }
+
     Self::inscriptions_paginated(
       Extension(server_config),
       Extension(index),
       Path(0),
-      accept_json,
+      AcceptJson(accept_json),
     )
     .await
   }
@@ -1942,10 +2744,20 @@ impl Server {
       let next = more.then_some(page_index + 1);
 
       Ok(if accept_json {
-        Json(api::Inscriptions {
+        // pages are in descending sequence number order, so the next page can
+        // also be requested with the last inscription as a cursor
+        let next_cursor = match inscriptions.last().filter(|_| more) {
+          Some(last) => index
+            .get_inscription_entry(*last)?
+            .map(|entry| entry.sequence_number),
+          None => None,
+        };
+
+        Json(api::InscriptionListing {
           ids: inscriptions,
-          page_index,
           more,
+          next_cursor,
+          page_index,
         })
         .into_response()
       } else {
@@ -1960,6 +2772,77 @@ impl Server {
     })
   }
 
+  async fn inscription_listing(index: Arc<Index>, query: InscriptionsQuery) -> ServerResult {
+    task::block_in_place(|| {
+      let filter = InscriptionFilter {
+        charm: query.charm,
+        content_type: query.content_type,
+        cursed: query.cursed,
+        max_height: query.max_height,
+        media: query.media,
+        metaprotocol: query.metaprotocol,
+        min_height: query.min_height,
+        parent: query.parent,
+        rarity: query.rarity,
+      };
+
+      Self::check_inscription_filter(&index, &filter)?;
+
+      let sort = query.sort.unwrap_or_default();
+
+      if sort.needs_details() && !index.has_inscription_details_index() {
+        return Err(ServerError::BadRequest(
+          "sorting by fee or size requires index created with `--index-inscription-details` flag"
+            .into(),
+        ));
+      }
+
+      let (ids, next_cursor) = index
+        .list_inscriptions(
+          &filter,
+          sort,
+          query.order.unwrap_or_default(),
+          query.cursor,
+          100,
+        )?
+        .ok_or_else(|| ServerError::BadRequest("unknown cursor".into()))?;
+
+      Ok(
+        Json(api::InscriptionListing {
+          ids,
+          more: next_cursor.is_some(),
+          next_cursor,
+          page_index: 0,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  fn check_inscription_filter(index: &Index, filter: &InscriptionFilter) -> ServerResult<()> {
+    if filter.needs_details() && !index.has_inscription_details_index() {
+      return Err(ServerError::BadRequest(
+        "filtering by content type, media or metaprotocol requires index created with `--index-inscription-details` flag".into(),
+      ));
+    }
+
+    if filter.rarity.is_some() && !index.has_sat_index() {
+      return Err(ServerError::BadRequest(
+        "filtering by rarity requires sat index".into(),
+      ));
+    }
+
+    if let Some(media) = &filter.media {
+      if !Media::KINDS.contains(&media.as_str()) {
+        return Err(ServerError::BadRequest(format!(
+          "unknown media kind `{media}`"
+        )));
+      }
+    }
+
+    Ok(())
+  }
+
   async fn inscriptions_in_block(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -2284,13 +3167,17 @@ mod tests {
         .or_defaults()
         .unwrap();
 
-      let index = Arc::new(Index::open(&settings).unwrap());
+      let (index, event_receiver) = server.open_index(&settings).unwrap();
       let ord_server_handle = Handle::new();
 
       {
         let index = index.clone();
         let ord_server_handle = ord_server_handle.clone();
-        thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+        thread::spawn(|| {
+          server
+            .run(settings, index, ord_server_handle, event_receiver)
+            .unwrap()
+        });
       }
 
       while index.statistic(crate::index::Statistic::Commits) == 0 {
@@ -2328,6 +3215,14 @@ mod tests {
       self.server_flag("--https")
     }
 
+    fn index_addresses(self) -> Self {
+      self.ord_flag("--index-addresses")
+    }
+
+    fn index_inscription_details(self) -> Self {
+      self.ord_flag("--index-inscription-details")
+    }
+
     fn index_runes(self) -> Self {
       self.ord_flag("--index-runes")
     }
@@ -2336,6 +3231,10 @@ mod tests {
       self.ord_flag("--index-sats")
     }
 
+    fn index_search(self) -> Self {
+      self.ord_flag("--index-search")
+    }
+
     fn redirect_http_to_https(self) -> Self {
       self.server_flag("--redirect-http-to-https")
     }
@@ -2424,6 +3323,25 @@ mod tests {
       reqwest::blocking::get(self.join_url(path.as_ref())).unwrap()
     }
 
+    #[track_caller]
+    fn get_with_headers(
+      &self,
+      path: impl AsRef<str>,
+      headers: &[(header::HeaderName, &str)],
+    ) -> reqwest::blocking::Response {
+      if let Err(error) = self.index.update() {
+        log::error!("{error}");
+      }
+
+      let mut request = reqwest::blocking::Client::new().get(self.join_url(path.as_ref()));
+
+      for (name, value) in headers {
+        request = request.header(name, *value);
+      }
+
+      request.send().unwrap()
+    }
+
     #[track_caller]
     pub(crate) fn get_json<T: DeserializeOwned>(&self, path: impl AsRef<str>) -> T {
       if let Err(error) = self.index.update() {
@@ -2443,6 +3361,19 @@ mod tests {
       response.json().unwrap()
     }
 
+    #[track_caller]
+    fn graphql(&self, query: &str) -> reqwest::blocking::Response {
+      if let Err(error) = self.index.update() {
+        log::error!("{error}");
+      }
+
+      reqwest::blocking::Client::new()
+        .post(self.join_url("/graphql"))
+        .json(&serde_json::json!({ "query": query }))
+        .send()
+        .unwrap()
+    }
+
     fn join_url(&self, url: &str) -> Url {
       self.url.join(url).unwrap()
     }
@@ -2765,33 +3696,198 @@ mod tests {
   }
 
   #[test]
-  fn search_for_blockhash_returns_block() {
-    TestServer::new().assert_redirect(
-      "/search/12a765e31ffd4059bada1e25190f6e98c99d9714d334efa41a195a7e7e04bfe2",
-      "/block/12a765e31ffd4059bada1e25190f6e98c99d9714d334efa41a195a7e7e04bfe2",
-    );
-  }
+  fn full_text_search_requires_search_index() {
+    let server = TestServer::new();
 
-  #[test]
-  fn search_for_txid_returns_transaction() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000",
-      "/tx/0000000000000000000000000000000000000000000000000000000000000000",
+    server.assert_response(
+      "/search?q=foo",
+      StatusCode::NOT_FOUND,
+      "this server has no search index",
     );
-  }
 
-  #[test]
-  fn search_for_outpoint_returns_output() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000:0",
-      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
+    server.assert_response(
+      "/search",
+      StatusCode::BAD_REQUEST,
+      "missing `q` or `query` parameter",
     );
   }
 
   #[test]
-  fn search_for_inscription_id_returns_inscription() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000i0",
+  fn full_text_search_finds_inscriptions() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_search()
+      .build();
+
+    server.mine_blocks(1);
+
+    let mut ids = Vec::new();
+
+    for (i, inscription) in [
+      inscription("text/plain;charset=utf-8", "Hello, world!"),
+      inscription("text/html", "<p>hello there</p>"),
+      inscription("image/png", "hello"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription.to_witness())],
+        ..default()
+      });
+
+      server.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    let search = |query: &str| {
+      server
+        .get_json::<api::Search>(format!("/search?{query}"))
+        .inscriptions
+    };
+
+    assert_eq!(search("q=HELLO"), [ids[1], ids[0]]);
+    assert_eq!(search("q=hello+world"), [ids[0]]);
+    assert!(search("q=goodbye").is_empty());
+    assert_eq!(search("q=hello&content_type=text/html"), [ids[1]]);
+    assert_eq!(search("q=hello&content_type=text"), [ids[1], ids[0]]);
+    assert_eq!(search("q=&content_type=image"), [ids[2]]);
+    assert_eq!(search("q=hello&min_height=3"), [ids[1]]);
+    assert_eq!(search("q=hello&max_height=2"), [ids[0]]);
+    assert!(search(&format!("q=hello&parent={}", ids[2])).is_empty());
+    assert_eq!(search("q=&min_height=3"), [ids[2], ids[1]]);
+    assert_eq!(search("q="), [ids[2], ids[1], ids[0]]);
+    assert_eq!(search("q=hello&cursor=1"), [ids[0]]);
+    assert!(search("q=hello&cursor=0").is_empty());
+
+    let search = server.get_json::<api::Search>("/search?q=hello");
+    assert!(!search.more);
+    assert_eq!(search.next_cursor, None);
+
+    server.assert_response_regex(
+      "/search?q=hello",
+      StatusCode::OK,
+      format!(
+        ".*<title>Search: hello</title>.*
+<h2>Inscriptions</h2>
+<div class=thumbnails>
+  <a href=/inscription/{}>.*</a>
+  <a href=/inscription/{}>.*</a>
+</div>
+  </main>.*",
+        ids[1], ids[0]
+      ),
+    );
+
+    server.assert_response_regex(
+      "/search?q=hello&cursor=1",
+      StatusCode::OK,
+      format!(
+        ".*<h2>Inscriptions</h2>
+<div class=thumbnails>
+  <a href=/inscription/{}>.*</a>
+</div>
+<div class=center>
+  <a class=prev href=\"/search\\?q=hello\">first</a>
+next
+</div>.*",
+        ids[0]
+      ),
+    );
+
+    server.assert_response(
+      "/search?q=hello&rarity=common",
+      StatusCode::BAD_REQUEST,
+      "filtering by rarity requires sat index",
+    );
+  }
+
+  #[test]
+  fn full_text_search_finds_runes() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .index_search()
+      .build();
+
+    server.mine_blocks(1);
+
+    server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(u128::MAX),
+          spacers: Some(1),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let spaced_rune = SpacedRune {
+      rune: Rune(RUNE),
+      spacers: 1,
+    };
+
+    for query in ["aaaaaaaaaaaaa", "A•AAAAAAAAAAAA", "a+aaaaaaaaaaaa"] {
+      assert_eq!(
+        server
+          .get_json::<api::Search>(format!("/search?q={query}"))
+          .runes,
+        [spaced_rune],
+      );
+    }
+
+    assert!(server
+      .get_json::<api::Search>("/search?q=aaaa")
+      .runes
+      .is_empty());
+
+    server.assert_response_regex(
+      "/search?q=aaaaaaaaaaaaa",
+      StatusCode::OK,
+      format!(
+        ".*<h2>Runes</h2>\n<ul>\n  <li><a href=/rune/{spaced_rune}>{spaced_rune}</a></li>\n</ul>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn search_for_blockhash_returns_block() {
+    TestServer::new().assert_redirect(
+      "/search/12a765e31ffd4059bada1e25190f6e98c99d9714d334efa41a195a7e7e04bfe2",
+      "/block/12a765e31ffd4059bada1e25190f6e98c99d9714d334efa41a195a7e7e04bfe2",
+    );
+  }
+
+  #[test]
+  fn search_for_txid_returns_transaction() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000",
+      "/tx/0000000000000000000000000000000000000000000000000000000000000000",
+    );
+  }
+
+  #[test]
+  fn search_for_outpoint_returns_output() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000:0",
+      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
+    );
+  }
+
+  #[test]
+  fn search_for_inscription_id_returns_inscription() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000i0",
       "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0",
     );
   }
@@ -3549,6 +4645,7 @@ mod tests {
         sat_ranges: None,
         indexed: true,
         inscriptions: Vec::new(),
+        mweb: false,
         runes: vec![(
           SpacedRune {
             rune: Rune(RUNE),
@@ -3650,6 +4747,8 @@ mod tests {
   <dd><a href=/runes>0</a></dd>
   <dt>lost sats</dt>
   <dd>.*</dd>
+  <dt>mweb sats</dt>
+  <dd>.*</dd>
   <dt>started</dt>
   <dd>.*</dd>
   <dt>uptime</dt>
@@ -3662,8 +4761,14 @@ mod tests {
   <dd>false</dd>
   <dt>address index</dt>
   <dd>false</dd>
+  <dt>address history index</dt>
+  <dd>false</dd>
+  <dt>inscription history index</dt>
+  <dd>false</dd>
   <dt>inscription index</dt>
   <dd>true</dd>
+  <dt>rune history index</dt>
+  <dd>false</dd>
   <dt>rune index</dt>
   <dd>false</dd>
   <dt>sat index</dt>
@@ -4812,260 +5917,1093 @@ mod tests {
       .build();
     server.mine_blocks(1);
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
-      ..default()
-    });
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      r".*<dt>sat</dt>\s*<dd><a href=/sat/5000000000>5000000000</a></dd>\s*<dt>sat name</dt>\s*<dd><a href=/sat/bgmbpulndxfd>bgmbpulndxfd</a></dd>\s*<dt>preview</dt>.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_can_be_looked_up_by_sat_name() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", Sat(5000000000).name()),
+      StatusCode::OK,
+      ".*<title>Inscription 0</title.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_can_be_looked_up_by_sat_name_with_letter_i() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.assert_response_regex("/inscription/i", StatusCode::NOT_FOUND, ".*");
+  }
+
+  #[test]
+  fn inscription_page_does_not_have_sat_when_sats_are_not_tracked() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      r".*<dt>value</dt>\s*<dd>5000000000</dd>\s*<dt>preview</dt>.*",
+    );
+  }
+
+  #[test]
+  fn strict_transport_security_header_is_set() {
+    assert_eq!(
+      TestServer::new()
+        .get("/status")
+        .headers()
+        .get(header::STRICT_TRANSPORT_SECURITY)
+        .unwrap(),
+      "max-age=31536000; includeSubDomains; preload",
+    );
+  }
+
+  #[test]
+  fn feed() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/feed.xml",
+      StatusCode::OK,
+      ".*<title>Inscription 0</title>.*",
+    );
+  }
+
+  #[test]
+  fn events_are_not_served_by_default() {
+    TestServer::new().assert_response("/events", StatusCode::NOT_FOUND, "event stream not found");
+  }
+
+  #[test]
+  fn events_rejects_unknown_kinds() {
+    let server = TestServer::builder().server_flag("--events").build();
+
+    assert_eq!(
+      reqwest::blocking::get(server.join_url("/events?kind=foo"))
+        .unwrap()
+        .status(),
+      StatusCode::BAD_REQUEST,
+    );
+  }
+
+  #[test]
+  fn events() {
+    use std::io::BufRead;
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--events")
+      .build();
+
+    server.mine_blocks(1);
+
+    let response =
+      reqwest::blocking::get(server.join_url("/events?kind=inscription_created")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream",
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let mut lines = io::BufReader::new(response).lines();
+
+    assert_eq!(lines.next().unwrap().unwrap(), "event: inscription_created");
+
+    let data = lines.next().unwrap().unwrap();
+
+    let event = serde_json::from_str::<Event>(data.strip_prefix("data: ").unwrap()).unwrap();
+
+    assert_eq!(
+      event.inscription_id(),
+      Some(InscriptionId { txid, index: 0 }),
+    );
+    assert_eq!(event.block_height(), 2);
+  }
+
+  #[test]
+  fn inscription_with_unknown_type_and_no_body_has_unknown_preview() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("foo/bar".as_bytes().to_vec()),
+          body: None,
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn inscription_with_known_type_and_no_body_has_unknown_preview() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("image/png".as_bytes().to_vec()),
+          body: None,
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn content_responses_have_cache_control_headers() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/content/{}", InscriptionId { txid, index: 0 }));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "public, max-age=1209600, immutable"
+    );
+  }
+
+  #[test]
+  fn content_responses_support_range_requests() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("video/mp4", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let path = format!("/content/{}", InscriptionId { txid, index: 0 });
+
+    let response = server.get(&path);
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+    assert_eq!(response.text().unwrap(), "hello");
+
+    let response = server.get_with_headers(&path, &[(header::RANGE, "bytes=1-3")]);
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 1-3/5");
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "video/mp4");
+    assert_eq!(response.text().unwrap(), "ell");
+
+    let response = server.get_with_headers(&path, &[(header::RANGE, "bytes=-2")]);
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 3-4/5");
+    assert_eq!(response.text().unwrap(), "lo");
+
+    let response = server.get_with_headers(&path, &[(header::RANGE, "bytes=5-")]);
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes */5");
+
+    let response = server.get_with_headers(&path, &[(header::RANGE, "bytes=0-1,3-4")]);
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "hello");
+
+    let response = server.get_with_headers(
+      &path,
+      &[(header::RANGE, "bytes=1-3"), (header::IF_RANGE, "\"foo\"")],
+    );
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "hello");
+  }
+
+  #[test]
+  fn content_responses_have_etags() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let etag = format!("\"{id}\"");
+
+    let response = server.get(format!("/content/{id}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+
+    let response = server.get_with_headers(
+      format!("/content/{id}"),
+      &[(header::IF_NONE_MATCH, &format!("\"foo\", W/{etag}"))],
+    );
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    assert!(response.text().unwrap().is_empty());
+
+    let response = server.get_with_headers(
+      format!("/content/{id}"),
+      &[(header::IF_NONE_MATCH, "\"foo\"")],
+    );
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "hello");
+
+    let response = server.get_with_headers(
+      format!("/content/{id}"),
+      &[(header::RANGE, "bytes=0-1"), (header::IF_RANGE, &etag)],
+    );
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.text().unwrap(), "he");
+  }
+
+  #[test]
+  fn encoded_content_responses_have_encoding_specific_etags() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          content_encoding: Some("br".into()),
+          body: Some("hello".into()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let etag = format!("\"{id}-br\"");
+
+    let response =
+      server.get_with_headers(format!("/content/{id}"), &[(header::ACCEPT_ENCODING, "br")]);
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    assert_eq!(response.headers()[header::VARY], "accept-encoding");
+
+    let response = server.get_with_headers(
+      format!("/content/{id}"),
+      &[
+        (header::ACCEPT_ENCODING, "br"),
+        (header::IF_NONE_MATCH, &etag),
+      ],
+    );
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    assert_eq!(response.headers()[header::VARY], "accept-encoding");
+
+    let response = server.get_with_headers(
+      format!("/content/{id}"),
+      &[
+        (header::ACCEPT_ENCODING, "identity"),
+        (header::IF_NONE_MATCH, &etag),
+      ],
+    );
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+  }
+
+  #[test]
+  fn error_content_responses_have_max_age_zero_cache_control_headers() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    let response =
+      server.get("/content/6ac5cacb768794f4fd7a78bf00f2074891fce68bd65c4ff36e77177237aacacai0");
+
+    assert_eq!(response.status(), 404);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-store"
+    );
+  }
+
+  #[test]
+  fn inscriptions_page_with_no_prev_or_next() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build()
+      .assert_response_regex("/inscriptions", StatusCode::OK, ".*prev\nnext.*");
+  }
+
+  #[test]
+  fn inscriptions_page_with_no_next() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    for i in 0..101 {
+      server.mine_blocks(1);
+      server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/inscriptions/1",
+      StatusCode::OK,
+      ".*<a class=prev href=/inscriptions/0>prev</a>\nnext.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_page_with_no_prev() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    for i in 0..101 {
+      server.mine_blocks(1);
+      server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/inscriptions/0",
+      StatusCode::OK,
+      ".*prev\n<a class=next href=/inscriptions/1>next</a>.*",
+    );
+  }
+
+  #[test]
+  fn inscription_listing_filters_and_sorts() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_inscription_details()
+      .index_sats()
+      .build();
+
+    server.mine_blocks(3);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, inscription("text/plain", "hello").to_witness()),
+        (2, 0, 0, inscription("image/png", "foobarbaz").to_witness()),
+      ],
+      outputs: 2,
+      fee: 1000,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let blessed = InscriptionId { txid, index: 0 };
+    let cursed = InscriptionId { txid, index: 1 };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        3,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain;charset=utf-8".into()),
+          body: Some("hi".into()),
+          metaprotocol: Some("foo".into()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      fee: 3000,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let metaprotocol = InscriptionId { txid, index: 0 };
+
+    let list = |query: &str| {
+      server
+        .get_json::<api::InscriptionListing>(format!("/inscriptions?{query}"))
+        .ids
+    };
+
+    assert_eq!(list("order=desc"), [metaprotocol, cursed, blessed]);
+    assert_eq!(list("order=asc"), [blessed, cursed, metaprotocol]);
+    assert_eq!(list("sort=number"), [metaprotocol, blessed, cursed]);
+    assert_eq!(
+      list("sort=number&order=asc"),
+      [cursed, blessed, metaprotocol]
+    );
+    assert_eq!(list("sort=fee"), [metaprotocol, cursed, blessed]);
+    assert_eq!(list("sort=size"), [cursed, blessed, metaprotocol]);
+    assert_eq!(list("sort=size&order=asc"), [metaprotocol, blessed, cursed]);
+
+    assert_eq!(list("content_type=text/plain"), [metaprotocol, blessed]);
+    assert_eq!(list("content_type=image"), [cursed]);
+    assert_eq!(list("media=image"), [cursed]);
+    assert_eq!(list("metaprotocol=foo"), [metaprotocol]);
+    assert_eq!(list("cursed=true"), [cursed]);
+    assert_eq!(list("cursed=false"), [metaprotocol, blessed]);
+    assert_eq!(list("charm=cursed"), [cursed]);
+    assert_eq!(list("rarity=uncommon"), [metaprotocol, cursed, blessed]);
+    assert_eq!(list("min_height=5"), [metaprotocol]);
+    assert_eq!(list("max_height=4&sort=number"), [blessed, cursed]);
+    assert!(list(&format!("parent={blessed}")).is_empty());
+
+    let response = server.get_with_headers(
+      "/inscriptions?media=foo",
+      &[(header::ACCEPT, "application/json")],
+    );
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.text().unwrap(), "unknown media kind `foo`");
+
+    let response = server.get_with_headers(
+      "/inscriptions?cursor=3",
+      &[(header::ACCEPT, "application/json")],
+    );
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.text().unwrap(), "unknown cursor");
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionListing>("/inscriptions"),
+      api::InscriptionListing {
+        ids: vec![metaprotocol, cursed, blessed],
+        more: false,
+        next_cursor: None,
+        page_index: 0,
+      }
+    );
+
+    server.assert_response(
+      "/inscriptions?sort=fee",
+      StatusCode::BAD_REQUEST,
+      "filtering, sorting and cursors are only supported by the JSON API",
+    );
+  }
+
+  #[test]
+  fn inscription_listing_details_require_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    assert_eq!(
+      server
+        .get_json::<api::InscriptionListing>("/inscriptions?sort=number&cursed=false")
+        .ids,
+      [id],
+    );
+
+    for (query, message) in [
+      (
+        "sort=fee",
+        "sorting by fee or size requires index created with `--index-inscription-details` flag",
+      ),
+      (
+        "media=text",
+        "filtering by content type, media or metaprotocol requires index created with `--index-inscription-details` flag",
+      ),
+    ] {
+      let response = server.get_with_headers(
+        format!("/inscriptions?{query}"),
+        &[(header::ACCEPT, "application/json")],
+      );
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+      assert_eq!(response.text().unwrap(), message);
+    }
+  }
+
+  #[test]
+  fn inscription_listing_cursors_are_stable() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let mut ids = Vec::new();
+
+    for i in 0..101 {
+      server.mine_blocks(1);
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..default()
+      });
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    server.mine_blocks(1);
+
+    let listing = server.get_json::<api::InscriptionListing>("/inscriptions?order=desc");
+
+    assert_eq!(listing.ids.len(), 100);
+    assert_eq!(listing.ids[0], ids[100]);
+    assert!(listing.more);
+    assert_eq!(listing.next_cursor, Some(1));
+
+    let page = server.get_json::<api::InscriptionListing>("/inscriptions/0");
+
+    assert_eq!(page.ids, listing.ids);
+    assert_eq!(page.next_cursor, Some(1));
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(102, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionListing>("/inscriptions?order=desc&cursor=1"),
+      api::InscriptionListing {
+        ids: vec![ids[0]],
+        more: false,
+        next_cursor: None,
+        page_index: 0,
+      }
+    );
+  }
+
+  #[test]
+  fn collection_stats() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
+      .build();
+
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/r/collection/{}", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Collection>(format!("/r/collection/{parent}")),
+      api::Collection {
+        children: 0,
+        content_types: Vec::new(),
+        holders: Some(0),
+        id: parent,
+        mints: Vec::new(),
+        number: 0,
+      }
+    );
+
+    let mut builder = script::Builder::new();
+    for (content_type, body) in [("text/plain", "foo"), ("image/png", "bar")] {
+      builder = Inscription {
+        content_type: Some(content_type.into()),
+        body: Some(body.into()),
+        parents: vec![parent.value()],
+        ..default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Witness::from_slice(&[builder.into_bytes(), Vec::new()]),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          3,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain;charset=utf-8".into()),
+            body: Some("baz".into()),
+            parents: vec![parent.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (3, 1, 0, Default::default()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let address = Chain::Regtest
+      .address_from_script(&ScriptBuf::new_v0_p2wpkh(&bitcoin::WPubkeyHash::all_zeros()))
+      .unwrap()
+      .to_string();
+
+    let collection = api::Collection {
+      children: 3,
+      content_types: vec![
+        api::CollectionContentType {
+          children: 2,
+          content_type: Some("text/plain".into()),
+        },
+        api::CollectionContentType {
+          children: 1,
+          content_type: Some("image/png".into()),
+        },
+      ],
+      holders: Some(1),
+      id: parent,
+      mints: vec![
+        api::CollectionMints {
+          children: 2,
+          height: 3,
+        },
+        api::CollectionMints {
+          children: 1,
+          height: 4,
+        },
+      ],
+      number: 0,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::Collection>(format!("/r/collection/{parent}")),
+      collection
+    );
 
-    server.mine_blocks(1);
+    pretty_assert_eq!(
+      server.get_json::<api::Collection>(format!("/collection/{parent}")),
+      collection
+    );
 
     server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      format!("/collection/{parent}"),
       StatusCode::OK,
-      r".*<dt>sat</dt>\s*<dd><a href=/sat/5000000000>5000000000</a></dd>\s*<dt>sat name</dt>\s*<dd><a href=/sat/bgmbpulndxfd>bgmbpulndxfd</a></dd>\s*<dt>preview</dt>.*",
+      format!(
+        ".*<title>Inscription 0 Collection</title>.*
+<dl>
+  <dt>children</dt>
+  <dd><a href=/children/{parent}>3</a></dd>
+  <dt>holders</dt>
+  <dd><a href=/collection/{parent}/holders>1</a></dd>
+</dl>
+<h2>Content Types</h2>.*"
+      ),
     );
-  }
 
-  #[test]
-  fn inscriptions_can_be_looked_up_by_sat_name() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
-    server.mine_blocks(1);
+    pretty_assert_eq!(
+      server.get_json::<api::CollectionHolders>(format!("/r/collection/{parent}/holders")),
+      api::CollectionHolders {
+        holders: vec![api::CollectionHolder {
+          address: Some(address),
+          children: 3,
+        }],
+        id: parent,
+        more: false,
+        page: 0,
+      }
+    );
 
     server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      inputs: &[(4, 1, 0, Default::default())],
+      receiver: Some(recipient_as_address()),
       ..default()
     });
 
     server.mine_blocks(1);
 
+    let holder = Chain::Regtest
+      .address_from_script(&recipient())
+      .unwrap()
+      .to_string();
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Collection>(format!("/r/collection/{parent}"))
+        .holders,
+      Some(1),
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::CollectionHolders>(format!("/collection/{parent}/holders")),
+      api::CollectionHolders {
+        holders: vec![api::CollectionHolder {
+          address: Some(holder.clone()),
+          children: 3,
+        }],
+        id: parent,
+        more: false,
+        page: 0,
+      }
+    );
+
     server.assert_response_regex(
-      format!("/inscription/{}", Sat(5000000000).name()),
+      format!("/collection/{parent}/holders"),
       StatusCode::OK,
-      ".*<title>Inscription 0</title.*",
+      format!(
+        ".*<title>Inscription 0 Collection Holders</title>.*
+<h1><a href=/collection/{parent}>Inscription 0 Collection</a> Holders</h1>
+<table>
+  <tr>
+    <th>holder</th>
+    <th>children</th>
+  </tr>
+  <tr>
+    <td><a class=monospace href=/address/{holder}>{holder}</a></td>
+    <td>3</td>
+  </tr>
+</table>.*"
+      ),
     );
   }
 
   #[test]
-  fn inscriptions_can_be_looked_up_by_sat_name_with_letter_i() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
-    server.assert_response_regex("/inscription/i", StatusCode::NOT_FOUND, ".*");
-  }
-
-  #[test]
-  fn inscription_page_does_not_have_sat_when_sats_are_not_tracked() {
+  fn collection_holders_require_address_index() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
+
     server.mine_blocks(1);
 
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      r".*<dt>value</dt>\s*<dd>5000000000</dd>\s*<dt>preview</dt>.*",
+    assert_eq!(
+      server
+        .get_json::<api::Collection>(format!("/r/collection/{txid}i0"))
+        .holders,
+      None,
     );
-  }
 
-  #[test]
-  fn strict_transport_security_header_is_set() {
-    assert_eq!(
-      TestServer::new()
-        .get("/status")
-        .headers()
-        .get(header::STRICT_TRANSPORT_SECURITY)
-        .unwrap(),
-      "max-age=31536000; includeSubDomains; preload",
+    server.assert_response(
+      format!("/r/collection/{txid}i0/holders"),
+      StatusCode::NOT_FOUND,
+      "this server has no address index",
     );
   }
 
   #[test]
-  fn feed() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
+  fn graphql_inscription_graph() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
     server.mine_blocks(1);
 
-    server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/feed.xml",
-      StatusCode::OK,
-      ".*<title>Inscription 0</title>.*",
+    let id = InscriptionId { txid, index: 0 };
+
+    let response = server.graphql(&format!(
+      r#"{{
+        inscription(query: "{id}") {{
+          id
+          number
+          contentType
+          block {{ height }}
+          output {{
+            outpoint
+            transaction {{ txid inscriptions {{ edges {{ node {{ id }} }} }} }}
+          }}
+          sat {{ number }}
+        }}
+        block(query: "2") {{
+          height
+          inscriptions {{ edges {{ node {{ id }} }} }}
+        }}
+      }}"#
+    ));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    pretty_assert_eq!(
+      response.json::<serde_json::Value>().unwrap(),
+      serde_json::json!({
+        "data": {
+          "inscription": {
+            "id": id.to_string(),
+            "number": 0,
+            "contentType": "text/plain",
+            "block": { "height": 2 },
+            "output": {
+              "outpoint": format!("{txid}:0"),
+              "transaction": {
+                "txid": txid.to_string(),
+                "inscriptions": { "edges": [{ "node": { "id": id.to_string() } }] },
+              },
+            },
+            "sat": null,
+          },
+          "block": {
+            "height": 2,
+            "inscriptions": { "edges": [{ "node": { "id": id.to_string() } }] },
+          },
+        },
+      }),
     );
   }
 
   #[test]
-  fn inscription_with_unknown_type_and_no_body_has_unknown_preview() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
-    server.mine_blocks(1);
+  fn graphql_errors_are_returned() {
+    let server = TestServer::new();
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        Inscription {
-          content_type: Some("foo/bar".as_bytes().to_vec()),
-          body: None,
-          ..default()
-        }
-        .to_witness(),
-      )],
-      ..default()
-    });
+    let response = server.graphql(r#"{ rune(query: "FOO") { name } }"#);
 
-    let inscription_id = InscriptionId { txid, index: 0 };
+    assert_eq!(response.status(), StatusCode::OK);
 
-    server.mine_blocks(1);
+    let response = response.json::<serde_json::Value>().unwrap();
 
-    server.assert_response(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    assert_eq!(response["data"]["rune"], serde_json::Value::Null);
+    assert_eq!(
+      response["errors"][0]["message"],
+      "this server has no rune index"
     );
   }
 
   #[test]
-  fn inscription_with_known_type_and_no_body_has_unknown_preview() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
+  fn graphql_list_fields_are_paginated() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
     server.mine_blocks(1);
 
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        Inscription {
-          content_type: Some("image/png".as_bytes().to_vec()),
-          body: None,
-          ..default()
-        }
-        .to_witness(),
-      )],
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 3,
       ..default()
     });
 
-    let inscription_id = InscriptionId { txid, index: 0 };
-
     server.mine_blocks(1);
 
-    server.assert_response(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    let page = |after: &str| {
+      server
+        .graphql(&format!(
+          r#"{{
+            transaction(txid: "{txid}") {{
+              outputs(first: 2{after}) {{
+                edges {{ cursor node {{ outpoint }} }}
+                pageInfo {{ hasPreviousPage hasNextPage endCursor }}
+              }}
+            }}
+          }}"#
+        ))
+        .json::<serde_json::Value>()
+        .unwrap()["data"]["transaction"]["outputs"]
+        .clone()
+    };
+
+    let first = page("");
+
+    pretty_assert_eq!(
+      first,
+      serde_json::json!({
+        "edges": [
+          { "cursor": "0", "node": { "outpoint": format!("{txid}:0") } },
+          { "cursor": "1", "node": { "outpoint": format!("{txid}:1") } },
+        ],
+        "pageInfo": {
+          "hasPreviousPage": false,
+          "hasNextPage": true,
+          "endCursor": "1",
+        },
+      }),
+    );
+
+    pretty_assert_eq!(
+      page(r#", after: "1""#),
+      serde_json::json!({
+        "edges": [
+          { "cursor": "2", "node": { "outpoint": format!("{txid}:2") } },
+        ],
+        "pageInfo": {
+          "hasPreviousPage": true,
+          "hasNextPage": false,
+          "endCursor": "2",
+        },
+      }),
     );
   }
 
   #[test]
-  fn content_responses_have_cache_control_headers() {
+  fn graphql_page_size_is_capped() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
+
     server.mine_blocks(1);
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 101,
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let response = server.get(format!("/content/{}", InscriptionId { txid, index: 0 }));
-
-    assert_eq!(response.status(), StatusCode::OK);
-    assert_eq!(
-      response.headers().get(header::CACHE_CONTROL).unwrap(),
-      "public, max-age=1209600, immutable"
-    );
-  }
-
-  #[test]
-  fn error_content_responses_have_max_age_zero_cache_control_headers() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
-    let response =
-      server.get("/content/6ac5cacb768794f4fd7a78bf00f2074891fce68bd65c4ff36e77177237aacacai0");
+    let response = server
+      .graphql(
+        r#"{ block(query: "2") { transactions(first: 1) { edges { node { outputs(first: 1000) { edges { cursor } pageInfo { hasNextPage } } } } } } }"#,
+      )
+      .json::<serde_json::Value>()
+      .unwrap();
 
-    assert_eq!(response.status(), 404);
-    assert_eq!(
-      response.headers().get(header::CACHE_CONTROL).unwrap(),
-      "no-store"
-    );
-  }
+    let outputs = &response["data"]["block"]["transactions"]["edges"][0]["node"]["outputs"];
 
-  #[test]
-  fn inscriptions_page_with_no_prev_or_next() {
-    TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build()
-      .assert_response_regex("/inscriptions", StatusCode::OK, ".*prev\nnext.*");
+    assert_eq!(outputs["edges"].as_array().unwrap().len(), 100);
+    assert_eq!(outputs["pageInfo"]["hasNextPage"], true);
   }
 
   #[test]
-  fn inscriptions_page_with_no_next() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
-
-    for i in 0..101 {
-      server.mine_blocks(1);
-      server.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
-        ..default()
-      });
-    }
+  fn graphql_complexity_is_limited() {
+    let server = TestServer::new();
 
-    server.mine_blocks(1);
+    let response = server
+      .graphql(
+        r#"{ block(query: "0") { transactions { edges { node { outputs { edges { node { inscriptions { edges { node { id } } } } } } } } } } }"#,
+      )
+      .json::<serde_json::Value>()
+      .unwrap();
 
-    server.assert_response_regex(
-      "/inscriptions/1",
-      StatusCode::OK,
-      ".*<a class=prev href=/inscriptions/0>prev</a>\nnext.*",
-    );
+    assert_eq!(response["data"], serde_json::Value::Null);
+    assert_eq!(response["errors"][0]["message"], "Query is too complex.");
   }
 
   #[test]
-  fn inscriptions_page_with_no_prev() {
+  fn graphql_requires_json_api() {
     let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
+      .server_flag("--disable-json-api")
       .build();
 
-    for i in 0..101 {
-      server.mine_blocks(1);
-      server.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
-        ..default()
-      });
-    }
-
-    server.mine_blocks(1);
-
-    server.assert_response_regex(
-      "/inscriptions/0",
-      StatusCode::OK,
-      ".*prev\n<a class=next href=/inscriptions/1>next</a>.*",
+    assert_eq!(
+      server
+        .graphql("{ block(query: \"0\") { height } }")
+        .status(),
+      StatusCode::NOT_FOUND,
     );
   }
 
@@ -5411,6 +7349,7 @@ next
 .*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
     <div class=center>
       <a href=/children/{parent_inscription_id}>all</a>
+      <a href=/collection/{parent_inscription_id}>stats</a>
     </div>.*"
       ),
     );
@@ -6600,6 +8539,16 @@ next
 
     server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
 
+    let response =
+      server.get_with_headers(format!("/content/{id}"), &[(header::RANGE, "bytes=1-")]);
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+      response.headers()[header::ETAG],
+      format!("\"{delegate}\"").as_str()
+    );
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 1-2/3");
+    assert_eq!(response.text().unwrap(), "oo");
+
     server.assert_response(format!("/preview/{id}"), StatusCode::OK, "foo");
 
     assert_eq!(
@@ -0,0 +1,103 @@
+use {super::*, std::ops::Range};
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ByteRange {
+  Satisfiable(Range<usize>),
+  Unsatisfiable,
+}
+
+impl ByteRange {
+  /// Parse a `Range` header against content of length `len`. Returns `None`
+  /// if the header should be ignored and the full content served, which is
+  /// the case for unknown units, malformed ranges, and multiple ranges.
+  pub(crate) fn parse(header: &HeaderValue, len: usize) -> Option<Self> {
+    let spec = header.to_str().ok()?.trim().strip_prefix("bytes=")?;
+
+    if spec.contains(',') {
+      return None;
+    }
+
+    let (start, end) = spec.trim().split_once('-')?;
+
+    let range = match (start.trim(), end.trim()) {
+      ("", "") => return None,
+      ("", suffix) => {
+        let suffix = suffix.parse::<usize>().ok()?;
+
+        if suffix == 0 || len == 0 {
+          return Some(Self::Unsatisfiable);
+        }
+
+        len.saturating_sub(suffix)..len
+      }
+      (start, "") => {
+        let start = start.parse::<usize>().ok()?;
+
+        if start >= len {
+          return Some(Self::Unsatisfiable);
+        }
+
+        start..len
+      }
+      (start, end) => {
+        let start = start.parse::<usize>().ok()?;
+        let end = end.parse::<usize>().ok()?;
+
+        if end < start {
+          return None;
+        }
+
+        if start >= len {
+          return Some(Self::Unsatisfiable);
+        }
+
+        start..end.min(len - 1) + 1
+      }
+    };
+
+    Some(Self::Satisfiable(range))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[track_caller]
+  fn case(header: &str, len: usize, expected: Option<ByteRange>) {
+    assert_eq!(
+      ByteRange::parse(&HeaderValue::from_str(header).unwrap(), len),
+      expected
+    );
+  }
+
+  #[test]
+  fn satisfiable() {
+    case("bytes=0-0", 10, Some(ByteRange::Satisfiable(0..1)));
+    case("bytes=2-5", 10, Some(ByteRange::Satisfiable(2..6)));
+    case("bytes=2-", 10, Some(ByteRange::Satisfiable(2..10)));
+    case("bytes=-3", 10, Some(ByteRange::Satisfiable(7..10)));
+    case("bytes=-30", 10, Some(ByteRange::Satisfiable(0..10)));
+    case("bytes=5-100", 10, Some(ByteRange::Satisfiable(5..10)));
+    case(" bytes= 1 - 2 ", 10, Some(ByteRange::Satisfiable(1..3)));
+  }
+
+  #[test]
+  fn unsatisfiable() {
+    case("bytes=10-", 10, Some(ByteRange::Unsatisfiable));
+    case("bytes=10-20", 10, Some(ByteRange::Unsatisfiable));
+    case("bytes=-0", 10, Some(ByteRange::Unsatisfiable));
+    case("bytes=0-", 0, Some(ByteRange::Unsatisfiable));
+    case("bytes=-1", 0, Some(ByteRange::Unsatisfiable));
+  }
+
+  #[test]
+  fn ignored() {
+    case("items=0-1", 10, None);
+    case("bytes=", 10, None);
+    case("bytes=-", 10, None);
+    case("bytes=5-2", 10, None);
+    case("bytes=a-2", 10, None);
+    case("bytes=0-1,3-4", 10, None);
+  }
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+#[derive(Debug, DeserializeFromStr)]
+pub(crate) struct EventKinds(Vec<&'static str>);
+
+impl FromStr for EventKinds {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    s.split(',')
+      .map(str::trim)
+      .filter(|kind| !kind.is_empty())
+      .map(|kind| {
+        Event::KINDS
+          .iter()
+          .find(|known| **known == kind)
+          .copied()
+          .ok_or_else(|| anyhow!("unknown event kind `{kind}`"))
+      })
+      .collect::<Result<Vec<&'static str>>>()
+      .map(Self)
+  }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct EventFilter {
+  pub(crate) inscription: Option<InscriptionId>,
+  pub(crate) kind: Option<EventKinds>,
+  pub(crate) parent: Option<InscriptionId>,
+  pub(crate) rune: Option<RuneId>,
+}
+
+impl EventFilter {
+  pub(crate) fn matches(&self, index: &Index, event: &Event) -> Result<bool> {
+    if let Some(EventKinds(kinds)) = &self.kind {
+      if !kinds.contains(&event.kind()) {
+        return Ok(false);
+      }
+    }
+
+    if let Some(inscription) = self.inscription {
+      if event.inscription_id() != Some(inscription) {
+        return Ok(false);
+      }
+    }
+
+    if let Some(rune) = self.rune {
+      if event.rune_id() != Some(rune) {
+        return Ok(false);
+      }
+    }
+
+    if let Some(parent) = self.parent {
+      let event = match event {
+        Event::RolledBack { event } => event.as_ref(),
+        event => event,
+      };
+
+      match event {
+        Event::InscriptionCreated {
+          parent_inscription_ids,
+          ..
+        } => {
+          if !parent_inscription_ids.contains(&parent) {
+            return Ok(false);
+          }
+        }
+        Event::InscriptionTransferred { inscription_id, .. } => {
+          let Some(parent) = index.get_inscription_entry(parent)? else {
+            return Ok(false);
+          };
+
+          let Some(child) = index.get_inscription_entry(*inscription_id)? else {
+            return Ok(false);
+          };
+
+          if !child.parents.contains(&parent.sequence_number) {
+            return Ok(false);
+          }
+        }
+        _ => return Ok(false),
+      }
+    }
+
+    Ok(true)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn kinds_are_comma_separated() {
+    assert_eq!(
+      "inscription_created, rune_minted"
+        .parse::<EventKinds>()
+        .unwrap()
+        .0,
+      ["inscription_created", "rune_minted"],
+    );
+  }
+
+  #[test]
+  fn unknown_kinds_are_rejected() {
+    assert_eq!(
+      "inscription_created,foo"
+        .parse::<EventKinds>()
+        .unwrap_err()
+        .to_string(),
+      "unknown event kind `foo`",
+    );
+  }
+}
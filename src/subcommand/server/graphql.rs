@@ -0,0 +1,840 @@
+use {
+  super::*,
+  async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    Context, EmptyMutation, EmptySubscription, Object, OutputType,
+  },
+};
+
+type Result<T = (), E = async_graphql::Error> = std::result::Result<T, E>;
+
+pub(crate) type Schema = async_graphql::Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// A page of a list field. Cursors are offsets into the list.
+type Page<N> = Connection<usize, N>;
+
+const MAX_COMPLEXITY: usize = 5_000;
+
+const MAX_DEPTH: usize = 16;
+
+const MAX_PAGE_SIZE: usize = 100;
+
+pub(crate) fn schema(index: Arc<Index>, chain: Chain) -> Schema {
+  Schema::build(Query, EmptyMutation, EmptySubscription)
+    .data(index)
+    .data(chain)
+    .limit_complexity(MAX_COMPLEXITY)
+    .limit_depth(MAX_DEPTH)
+    .finish()
+}
+
+fn chain(ctx: &Context<'_>) -> Chain {
+  *ctx.data_unchecked::<Chain>()
+}
+
+/// Run `f` with the index on a thread where blocking is allowed, since index
+/// reads may block.
+async fn blocking<T: Send + 'static>(
+  ctx: &Context<'_>,
+  f: impl FnOnce(&Index) -> Result<T> + Send + 'static,
+) -> Result<T> {
+  let index = ctx.data_unchecked::<Arc<Index>>().clone();
+
+  task::spawn_blocking(move || f(&index)).await?
+}
+
+/// The number of items on a page of `first` items, at most `MAX_PAGE_SIZE`.
+fn page_size(first: Option<usize>) -> usize {
+  first.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+}
+
+/// The offset of the first item after cursor `after`.
+fn page_offset(after: Option<String>) -> Result<usize> {
+  Ok(match after {
+    Some(after) => usize::decode_cursor(&after)?.saturating_add(1),
+    None => 0,
+  })
+}
+
+/// Load the page of `keys` with at most `first` items after cursor `after`.
+fn paginate<K, N: OutputType>(
+  keys: impl IntoIterator<Item = K>,
+  first: Option<usize>,
+  after: Option<String>,
+  load: impl FnMut(K) -> Result<Option<N>>,
+) -> Result<Page<N>> {
+  let offset = page_offset(after)?;
+
+  let mut keys = keys.into_iter().skip(offset);
+
+  let page = keys.by_ref().take(page_size(first)).collect();
+
+  connection(offset, page, keys.next().is_some(), load)
+}
+
+/// Load the page of `keys` starting at `offset`. Keys which no longer refer
+/// to anything are skipped, without changing the cursors of later keys.
+fn connection<K, N: OutputType>(
+  offset: usize,
+  keys: Vec<K>,
+  more: bool,
+  mut load: impl FnMut(K) -> Result<Option<N>>,
+) -> Result<Page<N>> {
+  let mut connection = Connection::new(offset > 0, more);
+
+  for (i, key) in keys.into_iter().enumerate() {
+    if let Some(node) = load(key)? {
+      connection.edges.push(Edge::new(offset + i, node));
+    }
+  }
+
+  Ok(connection)
+}
+
+pub(crate) struct Query;
+
+#[Object]
+impl Query {
+  /// A block, by height or hash.
+  async fn block(&self, ctx: &Context<'_>, query: String) -> Result<Option<BlockNode>> {
+    let query = query.parse::<query::Block>()?;
+
+    blocking(ctx, move |index| {
+      let height = match query {
+        query::Block::Height(height) => height,
+        query::Block::Hash(hash) => match index.block_header_info(hash)? {
+          Some(info) => u32::try_from(info.height).unwrap(),
+          None => return Ok(None),
+        },
+      };
+
+      BlockNode::load(index, height)
+    })
+    .await
+  }
+
+  /// A transaction, by txid.
+  async fn transaction(&self, ctx: &Context<'_>, txid: String) -> Result<Option<TransactionNode>> {
+    let txid = txid.parse::<Txid>()?;
+
+    blocking(ctx, move |index| TransactionNode::load(index, txid)).await
+  }
+
+  /// An output, by outpoint.
+  async fn output(&self, ctx: &Context<'_>, outpoint: String) -> Result<Option<OutputNode>> {
+    let outpoint = outpoint.parse::<OutPoint>()?;
+
+    blocking(ctx, move |index| OutputNode::load(index, outpoint)).await
+  }
+
+  /// An inscription, by ID, number, or sat.
+  async fn inscription(&self, ctx: &Context<'_>, query: String) -> Result<Option<InscriptionNode>> {
+    let query = query.parse::<query::Inscription>()?;
+
+    blocking(ctx, move |index| InscriptionNode::load(index, query)).await
+  }
+
+  /// A sat, in any of the formats accepted by `/sat`.
+  async fn sat(&self, query: String) -> Result<SatNode> {
+    Ok(SatNode(query.parse()?))
+  }
+
+  /// A rune, by name, ID, or number.
+  async fn rune(&self, ctx: &Context<'_>, query: String) -> Result<Option<RuneNode>> {
+    let query = query.parse::<query::Rune>()?;
+
+    blocking(ctx, move |index| {
+      let rune = match query {
+        query::Rune::Spaced(spaced_rune) => Some(spaced_rune.rune),
+        query::Rune::Id(id) => index.get_rune_by_id(id)?,
+        query::Rune::Number(number) => {
+          index.get_rune_by_number(usize::try_from(number).unwrap())?
+        }
+      };
+
+      match rune {
+        Some(rune) => RuneNode::load(index, rune),
+        None => Ok(None),
+      }
+    })
+    .await
+  }
+
+  /// An address. Requires the address index.
+  async fn address(&self, ctx: &Context<'_>, address: String) -> Result<AddressNode> {
+    Ok(AddressNode(
+      address
+        .parse::<Address<NetworkUnchecked>>()?
+        .require_network(chain(ctx).network())?,
+    ))
+  }
+}
+
+pub(crate) struct BlockNode {
+  block: Block,
+  height: u32,
+}
+
+impl BlockNode {
+  fn load(index: &Index, height: u32) -> Result<Option<Self>> {
+    Ok(
+      index
+        .get_block_by_height(height)?
+        .map(|block| Self { block, height }),
+    )
+  }
+}
+
+#[Object(name = "Block")]
+impl BlockNode {
+  async fn hash(&self) -> String {
+    self.block.block_hash().to_string()
+  }
+
+  async fn height(&self) -> u32 {
+    self.height
+  }
+
+  async fn timestamp(&self) -> u32 {
+    self.block.header.time
+  }
+
+  async fn previous(&self, ctx: &Context<'_>) -> Result<Option<BlockNode>> {
+    match self.height.checked_sub(1) {
+      Some(height) => blocking(ctx, move |index| BlockNode::load(index, height)).await,
+      None => Ok(None),
+    }
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn transactions(
+    &self,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<TransactionNode>> {
+    paginate(&self.block.txdata, first, after, |tx| {
+      Ok(Some(TransactionNode(tx.clone())))
+    })
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<InscriptionNode>> {
+    let height = self.height;
+
+    blocking(ctx, move |index| {
+      paginate(
+        index.get_inscriptions_in_block(height)?,
+        first,
+        after,
+        |id| InscriptionNode::load(index, query::Inscription::Id(id)),
+      )
+    })
+    .await
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn runes(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<RuneNode>> {
+    let height = self.height;
+
+    blocking(ctx, move |index| {
+      paginate(
+        index.get_runes_in_block(height.into())?,
+        first,
+        after,
+        |spaced_rune| RuneNode::load(index, spaced_rune.rune),
+      )
+    })
+    .await
+  }
+}
+
+pub(crate) struct TransactionNode(Transaction);
+
+impl TransactionNode {
+  fn load(index: &Index, txid: Txid) -> Result<Option<Self>> {
+    Ok(index.get_transaction(txid)?.map(Self))
+  }
+}
+
+#[Object(name = "Transaction")]
+impl TransactionNode {
+  async fn txid(&self) -> String {
+    self.0.txid().to_string()
+  }
+
+  /// The outputs spent by this transaction.
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn inputs(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<OutputNode>> {
+    let outpoints = self
+      .0
+      .input
+      .iter()
+      .map(|input| input.previous_output)
+      .filter(|outpoint| !outpoint.is_null())
+      .collect::<Vec<OutPoint>>();
+
+    blocking(ctx, move |index| {
+      paginate(outpoints, first, after, |outpoint| {
+        OutputNode::load(index, outpoint)
+      })
+    })
+    .await
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn outputs(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<OutputNode>> {
+    let txid = self.0.txid();
+
+    let outputs = u32::try_from(self.0.output.len()).unwrap();
+
+    blocking(ctx, move |index| {
+      paginate(0..outputs, first, after, |vout| {
+        OutputNode::load(index, OutPoint { txid, vout })
+      })
+    })
+    .await
+  }
+
+  /// Inscriptions revealed by this transaction.
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<InscriptionNode>> {
+    let txid = self.0.txid();
+
+    blocking(ctx, move |index| {
+      paginate(
+        0..index.inscription_count(txid)?,
+        first,
+        after,
+        |index_in_transaction| {
+          InscriptionNode::load(
+            index,
+            query::Inscription::Id(InscriptionId {
+              txid,
+              index: index_in_transaction,
+            }),
+          )
+        },
+      )
+    })
+    .await
+  }
+
+  /// The rune etched by this transaction.
+  async fn etching(&self, ctx: &Context<'_>) -> Result<Option<RuneNode>> {
+    let txid = self.0.txid();
+
+    blocking(ctx, move |index| match index.get_etching(txid)? {
+      Some(spaced_rune) => RuneNode::load(index, spaced_rune.rune),
+      None => Ok(None),
+    })
+    .await
+  }
+}
+
+pub(crate) struct OutputNode {
+  outpoint: OutPoint,
+  output: api::Output,
+}
+
+impl OutputNode {
+  fn load(index: &Index, outpoint: OutPoint) -> Result<Option<Self>> {
+    Ok(
+      index
+        .get_output_info(outpoint)?
+        .map(|(output, _txout)| Self { outpoint, output }),
+    )
+  }
+}
+
+#[Object(name = "Output")]
+impl OutputNode {
+  async fn outpoint(&self) -> String {
+    self.outpoint.to_string()
+  }
+
+  async fn value(&self) -> u64 {
+    self.output.value
+  }
+
+  async fn script_pubkey(&self) -> String {
+    hex::encode(self.output.script_pubkey.as_bytes())
+  }
+
+  async fn spent(&self) -> bool {
+    self.output.spent
+  }
+
+  async fn address(&self) -> Option<AddressNode> {
+    self
+      .output
+      .address
+      .clone()
+      .map(|address| AddressNode(address.assume_checked()))
+  }
+
+  async fn transaction(&self, ctx: &Context<'_>) -> Result<Option<TransactionNode>> {
+    let txid = self.outpoint.txid;
+
+    blocking(ctx, move |index| TransactionNode::load(index, txid)).await
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<InscriptionNode>> {
+    let inscriptions = self.output.inscriptions.clone();
+
+    blocking(ctx, move |index| {
+      paginate(inscriptions, first, after, |id| {
+        InscriptionNode::load(index, query::Inscription::Id(id))
+      })
+    })
+    .await
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn runes(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<RuneBalance>> {
+    let balances = self
+      .output
+      .runes
+      .iter()
+      .map(|(spaced_rune, pile)| (spaced_rune.rune, pile.amount))
+      .collect::<Vec<(Rune, u128)>>();
+
+    blocking(ctx, move |index| {
+      paginate(balances, first, after, |(rune, amount)| {
+        RuneBalance::load(index, rune, amount)
+      })
+    })
+    .await
+  }
+}
+
+pub(crate) struct InscriptionNode(api::Inscription);
+
+impl InscriptionNode {
+  fn load(index: &Index, query: query::Inscription) -> Result<Option<Self>> {
+    Ok(
+      index
+        .inscription_info(query, None)?
+        .map(|(inscription, _txout, _inscription)| Self(inscription)),
+    )
+  }
+}
+
+#[Object(name = "Inscription")]
+impl InscriptionNode {
+  async fn id(&self) -> String {
+    self.0.id.to_string()
+  }
+
+  async fn number(&self) -> i32 {
+    self.0.number
+  }
+
+  async fn charms(&self) -> Vec<String> {
+    self.0.charms.iter().map(Charm::to_string).collect()
+  }
+
+  async fn content_length(&self) -> Option<u64> {
+    self
+      .0
+      .content_length
+      .map(|length| length.try_into().unwrap())
+  }
+
+  async fn content_type(&self) -> Option<&str> {
+    self.0.content_type.as_deref()
+  }
+
+  async fn fee(&self) -> u64 {
+    self.0.fee
+  }
+
+  async fn height(&self) -> u32 {
+    self.0.height
+  }
+
+  async fn timestamp(&self) -> i64 {
+    self.0.timestamp
+  }
+
+  async fn satpoint(&self) -> String {
+    self.0.satpoint.to_string()
+  }
+
+  async fn address(&self, ctx: &Context<'_>) -> Option<AddressNode> {
+    self
+      .0
+      .address
+      .as_deref()
+      .and_then(|address| address.parse::<Address<NetworkUnchecked>>().ok())
+      .and_then(|address| address.require_network(chain(ctx).network()).ok())
+      .map(AddressNode)
+  }
+
+  async fn block(&self, ctx: &Context<'_>) -> Result<Option<BlockNode>> {
+    let height = self.0.height;
+
+    blocking(ctx, move |index| BlockNode::load(index, height)).await
+  }
+
+  async fn output(&self, ctx: &Context<'_>) -> Result<Option<OutputNode>> {
+    let outpoint = self.0.satpoint.outpoint;
+
+    if outpoint == unbound_outpoint() {
+      return Ok(None);
+    }
+
+    blocking(ctx, move |index| OutputNode::load(index, outpoint)).await
+  }
+
+  async fn sat(&self) -> Option<SatNode> {
+    self.0.sat.map(SatNode)
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn parents(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<InscriptionNode>> {
+    let parents = self.0.parents.clone();
+
+    blocking(ctx, move |index| {
+      paginate(parents, first, after, |id| {
+        InscriptionNode::load(index, query::Inscription::Id(id))
+      })
+    })
+    .await
+  }
+
+  /// Children are read a page at a time, so inscriptions with many children
+  /// are not loaded in full.
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn children(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<InscriptionNode>> {
+    let id = self.0.id;
+
+    let offset = page_offset(after)?;
+
+    blocking(ctx, move |index| {
+      let Some(entry) = index.get_inscription_entry(id)? else {
+        return Ok(Connection::new(false, false));
+      };
+
+      let (children, more) = index.get_children_by_sequence_number_from(
+        entry.sequence_number,
+        offset,
+        page_size(first),
+      )?;
+
+      connection(offset, children, more, |id| {
+        InscriptionNode::load(index, query::Inscription::Id(id))
+      })
+    })
+    .await
+  }
+
+  async fn rune(&self, ctx: &Context<'_>) -> Result<Option<RuneNode>> {
+    match self.0.rune {
+      Some(spaced_rune) => {
+        blocking(ctx, move |index| RuneNode::load(index, spaced_rune.rune)).await
+      }
+      None => Ok(None),
+    }
+  }
+}
+
+pub(crate) struct SatNode(Sat);
+
+#[Object(name = "Sat")]
+impl SatNode {
+  async fn number(&self) -> u64 {
+    self.0.n()
+  }
+
+  async fn name(&self) -> String {
+    self.0.name()
+  }
+
+  async fn rarity(&self) -> String {
+    self.0.rarity().to_string()
+  }
+
+  async fn height(&self) -> u32 {
+    self.0.height().n()
+  }
+
+  /// Inscriptions on this sat. Requires the sat index.
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<InscriptionNode>> {
+    let sat = self.0;
+
+    blocking(ctx, move |index| {
+      if !index.has_sat_index() {
+        return Err("this server has no sat index".into());
+      }
+
+      paginate(index.get_inscription_ids_by_sat(sat)?, first, after, |id| {
+        InscriptionNode::load(index, query::Inscription::Id(id))
+      })
+    })
+    .await
+  }
+}
+
+pub(crate) struct RuneNode {
+  entry: RuneEntry,
+  id: RuneId,
+  parent: Option<InscriptionId>,
+}
+
+impl RuneNode {
+  fn load(index: &Index, rune: Rune) -> Result<Option<Self>> {
+    if !index.has_rune_index() {
+      return Err("this server has no rune index".into());
+    }
+
+    Ok(
+      index
+        .rune(rune)?
+        .map(|(id, entry, parent)| Self { entry, id, parent }),
+    )
+  }
+}
+
+#[Object(name = "Rune")]
+impl RuneNode {
+  async fn id(&self) -> String {
+    self.id.to_string()
+  }
+
+  async fn name(&self) -> String {
+    self.entry.spaced_rune.to_string()
+  }
+
+  async fn number(&self) -> u64 {
+    self.entry.number
+  }
+
+  async fn divisibility(&self) -> u8 {
+    self.entry.divisibility
+  }
+
+  async fn symbol(&self) -> Option<String> {
+    self.entry.symbol.map(String::from)
+  }
+
+  /// Amounts are strings, since they may not fit in a GraphQL integer.
+  async fn premine(&self) -> String {
+    self.entry.premine.to_string()
+  }
+
+  async fn burned(&self) -> String {
+    self.entry.burned.to_string()
+  }
+
+  async fn mints(&self) -> String {
+    self.entry.mints.to_string()
+  }
+
+  async fn supply(&self) -> String {
+    self.entry.supply().to_string()
+  }
+
+  async fn turbo(&self) -> bool {
+    self.entry.turbo
+  }
+
+  async fn timestamp(&self) -> u64 {
+    self.entry.timestamp
+  }
+
+  async fn block(&self, ctx: &Context<'_>) -> Result<Option<BlockNode>> {
+    let height = self.entry.block.try_into().unwrap();
+
+    blocking(ctx, move |index| BlockNode::load(index, height)).await
+  }
+
+  async fn etching(&self, ctx: &Context<'_>) -> Result<Option<TransactionNode>> {
+    let txid = self.entry.etching;
+
+    blocking(ctx, move |index| TransactionNode::load(index, txid)).await
+  }
+
+  async fn parent(&self, ctx: &Context<'_>) -> Result<Option<InscriptionNode>> {
+    match self.parent {
+      Some(parent) => {
+        blocking(ctx, move |index| {
+          InscriptionNode::load(index, query::Inscription::Id(parent))
+        })
+        .await
+      }
+      None => Ok(None),
+    }
+  }
+}
+
+pub(crate) struct RuneBalance {
+  amount: u128,
+  rune: RuneNode,
+}
+
+impl RuneBalance {
+  fn load(index: &Index, rune: Rune, amount: u128) -> Result<Option<Self>> {
+    Ok(RuneNode::load(index, rune)?.map(|rune| Self { amount, rune }))
+  }
+}
+
+#[Object]
+impl RuneBalance {
+  /// The balance in the rune's smallest unit, as a string.
+  async fn amount(&self) -> String {
+    self.amount.to_string()
+  }
+
+  async fn rune(&self) -> &RuneNode {
+    &self.rune
+  }
+}
+
+pub(crate) struct AddressNode(Address);
+
+impl AddressNode {
+  fn outpoints(index: &Index, address: &Address) -> Result<Vec<OutPoint>> {
+    if !index.has_address_index() {
+      return Err("this server has no address index".into());
+    }
+
+    let mut outputs = index.get_address_info(address)?;
+
+    outputs.sort();
+
+    Ok(outputs)
+  }
+}
+
+#[Object(name = "Address")]
+impl AddressNode {
+  async fn address(&self) -> String {
+    self.0.to_string()
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn outputs(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<OutputNode>> {
+    let address = self.0.clone();
+
+    blocking(ctx, move |index| {
+      paginate(
+        Self::outpoints(index, &address)?,
+        first,
+        after,
+        |outpoint| OutputNode::load(index, outpoint),
+      )
+    })
+    .await
+  }
+
+  async fn sat_balance(&self, ctx: &Context<'_>) -> Result<u64> {
+    let address = self.0.clone();
+
+    blocking(ctx, move |index| {
+      Ok(index.get_sat_balances_for_outputs(&Self::outpoints(index, &address)?)?)
+    })
+    .await
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<InscriptionNode>> {
+    let address = self.0.clone();
+
+    blocking(ctx, move |index| {
+      paginate(
+        index.get_inscriptions_for_outputs(&Self::outpoints(index, &address)?)?,
+        first,
+        after,
+        |id| InscriptionNode::load(index, query::Inscription::Id(id)),
+      )
+    })
+    .await
+  }
+
+  #[graphql(complexity = "page_size(first) * child_complexity")]
+  async fn runes(
+    &self,
+    ctx: &Context<'_>,
+    first: Option<usize>,
+    after: Option<String>,
+  ) -> Result<Page<RuneBalance>> {
+    let address = self.0.clone();
+
+    blocking(ctx, move |index| {
+      let mut balances = BTreeMap::<Rune, u128>::new();
+
+      for outpoint in Self::outpoints(index, &address)? {
+        for (spaced_rune, pile) in index.get_rune_balances_for_output(outpoint)? {
+          *balances.entry(spaced_rune.rune).or_default() += pile.amount;
+        }
+      }
+
+      paginate(balances, first, after, |(rune, amount)| {
+        RuneBalance::load(index, rune, amount)
+      })
+    })
+    .await
+  }
+}
@@ -6,6 +6,7 @@ pub(crate) struct ServerConfig {
   pub(crate) csp_origin: Option<String>,
   pub(crate) decompress: bool,
   pub(crate) domain: Option<String>,
+  pub(crate) events: Option<broadcast::Sender<Event>>,
   pub(crate) index_sats: bool,
   pub(crate) json_api_enabled: bool,
   pub(crate) proxy: Option<Url>,
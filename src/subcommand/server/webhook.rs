@@ -0,0 +1,113 @@
+use super::*;
+
+const BATCH_SIZE: usize = 100;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+pub(super) struct Webhook {
+  client: reqwest::blocking::Client,
+  polling_interval: Duration,
+  url: Url,
+}
+
+impl Webhook {
+  pub(super) fn spawn(url: Url, polling_interval: Duration, index: Arc<Index>) {
+    thread::spawn(move || {
+      // the blocking client may not be created on the server's async runtime
+      let client = match reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+      {
+        Ok(client) => client,
+        Err(err) => {
+          log::error!("Failed to create client for webhook {url}: {err}");
+          return;
+        }
+      };
+
+      Self {
+        client,
+        polling_interval,
+        url,
+      }
+      .run(&index);
+    });
+  }
+
+  fn run(self, index: &Index) {
+    while !SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      match self.deliver_pending(index) {
+        Ok(0) => thread::sleep(self.polling_interval),
+        Ok(_) => {}
+        Err(err) => {
+          log::warn!("Delivering events to webhook {}: {err}", self.url);
+          thread::sleep(self.polling_interval);
+        }
+      }
+    }
+  }
+
+  fn deliver_pending(&self, index: &Index) -> Result<usize> {
+    let events = index.get_webhook_events(&self.url, BATCH_SIZE)?;
+
+    let mut delivered = 0;
+
+    let result = events.iter().try_for_each(|(event_number, event)| {
+      self.deliver(*event_number, event)?;
+      delivered += 1;
+      Ok(())
+    });
+
+    index.acknowledge_webhook_events(&self.url, &events[..delivered])?;
+
+    result.map(|()| events.len())
+  }
+
+  fn deliver(&self, event_number: u64, event: &Event) -> Result {
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+      let result = self
+        .client
+        .post(self.url.clone())
+        .header("ord-event-number", event_number)
+        .json(event)
+        .send()
+        .and_then(|response| response.error_for_status());
+
+      let err = match result {
+        Ok(_) => return Ok(()),
+        Err(err) => err,
+      };
+
+      // client errors other than timeouts and rate limiting won't succeed on
+      // retry, so the event is logged and skipped
+      if let Some(status) = err.status() {
+        if status.is_client_error()
+          && status != reqwest::StatusCode::REQUEST_TIMEOUT
+          && status != reqwest::StatusCode::TOO_MANY_REQUESTS
+        {
+          log::error!(
+            "Webhook {} rejected event {event_number}, skipping: {err}",
+            self.url,
+          );
+          return Ok(());
+        }
+      }
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        bail!("shutting down before event {event_number} was delivered: {err}");
+      }
+
+      log::warn!(
+        "Failed to deliver event {event_number} to webhook {}, retrying in {}s: {err}",
+        self.url,
+        backoff.as_secs(),
+      );
+
+      thread::sleep(backoff);
+
+      backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+  }
+}
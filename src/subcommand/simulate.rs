@@ -0,0 +1,41 @@
+use {super::*, crate::wallet::Wallet, base64::Engine, bitcoin::psbt::Psbt};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Simulate {
+  #[arg(help = "Simulate <TRANSACTION>, a base64 PSBT or hex transaction.")]
+  transaction: String,
+}
+
+impl Simulate {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.update()?;
+
+    let (transaction, prevouts) = decode(&self.transaction)?;
+
+    Ok(Some(Box::new(index.simulate(&transaction, &prevouts)?)))
+  }
+}
+
+/// Decode a hex-encoded transaction or base64-encoded PSBT, returning the
+/// outputs spent by each input when known.
+pub(crate) fn decode(transaction: &str) -> Result<(Transaction, Vec<Option<TxOut>>)> {
+  let transaction = transaction.trim();
+
+  if let Ok(hex) = hex::decode(transaction) {
+    let transaction: Transaction = consensus::encode::deserialize(&hex)?;
+    let prevouts = vec![None; transaction.input.len()];
+    return Ok((transaction, prevouts));
+  }
+
+  let psbt = Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(transaction)
+      .context("transaction is neither hex nor a base64 PSBT")?,
+  )?;
+
+  let prevouts = Wallet::psbt_prevouts(&psbt);
+
+  Ok((psbt.unsigned_tx, prevouts))
+}
@@ -1,19 +1,26 @@
 use {
   super::*,
   crate::wallet::{batch, wallet_constructor::WalletConstructor, ListDescriptorsResult, Wallet},
+  base64::Engine,
+  bitcoin::psbt::Psbt,
   shared_args::SharedArgs,
 };
 
 pub mod balance;
 mod batch_command;
+pub mod broadcast;
 pub mod burn;
+pub mod burn_runes;
 pub mod cardinals;
+pub mod consolidate;
 pub mod create;
 pub mod dump;
 pub mod inscribe;
 pub mod inscriptions;
+pub mod inspect_psbt;
 mod label;
 pub mod mint;
+pub mod offer;
 pub mod outputs;
 pub mod pending;
 pub mod receive;
@@ -22,7 +29,10 @@ pub mod resume;
 pub mod runics;
 pub mod sats;
 pub mod send;
+pub mod send_many;
 mod shared_args;
+pub mod sign_psbt;
+pub mod split;
 pub mod transactions;
 
 #[derive(Debug, Parser)]
@@ -47,10 +57,16 @@ pub(crate) enum Subcommand {
   Balance,
   #[command(about = "Create inscriptions and runes")]
   Batch(batch_command::Batch),
-  #[command(about = "Burn an inscription")]
+  #[command(about = "Finalize and broadcast a signed PSBT or transaction")]
+  Broadcast(broadcast::Broadcast),
+  #[command(about = "Burn inscriptions")]
   Burn(burn::Burn),
+  #[command(about = "Burn runes")]
+  BurnRunes(burn_runes::BurnRunes),
   #[command(about = "List unspent cardinal outputs in wallet")]
   Cardinals,
+  #[command(about = "Merge cardinal outputs")]
+  Consolidate(consolidate::Consolidate),
   #[command(about = "Create new wallet")]
   Create(create::Create),
   #[command(about = "Dump wallet descriptors")]
@@ -59,10 +75,14 @@ pub(crate) enum Subcommand {
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
   Inscriptions,
+  #[command(about = "Show the inscriptions and runes moved by a PSBT")]
+  InspectPsbt(inspect_psbt::InspectPsbt),
   #[command(about = "Export output labels")]
   Label,
   #[command(about = "Mint a rune")]
   Mint(mint::Mint),
+  #[command(subcommand, about = "Offer commands")]
+  Offer(offer::Offer),
   #[command(about = "List all unspent outputs in wallet")]
   Outputs(outputs::Outputs),
   #[command(about = "List pending etchings")]
@@ -79,6 +99,12 @@ pub(crate) enum Subcommand {
   Sats(sats::Sats),
   #[command(about = "Send sat or inscription")]
   Send(send::Send),
+  #[command(about = "Send inscriptions, runes and LTC to many recipients in one transaction")]
+  SendMany(send_many::SendMany),
+  #[command(about = "Sign a PSBT with wallet keys, without connecting to Litecoin Core")]
+  SignPsbt(sign_psbt::SignPsbt),
+  #[command(about = "Trim excess postage and split rare sats into their own outputs")]
+  Split(split::Split),
   #[command(about = "See wallet transactions")]
   Transactions(transactions::Transactions),
 }
@@ -86,8 +112,11 @@ pub(crate) enum Subcommand {
 impl WalletCommand {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     match self.subcommand {
+      Subcommand::Broadcast(broadcast) => return broadcast.run(self.name, &settings),
       Subcommand::Create(create) => return create.run(self.name, &settings),
+      Subcommand::InspectPsbt(inspect_psbt) => return inspect_psbt.run(self.name, &settings),
       Subcommand::Restore(restore) => return restore.run(self.name, &settings),
+      Subcommand::SignPsbt(sign_psbt) => return sign_psbt.run(self.name, &settings),
       _ => {}
     };
 
@@ -109,13 +138,20 @@ impl WalletCommand {
       Subcommand::Balance => balance::run(wallet),
       Subcommand::Batch(batch) => batch.run(wallet),
       Subcommand::Burn(burn) => burn.run(wallet),
+      Subcommand::BurnRunes(burn_runes) => burn_runes.run(wallet),
       Subcommand::Cardinals => cardinals::run(wallet),
-      Subcommand::Create(_) | Subcommand::Restore(_) => unreachable!(),
+      Subcommand::Consolidate(consolidate) => consolidate.run(wallet),
+      Subcommand::Broadcast(_)
+      | Subcommand::Create(_)
+      | Subcommand::InspectPsbt(_)
+      | Subcommand::Restore(_)
+      | Subcommand::SignPsbt(_) => unreachable!(),
       Subcommand::Dump => dump::run(wallet),
       Subcommand::Inscribe(inscribe) => inscribe.run(wallet),
       Subcommand::Inscriptions => inscriptions::run(wallet),
       Subcommand::Label => label::run(wallet),
       Subcommand::Mint(mint) => mint.run(wallet),
+      Subcommand::Offer(offer) => offer.run(wallet),
       Subcommand::Outputs(outputs) => outputs.run(wallet),
       Subcommand::Pending(pending) => pending.run(wallet),
       Subcommand::Receive(receive) => receive.run(wallet),
@@ -123,7 +159,17 @@ impl WalletCommand {
       Subcommand::Runics => runics::run(wallet),
       Subcommand::Sats(sats) => sats.run(wallet),
       Subcommand::Send(send) => send.run(wallet),
+      Subcommand::SendMany(send_many) => send_many.run(wallet),
+      Subcommand::Split(split) => split.run(wallet),
       Subcommand::Transactions(transactions) => transactions.run(wallet),
     }
   }
 }
+
+fn decode_psbt(psbt: &str) -> Result<Psbt> {
+  Ok(Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt.trim())
+      .context("PSBT is not valid base64")?,
+  )?)
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Broadcast {
+  #[arg(help = "Broadcast <TRANSACTION>, a signed base64 PSBT or hex transaction.")]
+  transaction: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Output {
+  pub txid: Txid,
+}
+
+impl Broadcast {
+  pub(crate) fn run(self, name: String, settings: &Settings) -> SubcommandResult {
+    let database = Wallet::open_existing_database(&name, settings)?;
+
+    let keychain = Wallet::load_keychain(&database)?;
+
+    let bitcoin_client = settings.bitcoin_rpc_client(Some(name.clone()))?;
+
+    let transaction = self.transaction.trim();
+
+    let signed_tx = match hex::decode(transaction) {
+      Ok(signed_tx) => signed_tx,
+      Err(_) => {
+        let psbt = decode_psbt(transaction)?;
+
+        let result = bitcoin_client.finalize_psbt(
+          &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+          None,
+        )?;
+
+        if !result.complete {
+          bail!("PSBT is not fully signed");
+        }
+
+        result
+          .hex
+          .ok_or_else(|| anyhow!("unable to finalize PSBT"))?
+      }
+    };
+
+    let txid = bitcoin_client.send_raw_transaction(&signed_tx)?;
+
+    if keychain.is_some() {
+      Wallet::advance_change_index(&database, &consensus::encode::deserialize(&signed_tx)?)?;
+    }
+
+    Ok(Some(Box::new(Output { txid })))
+  }
+}
@@ -13,41 +13,75 @@ pub struct Burn {
     help = "Target <AMOUNT> postage with sent inscriptions. [default: 10000 sat]"
   )]
   postage: Option<Amount>,
-  inscription_id: InscriptionId,
+  #[arg(
+    value_name = "INSCRIPTION_ID",
+    required = true,
+    help = "Burn <INSCRIPTION_ID>s, sending them all to a single OP_RETURN output."
+  )]
+  inscription_ids: Vec<InscriptionId>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub outgoing: Outgoing,
+  pub outgoings: Vec<Outgoing>,
+  pub fee: u64,
 }
 
 impl Burn {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
-    let inscription_info = wallet
-      .inscription_info()
-      .get(&self.inscription_id)
-      .ok_or_else(|| anyhow!("inscription {} not found", self.inscription_id))?
-      .clone();
-
-    if inscription_info.value.unwrap() > MAX_BURN_SATS {
-      return Err(anyhow!(
-        "The amount of sats where the inscription is on exceeds {}",
-        MAX_BURN_SATS
-      ));
+    let mut satpoints = Vec::new();
+
+    for inscription_id in &self.inscription_ids {
+      let inscription_info = wallet
+        .inscription_info()
+        .get(inscription_id)
+        .ok_or_else(|| anyhow!("inscription {inscription_id} not found"))?
+        .clone();
+
+      if inscription_info.value.unwrap() > MAX_BURN_SATS {
+        return Err(anyhow!(
+          "The amount of sats where the inscription is on exceeds {}",
+          MAX_BURN_SATS
+        ));
+      }
+
+      satpoints.push(inscription_info.satpoint);
     }
 
     if self.postage.unwrap_or_default() > Amount::from_sat(MAX_BURN_SATS) {
       return Err(anyhow!("Target postage exceeds {}", MAX_BURN_SATS));
     }
 
-    let unsigned_transaction = Self::create_unsigned_burn_transaction(
-      &wallet,
-      inscription_info.satpoint,
-      self.postage,
-      self.fee_rate,
-    )?;
+    let unsigned_transaction = if let [satpoint] = satpoints.as_slice() {
+      Self::create_unsigned_burn_transaction(&wallet, *satpoint, self.postage, self.fee_rate)?
+    } else {
+      ensure!(
+        self.postage.is_none(),
+        "`--postage` cannot be used when burning more than one inscription",
+      );
+
+      Self::create_unsigned_burn_many_transaction(
+        &wallet,
+        &self.inscription_ids,
+        &satpoints,
+        self.fee_rate,
+      )?
+    };
 
     let (txid, psbt, fee) = wallet.sign_transaction(unsigned_transaction, self.dry_run)?;
 
-    Ok(Some(Box::new(send::Output {
+    Ok(Some(Box::new(Output {
       txid,
       psbt,
-      outgoing: Outgoing::InscriptionId(self.inscription_id),
+      outgoing: Outgoing::InscriptionId(self.inscription_ids[0]),
+      outgoings: self
+        .inscription_ids
+        .into_iter()
+        .map(Outgoing::InscriptionId)
+        .collect(),
       fee,
     })))
   }
@@ -93,4 +127,76 @@ impl Burn {
       .build_transaction()?,
     )
   }
+
+  /// Burn the whole of every output holding `satpoints`, so every inscription
+  /// on them must be one of `inscription_ids`.
+  fn create_unsigned_burn_many_transaction(
+    wallet: &Wallet,
+    inscription_ids: &[InscriptionId],
+    satpoints: &[SatPoint],
+    fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    let outpoints = satpoints
+      .iter()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    for (satpoint, inscriptions) in wallet.inscriptions() {
+      if !outpoints.contains(&satpoint.outpoint) {
+        continue;
+      }
+
+      for inscription_id in inscriptions {
+        ensure!(
+          inscription_ids.contains(inscription_id),
+          "cannot burn {} without also burning inscription {inscription_id} at {satpoint}",
+          satpoint.outpoint,
+        );
+      }
+    }
+
+    let mut value = 0;
+    for outpoint in &outpoints {
+      ensure!(
+        !runic_outputs.contains(outpoint),
+        "runic outpoints may not be burned"
+      );
+
+      value += wallet
+        .utxos()
+        .get(outpoint)
+        .ok_or_else(|| anyhow!("output {outpoint} not found in wallet"))?
+        .value;
+    }
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    // the burned outputs are spent first, so all of their sats, and the
+    // inscriptions on them, go to the OP_RETURN output
+    let unfunded_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: outpoints
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: vec![TxOut {
+        script_pubkey: script::Builder::new()
+          .push_opcode(opcodes::all::OP_RETURN)
+          .into_script(),
+        value,
+      }],
+    };
+
+    Ok(consensus::encode::deserialize(
+      &wallet.fund_raw_transaction(fee_rate, &unfunded_transaction)?,
+    )?)
+  }
 }
@@ -0,0 +1,148 @@
+use {super::*, crate::outgoing::Outgoing};
+
+#[derive(Debug, Parser)]
+pub struct BurnRunes {
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(help = "Burn <DECIMAL:RUNE>.")]
+  outgoing: Outgoing,
+}
+
+impl BurnRunes {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let Outgoing::Rune { decimal, rune } = self.outgoing else {
+      bail!("{} is not a rune amount", self.outgoing);
+    };
+
+    let unsigned_transaction =
+      Self::create_unsigned_transaction(&wallet, rune, decimal, self.fee_rate)?;
+
+    let (txid, psbt, fee) = wallet.sign_transaction(unsigned_transaction, self.dry_run)?;
+
+    Ok(Some(Box::new(burn::Output {
+      txid,
+      psbt,
+      outgoing: vec![self.outgoing],
+      fee,
+    })))
+  }
+
+  fn create_unsigned_transaction(
+    wallet: &Wallet,
+    spaced_rune: SpacedRune,
+    decimal: Decimal,
+    fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    ensure!(
+      wallet.has_rune_index(),
+      "burning runes with `ord wallet burn-runes` requires index created with `--index-runes` flag",
+    );
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    let (id, entry, _parent) = wallet
+      .get_rune(spaced_rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
+
+    let amount = decimal.to_integer(entry.divisibility)?;
+
+    ensure!(amount > 0, "cannot burn zero `{}`", entry.spaced_rune);
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<HashSet<OutPoint>>();
+
+    let mut inputs = Vec::new();
+    let mut input_balance = 0;
+    let mut other_runes = false;
+
+    for output in wallet.get_runic_outputs()? {
+      if input_balance >= amount {
+        break;
+      }
+
+      if inscribed_outputs.contains(&output) {
+        continue;
+      }
+
+      let balances = wallet.get_runes_balances_in_output(&output)?;
+
+      let Some(balance) = balances
+        .iter()
+        .find(|(rune, _)| rune.rune == spaced_rune.rune)
+        .map(|(_, pile)| pile.amount)
+      else {
+        continue;
+      };
+
+      input_balance += balance;
+      other_runes |= balances.len() > 1;
+      inputs.push(output);
+    }
+
+    ensure! {
+      input_balance >= amount,
+      "insufficient `{}` balance, only {} in wallet",
+      entry.spaced_rune,
+      Pile {
+        amount: input_balance,
+        divisibility: entry.divisibility,
+        symbol: entry.symbol,
+      },
+    }
+
+    // edicts to an OP_RETURN output burn runes, so the edict points at the
+    // runestone itself, and anything left over goes to the first non-OP_RETURN
+    // output
+    let runestone = Runestone {
+      edicts: vec![Edict {
+        amount,
+        id,
+        output: 0,
+      }],
+      ..default()
+    };
+
+    let mut output = vec![TxOut {
+      script_pubkey: runestone.encipher(),
+      value: 0,
+    }];
+
+    if input_balance > amount || other_runes {
+      output.push(TxOut {
+        script_pubkey: wallet.get_change_address()?.script_pubkey(),
+        value: TARGET_POSTAGE.to_sat(),
+      });
+    }
+
+    let unfunded_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::MAX,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output,
+    };
+
+    let unsigned_transaction: Transaction = consensus::encode::deserialize(
+      &wallet.fund_raw_transaction(fee_rate, &unfunded_transaction)?,
+    )?;
+
+    assert_eq!(
+      Runestone::decipher(&unsigned_transaction),
+      Some(Artifact::Runestone(runestone)),
+    );
+
+    Ok(unsigned_transaction)
+  }
+}
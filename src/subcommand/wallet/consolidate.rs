@@ -0,0 +1,91 @@
+use {super::*, crate::wallet::transaction_builder::TransactionBuilder};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Consolidate {
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    default_value = "500",
+    help = "Consolidate at most <MAX_INPUTS> outputs, smallest first."
+  )]
+  max_inputs: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub inputs: usize,
+  pub value: u64,
+  pub fee: u64,
+}
+
+impl Consolidate {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    // outputs holding inscriptions, runes or rare sats are never consolidated
+    let mut utxos = wallet
+      .get_common_cardinal_utxos()?
+      .into_iter()
+      .collect::<Vec<(OutPoint, TxOut)>>();
+
+    utxos.sort_by_key(|(_, txout)| txout.value);
+    utxos.truncate(self.max_inputs);
+
+    ensure!(
+      utxos.len() >= 2,
+      "wallet contains fewer than two cardinal outputs to consolidate",
+    );
+
+    let script_pubkey = wallet.get_change_address()?.script_pubkey();
+
+    let input_value = utxos.iter().map(|(_, txout)| txout.value).sum::<u64>();
+
+    let fee = self
+      .fee_rate
+      .fee(TransactionBuilder::estimate_vbytes_with(
+        utxos.len(),
+        vec![TxOut {
+          script_pubkey: script_pubkey.clone(),
+          value: 0,
+        }],
+      ))
+      .to_sat();
+
+    ensure!(
+      input_value >= fee + script_pubkey.dust_value().to_sat(),
+      "consolidating {} outputs worth {input_value} sats would cost {fee} sats in fees",
+      utxos.len(),
+    );
+
+    let unsigned_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: utxos
+        .iter()
+        .map(|(previous_output, _)| TxIn {
+          previous_output: *previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: vec![TxOut {
+        script_pubkey,
+        value: input_value - fee,
+      }],
+    };
+
+    let (txid, psbt, fee) = wallet.sign_transaction(unsigned_transaction, self.dry_run)?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      inputs: utxos.len(),
+      value: input_value - fee,
+      fee,
+    })))
+  }
+}
@@ -27,20 +27,21 @@ impl Create {
 
     let mnemonic = Mnemonic::from_entropy(&entropy)?;
 
-    Wallet::initialize(name, settings, mnemonic.to_seed(&self.passphrase))?;
-
-    let mut warn = String::new();
-    if !self.passphrase.is_empty() {
-      warn += "Passphrase is not used in wallet creation as descriptor wallets are not supported.";
-    }
-    warn += "Ord wallet created! The mnemonic above is not used as descriptor wallets are not \
-      supported in Litecoincore!!!! Please make a backup of the \
-      wallet.dat file and store it in a safe place.";
+    Wallet::initialize(
+      name,
+      settings,
+      mnemonic.to_seed(&self.passphrase),
+      &Wallet::read_password(true)?,
+      None,
+    )?;
 
     Ok(Some(Box::new(Output {
       mnemonic,
       passphrase: Some(self.passphrase),
-      message: warn,
+      message: "Ord wallet created! The mnemonic above, together with the passphrase if one \
+        was given, is the only way to restore the wallet's keys. Write it down and store it in \
+        a safe place. The wallet password only protects the keys stored in the wallet database."
+        .into(),
     })))
   }
 }
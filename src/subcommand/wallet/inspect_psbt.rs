@@ -0,0 +1,174 @@
+use {super::*, crate::wallet::annotation};
+
+#[derive(Debug, Parser)]
+pub(crate) struct InspectPsbt {
+  #[arg(help = "Inspect base64-encoded <PSBT>.")]
+  psbt: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Output {
+  pub inputs: Vec<Input>,
+  pub outputs: Vec<TxOutput>,
+  pub burned: BTreeMap<SpacedRune, Pile>,
+  pub fee: Option<u64>,
+  pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Input {
+  pub outpoint: OutPoint,
+  pub value: Option<u64>,
+  pub assets: Option<api::Assets>,
+  pub signed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TxOutput {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub value: u64,
+  pub change: bool,
+  pub assets: Option<api::Assets>,
+}
+
+impl InspectPsbt {
+  pub(crate) fn run(self, name: String, settings: &Settings) -> SubcommandResult {
+    let database = Wallet::open_existing_database(&name, settings)?;
+
+    let psbt = decode_psbt(&self.psbt)?;
+
+    let chain = settings.chain();
+
+    let prevouts = Wallet::psbt_prevouts(&psbt);
+
+    let annotations = annotation::inputs(&psbt)?;
+
+    let mut warnings = Vec::new();
+
+    let mut inputs = Vec::new();
+    for (i, ((txin, input), (prevout, assets))) in psbt
+      .unsigned_tx
+      .input
+      .iter()
+      .zip(&psbt.inputs)
+      .zip(prevouts.iter().zip(&annotations))
+      .enumerate()
+    {
+      if assets.is_none() {
+        warnings.push(format!(
+          "input {i} spending {} is not annotated, inscriptions and runes on it are unknown",
+          txin.previous_output
+        ));
+      }
+
+      inputs.push(Input {
+        outpoint: txin.previous_output,
+        value: prevout.as_ref().map(|txout| txout.value),
+        assets: assets.clone(),
+        signed: input.final_script_witness.is_some() || input.final_script_sig.is_some(),
+      });
+    }
+
+    let created = annotation::created(&psbt)?;
+
+    // recompute where assets end up rather than trusting output annotations,
+    // which are only used when some inputs are not annotated
+    let transfer = prevouts
+      .iter()
+      .zip(&annotations)
+      .map(|(prevout, assets)| Some((prevout.as_ref()?.value, assets.clone()?)))
+      .collect::<Option<Vec<(u64, api::Assets)>>>()
+      .map(|inputs| Transfer::simulate(&psbt.unsigned_tx, &inputs, &created));
+
+    let output_assets = match &transfer {
+      Some(transfer) => transfer.outputs.iter().cloned().map(Some).collect(),
+      None => annotation::outputs(&psbt)?,
+    };
+
+    let key_paths = Wallet::script_key_paths(
+      &database,
+      psbt
+        .unsigned_tx
+        .output
+        .iter()
+        .map(|txout| Some(txout.script_pubkey.as_script())),
+    )?;
+
+    let mut outputs = Vec::new();
+    for (vout, ((txout, assets), key_path)) in psbt
+      .unsigned_tx
+      .output
+      .iter()
+      .zip(output_assets)
+      .zip(key_paths)
+      .enumerate()
+    {
+      let change = matches!(key_path, Some((true, _)));
+
+      if let Some(assets) = assets.as_ref().filter(|_| change) {
+        for inscription_id in assets.inscriptions.keys() {
+          warnings.push(format!(
+            "inscription {inscription_id} would be sent to change output {vout}"
+          ));
+        }
+
+        if Runestone::decipher(&psbt.unsigned_tx).is_none() {
+          for (rune, balance) in &assets.runes {
+            warnings.push(format!(
+              "{} {rune} would be sent to change output {vout} without a runestone",
+              balance.pile()
+            ));
+          }
+        }
+      }
+
+      outputs.push(TxOutput {
+        address: chain
+          .address_from_script(&txout.script_pubkey)
+          .ok()
+          .map(|address| address.as_unchecked().clone()),
+        value: txout.value,
+        change,
+        assets,
+      });
+    }
+
+    let mut burned = BTreeMap::new();
+
+    if let Some(transfer) = &transfer {
+      for inscription_id in transfer.fee.keys() {
+        warnings.push(format!(
+          "inscription {inscription_id} would be lost to fees"
+        ));
+      }
+
+      for (rune, balance) in &transfer.burned {
+        warnings.push(format!("{} {rune} would be burned", balance.pile()));
+        burned.insert(*rune, balance.pile());
+      }
+    }
+
+    let fee = prevouts
+      .iter()
+      .map(|prevout| prevout.as_ref().map(|txout| txout.value))
+      .sum::<Option<u64>>()
+      .and_then(|input_value| {
+        input_value.checked_sub(
+          psbt
+            .unsigned_tx
+            .output
+            .iter()
+            .map(|txout| txout.value)
+            .sum(),
+        )
+      });
+
+    Ok(Some(Box::new(Output {
+      inputs,
+      outputs,
+      burned,
+      fee,
+      warnings,
+    })))
+  }
+}
@@ -2,6 +2,8 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Mint {
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
   #[clap(long, help = "Use <FEE_RATE> sats/vbyte for mint transaction.")]
   fee_rate: FeeRate,
   #[clap(long, help = "Mint <RUNE>. May contain `.` or `•`as spacers.")]
@@ -20,6 +22,7 @@ pub struct Output {
   pub rune: SpacedRune,
   pub pile: Pile,
   pub mint: Txid,
+  pub psbt: Option<String>,
 }
 
 impl Mint {
@@ -89,11 +92,27 @@ impl Mint {
 
     wallet.lock_non_cardinal_outputs()?;
 
-    let unsigned_transaction =
-      fund_raw_transaction(bitcoin_client, self.fee_rate, &unfunded_transaction)?;
+    let unsigned_transaction: Transaction = consensus::encode::deserialize(
+      &wallet.fund_raw_transaction(self.fee_rate, &unfunded_transaction)?,
+    )?;
 
-    let signed_transaction = bitcoin_client
-      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+    let pile = Pile {
+      amount,
+      divisibility: rune_entry.divisibility,
+      symbol: rune_entry.symbol,
+    };
+
+    if self.dry_run {
+      return Ok(Some(Box::new(Output {
+        rune: self.rune,
+        pile,
+        mint: unsigned_transaction.txid(),
+        psbt: Some(wallet.export_psbt(&unsigned_transaction, &BTreeMap::new())?),
+      })));
+    }
+
+    let signed_transaction = wallet
+      .sign_raw_transaction(&unsigned_transaction, None)?
       .hex;
 
     let signed_transaction = consensus::encode::deserialize(&signed_transaction)?;
@@ -103,16 +122,13 @@ impl Mint {
       Some(Artifact::Runestone(runestone)),
     );
 
-    let transaction = bitcoin_client.send_raw_transaction(&signed_transaction)?;
+    let transaction = wallet.send_raw_transaction(&signed_transaction)?;
 
     Ok(Some(Box::new(Output {
       rune: self.rune,
-      pile: Pile {
-        amount,
-        divisibility: rune_entry.divisibility,
-        symbol: rune_entry.symbol,
-      },
+      pile,
       mint: transaction,
+      psbt: None,
     })))
   }
 }
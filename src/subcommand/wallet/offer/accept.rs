@@ -0,0 +1,208 @@
+use {super::*, crate::outgoing::Outgoing};
+
+const SIGHASH_SINGLE_ANYONECANPAY: u8 = 0x83;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Accept {
+  #[arg(long, help = "Pay <AMOUNT> for the offered inscription or runes.")]
+  amount: Amount,
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Accept offer <PSBT>.")]
+  psbt: String,
+  #[arg(help = "Buy <OUTGOING>, an inscription ID or <DECIMAL:RUNE>.")]
+  outgoing: Outgoing,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: Option<String>,
+  pub outgoing: Outgoing,
+  pub fee: u64,
+}
+
+impl Accept {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let offer = decode_psbt(&self.psbt)?;
+
+    ensure!(
+      offer.inputs.len() == 1 && offer.outputs.len() == 1,
+      "offer must have exactly one input and one output",
+    );
+
+    let seller_txin = offer.unsigned_tx.input[0].clone();
+    let seller_payment = offer.unsigned_tx.output[0].clone();
+    let seller_input = offer.inputs[0].clone();
+
+    let Some(witness) = &seller_input.final_script_witness else {
+      bail!("offer is not signed");
+    };
+
+    // the seller's signature must commit only to their own input and output,
+    // since we will be adding ours
+    ensure!(
+      witness
+        .nth(0)
+        .map(|signature| signature.len() != 64
+          && signature.last() == Some(&SIGHASH_SINGLE_ANYONECANPAY))
+        .unwrap_or_default(),
+      "offer is not signed with SIGHASH_SINGLE|ANYONECANPAY",
+    );
+
+    ensure!(
+      seller_payment.value == self.amount.to_sat(),
+      "offer asks for {}, not {}",
+      Amount::from_sat(seller_payment.value),
+      self.amount,
+    );
+
+    let (seller_prevout, assets) = wallet.get_output_assets(seller_txin.previous_output)?;
+
+    match &self.outgoing {
+      Outgoing::InscriptionId(id) => ensure!(
+        assets.inscriptions.contains_key(id),
+        "offer does not sell inscription {id}",
+      ),
+      Outgoing::Rune { decimal, rune } => {
+        let sufficient = match assets.runes.get(rune) {
+          Some(balance) => balance.amount >= decimal.to_integer(balance.divisibility)?,
+          None => false,
+        };
+
+        ensure!(sufficient, "offer does not sell {decimal}:{rune}");
+      }
+      _ => bail!("offers can only sell inscriptions or runes"),
+    }
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<HashSet<OutPoint>>();
+
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    // The seller's input must be at the same index as their payment, so a
+    // small cardinal output of ours goes first, and receives the sats of the
+    // seller's input, which follow it.
+    let (padding, padding_txout) = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _)| {
+        **outpoint != seller_txin.previous_output
+          && !inscribed_outputs.contains(outpoint)
+          && !runic_outputs.contains(outpoint)
+          && !wallet.locked_utxos().contains_key(outpoint)
+      })
+      .min_by_key(|(_, txout)| txout.value)
+      .ok_or_else(|| anyhow!("wallet contains no cardinal outputs"))?;
+
+    let receive_address = wallet
+      .get_receive_address()?
+      .require_network(wallet.chain().network())?;
+
+    // the seller's input is not included when funding, so the fee for it is
+    // funded along with the seller's payment
+    let seller_input_fee = {
+      let mut transaction = Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: Vec::new(),
+        output: Vec::new(),
+      };
+
+      let vsize = transaction.vsize();
+
+      transaction.input.push(TxIn {
+        witness: witness.clone(),
+        ..seller_txin.clone()
+      });
+
+      self.fee_rate.fee(transaction.vsize() - vsize).to_sat()
+    };
+
+    let unfunded_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: *padding,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![
+        TxOut {
+          value: padding_txout.value,
+          script_pubkey: receive_address.script_pubkey(),
+        },
+        TxOut {
+          value: seller_payment.value + seller_input_fee,
+          script_pubkey: seller_payment.script_pubkey.clone(),
+        },
+      ],
+    };
+
+    let mut transaction: Transaction = consensus::encode::deserialize(
+      &wallet.fund_raw_transaction(self.fee_rate, &unfunded_transaction)?,
+    )?;
+
+    transaction.output[1].value = seller_payment.value;
+
+    transaction.input.insert(1, seller_txin.clone());
+
+    transaction.output[0].value += seller_prevout.value;
+
+    let fee = transaction
+      .input
+      .iter()
+      .map(|txin| {
+        if txin.previous_output == seller_txin.previous_output {
+          Ok(seller_prevout.value)
+        } else {
+          wallet
+            .utxos()
+            .get(&txin.previous_output)
+            .map(|txout| txout.value)
+            .ok_or_else(|| anyhow!("input {} not found in utxos", txin.previous_output))
+        }
+      })
+      .sum::<Result<u64>>()?
+      - transaction
+        .output
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<u64>();
+
+    let mut psbt = Psbt::from_unsigned_tx(transaction.clone())?;
+
+    psbt.inputs[1] = seller_input;
+
+    if self.dry_run {
+      wallet.annotate_psbt(&mut psbt, &BTreeMap::new())?;
+
+      return Ok(Some(Box::new(Output {
+        txid: transaction.txid(),
+        psbt: Some(base64::engine::general_purpose::STANDARD.encode(psbt.serialize())),
+        outgoing: self.outgoing,
+        fee,
+      })));
+    }
+
+    let txid = wallet.complete_psbt(
+      psbt,
+      &[(seller_txin.previous_output, seller_prevout)].into(),
+    )?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt: None,
+      outgoing: self.outgoing,
+      fee,
+    })))
+  }
+}
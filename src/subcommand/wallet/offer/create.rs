@@ -0,0 +1,131 @@
+use {super::*, crate::outgoing::Outgoing};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Create {
+  #[arg(long, help = "Sell for <AMOUNT>.")]
+  amount: Amount,
+  #[arg(help = "Sell <OUTGOING>, an inscription ID or <DECIMAL:RUNE>.")]
+  outgoing: Outgoing,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub psbt: String,
+  pub outgoing: Outgoing,
+  pub outpoint: OutPoint,
+  pub amount: u64,
+}
+
+impl Create {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .iter()
+      .flat_map(|(satpoint, inscription_ids)| {
+        inscription_ids
+          .iter()
+          .map(|inscription_id| (satpoint.outpoint, *inscription_id))
+      })
+      .collect::<Vec<(OutPoint, InscriptionId)>>();
+
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    let outpoint = match &self.outgoing {
+      Outgoing::InscriptionId(id) => {
+        let outpoint = wallet
+          .inscription_info()
+          .get(id)
+          .ok_or_else(|| anyhow!("inscription {id} not found"))?
+          .satpoint
+          .outpoint;
+
+        if let Some((_, other)) = inscribed_outputs
+          .iter()
+          .find(|(output, inscription_id)| *output == outpoint && inscription_id != id)
+        {
+          bail!("cannot sell {id} without also selling inscription {other} in output {outpoint}");
+        }
+
+        if runic_outputs.contains(&outpoint) {
+          bail!("cannot sell {id} without also selling runes in output {outpoint}");
+        }
+
+        outpoint
+      }
+      Outgoing::Rune { decimal, rune } => {
+        ensure!(
+          wallet.has_rune_index(),
+          "selling runes with `ord wallet offer create` requires index created with `--index-runes` flag",
+        );
+
+        let (_, entry, _) = wallet
+          .get_rune(rune.rune)?
+          .with_context(|| format!("rune `{}` has not been etched", rune.rune))?;
+
+        let amount = decimal.to_integer(entry.divisibility)?;
+
+        let mut outpoint = None;
+
+        for output in &runic_outputs {
+          if inscribed_outputs
+            .iter()
+            .any(|(outpoint, _)| outpoint == output)
+          {
+            continue;
+          }
+
+          let balances = wallet.get_runes_balances_in_output(output)?;
+
+          if balances.len() == 1
+            && balances
+              .get(rune)
+              .map(|pile| pile.amount == amount)
+              .unwrap_or_default()
+          {
+            outpoint = Some(*output);
+            break;
+          }
+        }
+
+        outpoint.ok_or_else(|| {
+          anyhow!(
+            "no output holds exactly {decimal}:{rune} and nothing else, send that amount to your own wallet with `ord wallet send` first"
+          )
+        })?
+      }
+      _ => bail!("offers can only sell inscriptions or runes"),
+    };
+
+    let payment_address = wallet
+      .get_receive_address()?
+      .require_network(wallet.chain().network())?;
+
+    ensure!(
+      payment_address.script_pubkey().dust_value() <= self.amount,
+      "amount below dust limit of {}sat",
+      payment_address.script_pubkey().dust_value().to_sat()
+    );
+
+    let psbt = wallet.sign_offer(&Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: self.amount.to_sat(),
+        script_pubkey: payment_address.script_pubkey(),
+      }],
+    })?;
+
+    Ok(Some(Box::new(Output {
+      psbt: base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+      outgoing: self.outgoing,
+      outpoint,
+      amount: self.amount.to_sat(),
+    })))
+  }
+}
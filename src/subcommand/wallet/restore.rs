@@ -6,6 +6,12 @@ pub(crate) struct Restore {
   from: Source,
   #[arg(long, help = "Use <PASSPHRASE> when deriving wallet")]
   pub(crate) passphrase: Option<String>,
+  #[arg(
+    long,
+    default_value_t = crate::wallet::RESTORE_LOOKAHEAD,
+    help = "Watch the first <LOOKAHEAD> receive and change addresses for outputs sent to them."
+  )]
+  pub(crate) lookahead: u64,
 }
 
 #[derive(clap::ValueEnum, Debug, Clone)]
@@ -15,45 +21,39 @@ enum Source {
 }
 
 impl Restore {
-  pub(crate) fn run(self, _name: String, _settings: &Settings) -> SubcommandResult {
-    bail!(
-      "Descriptor wallets are not supported in Litecoincore 21.2.1, copy your wallet.dat into \
-      your Litecoincore data directory."
+  pub(crate) fn run(self, name: String, settings: &Settings) -> SubcommandResult {
+    ensure!(
+      !settings
+        .bitcoin_rpc_client(None)?
+        .list_wallet_dir()?
+        .iter()
+        .any(|wallet_name| wallet_name == &name),
+      "wallet `{}` already exists",
+      name
     );
 
-    // ensure!(
-    //   !settings
-    //     .bitcoin_rpc_client(None)?
-    //     .list_wallet_dir()?
-    //     .iter()
-    //     .any(|wallet_name| wallet_name == &name),
-    //   "wallet `{}` already exists",
-    //   name
-    // );
-    //
-    // let mut buffer = String::new();
-    //
-    // match self.from {
-    //   Source::Descriptor => {
-    //     io::stdin().read_to_string(&mut buffer)?;
-    //     ensure!(
-    //       self.passphrase.is_none(),
-    //       "descriptor does not take a passphrase"
-    //     );
-    //     let wallet_descriptors: ListDescriptorsResult = serde_json::from_str(&buffer)?;
-    //     Wallet::initialize_from_descriptors(name, settings, wallet_descriptors.descriptors)?;
-    //   }
-    //   Source::Mnemonic => {
-    //     io::stdin().read_line(&mut buffer)?;
-    //     let mnemonic = Mnemonic::from_str(&buffer)?;
-    //     Wallet::initialize(
-    //       name,
-    //       settings,
-    //       mnemonic.to_seed(self.passphrase.unwrap_or_default()),
-    //     )?;
-    //   }
-    // }
-    //
-    // Ok(None)
+    let mut buffer = String::new();
+
+    match self.from {
+      Source::Descriptor => {
+        bail!(
+          "Descriptor wallets are not supported in Litecoincore 21.2.1, restore from a mnemonic \
+          or copy your wallet.dat into your Litecoincore data directory."
+        );
+      }
+      Source::Mnemonic => {
+        io::stdin().read_line(&mut buffer)?;
+        let mnemonic = Mnemonic::from_str(&buffer)?;
+        Wallet::initialize(
+          name,
+          settings,
+          mnemonic.to_seed(self.passphrase.unwrap_or_default()),
+          &Wallet::read_password(true)?,
+          Some(self.lookahead),
+        )?;
+      }
+    }
+
+    Ok(None)
   }
 }
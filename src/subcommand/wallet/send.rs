@@ -11,10 +11,28 @@ pub(crate) struct Send {
     help = "Target <AMOUNT> postage with sent inscriptions. [default: 10000 sat]"
   )]
   pub(crate) postage: Option<Amount>,
-  address: Address<NetworkUnchecked>,
+  address: Destination,
   outgoing: Outgoing,
 }
 
+#[derive(Clone, Debug)]
+enum Destination {
+  Address(Address<NetworkUnchecked>),
+  Mweb(String),
+}
+
+impl FromStr for Destination {
+  type Err = bitcoin::address::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if mweb::is_mweb_address(s) {
+      Ok(Self::Mweb(s.into()))
+    } else {
+      Ok(Self::Address(s.parse()?))
+    }
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Output {
   pub txid: Txid,
@@ -25,10 +43,31 @@ pub struct Output {
 
 impl Send {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
-    let address = self
-      .address
-      .clone()
-      .require_network(wallet.chain().network())?;
+    let address = match &self.address {
+      Destination::Address(address) => address.clone().require_network(wallet.chain().network())?,
+      Destination::Mweb(address) => {
+        let Outgoing::Amount(amount) = self.outgoing else {
+          bail!(
+            "refusing to send {} to MWEB address {address}: ordinals and runes cannot be tracked inside MWEB",
+            self.outgoing
+          );
+        };
+
+        ensure!(
+          !self.dry_run,
+          "sending to MWEB address {address} does not support --dry-run"
+        );
+
+        let (txid, psbt, fee) = Self::send_amount_to_mweb(&wallet, address, amount, self.fee_rate)?;
+
+        return Ok(Some(Box::new(Output {
+          txid,
+          psbt,
+          outgoing: self.outgoing,
+          fee,
+        })));
+      }
+    };
 
     let unsigned_transaction = match self.outgoing {
       Outgoing::Amount(amount) => {
@@ -100,15 +139,91 @@ impl Send {
       }],
     };
 
-    let unsigned_transaction = consensus::encode::deserialize(&fund_raw_transaction(
-      wallet.bitcoin_client(),
-      fee_rate,
-      &unfunded_transaction,
-    )?)?;
+    let unsigned_transaction = consensus::encode::deserialize(
+      &wallet.fund_raw_transaction(fee_rate, &unfunded_transaction)?,
+    )?;
 
     Ok(unsigned_transaction)
   }
 
+  /// MWEB addresses have no script pubkey, so transactions pegging into MWEB
+  /// are built, signed, and broadcast by Litecoin Core, which requires a Core
+  /// wallet holding private keys. Inscribed and runic outputs are locked first
+  /// so that Core does not spend them.
+  fn send_amount_to_mweb(
+    wallet: &Wallet,
+    address: &str,
+    amount: Amount,
+    fee_rate: FeeRate,
+  ) -> Result<(Txid, String, u64)> {
+    let wallet_info = wallet.bitcoin_client().get_wallet_info()?;
+
+    if !wallet_info.private_keys_enabled {
+      bail!(
+        "sending to MWEB address {address} requires Litecoin Core to sign, but Core wallet `{}` is watch-only",
+        wallet_info.wallet_name
+      );
+    }
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    let txid = wallet.bitcoin_client().call::<Txid>(
+      "sendtoaddress",
+      &[
+        address.into(),
+        amount.to_btc().into(),
+        serde_json::Value::Null,
+        serde_json::Value::Null,
+        serde_json::Value::Null,
+        serde_json::Value::Null,
+        serde_json::Value::Null,
+        serde_json::Value::Null,
+        serde_json::Value::Null,
+        fee_rate.n().into(),
+      ],
+    )?;
+
+    let transaction = wallet.bitcoin_client().get_raw_transaction(&txid, None)?;
+
+    let mut fee = 0;
+    for txin in &transaction.input {
+      let Some(txout) = wallet.utxos().get(&txin.previous_output) else {
+        bail!("input {} not found in utxos", txin.previous_output);
+      };
+      fee += txout.value;
+    }
+
+    for txout in &transaction.output {
+      fee = fee
+        .checked_sub(txout.value)
+        .ok_or_else(|| anyhow!("output value exceeds input value"))?;
+    }
+
+    let mut unsigned_transaction = transaction.clone();
+    for txin in &mut unsigned_transaction.input {
+      txin.script_sig = ScriptBuf::new();
+      txin.witness = Witness::new();
+    }
+
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_transaction)?;
+
+    for (input, txin) in psbt.inputs.iter_mut().zip(transaction.input) {
+      if !txin.script_sig.is_empty() {
+        input.final_script_sig = Some(txin.script_sig);
+      }
+
+      if !txin.witness.is_empty() {
+        input.final_script_witness = Some(txin.witness);
+      }
+    }
+
+    Ok((
+      txid,
+      base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+      fee,
+    ))
+  }
+
   fn create_unsigned_send_satpoint_transaction(
     wallet: &Wallet,
     destination: Address,
@@ -285,8 +400,7 @@ impl Send {
       },
     };
 
-    let unsigned_transaction =
-      fund_raw_transaction(wallet.bitcoin_client(), fee_rate, &unfunded_transaction)?;
+    let unsigned_transaction = wallet.fund_raw_transaction(fee_rate, &unfunded_transaction)?;
 
     let unsigned_transaction = consensus::encode::deserialize(&unsigned_transaction)?;
 
@@ -0,0 +1,393 @@
+use {
+  super::*,
+  crate::{outgoing::Outgoing, wallet::transaction_builder::TransactionBuilder},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct SendMany {
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  pub(crate) dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    alias = "nolimit",
+    help = "Do not check that the runestone is equal to or below the maximum OP_RETURN size of 82 bytes. Larger runestones are currently nonstandard and will not be relayed by Litecoin Core in its default configuration."
+  )]
+  no_limit: bool,
+  #[arg(
+    long,
+    help = "Target <AMOUNT> postage with sent inscriptions and runes. [default: 10000 sat]"
+  )]
+  postage: Option<Amount>,
+  #[arg(
+    help = "Send to recipients listed in <FILE>, a YAML list of `address` and `outgoing` pairs, or, if it ends in `.csv`, lines of `ADDRESS,OUTGOING`."
+  )]
+  file: PathBuf,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Recipient {
+  pub address: Address<NetworkUnchecked>,
+  pub outgoing: Outgoing,
+}
+
+impl Recipient {
+  pub(crate) fn load(path: &Path) -> Result<Vec<Self>> {
+    let recipients: Vec<Self> = if path.extension() == Some("csv".as_ref()) {
+      fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .filter(|(i, line)| !(*i == 0 && line.eq_ignore_ascii_case("address,outgoing")))
+        .map(|(i, line)| {
+          let (address, outgoing) = line
+            .split_once(',')
+            .ok_or_else(|| anyhow!("line {} must be of the form `ADDRESS,OUTGOING`", i + 1))?;
+
+          Ok(Self {
+            address: address
+              .trim()
+              .parse()
+              .with_context(|| format!("invalid address on line {}", i + 1))?,
+            outgoing: outgoing
+              .trim()
+              .parse()
+              .with_context(|| format!("invalid outgoing on line {}", i + 1))?,
+          })
+        })
+        .collect::<Result<_>>()?
+    } else {
+      serde_yaml::from_reader(fs::File::open(path)?)?
+    };
+
+    ensure!(
+      !recipients.is_empty(),
+      "recipients file must contain at least one recipient",
+    );
+
+    Ok(recipients)
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub recipients: usize,
+  pub fee: u64,
+}
+
+impl SendMany {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let recipients = Recipient::load(&self.file)?;
+
+    let unsigned_transaction = self.create_unsigned_transaction(&wallet, &recipients)?;
+
+    let (txid, psbt, fee) = wallet.sign_transaction(unsigned_transaction, self.dry_run)?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      recipients: recipients.len(),
+      fee,
+    })))
+  }
+
+  fn create_unsigned_transaction(
+    &self,
+    wallet: &Wallet,
+    recipients: &[Recipient],
+  ) -> Result<Transaction> {
+    wallet.lock_non_cardinal_outputs()?;
+
+    let network = wallet.chain().network();
+
+    let recipients = recipients
+      .iter()
+      .map(|recipient| {
+        Ok((
+          recipient.address.clone().require_network(network)?,
+          recipient.outgoing.clone(),
+        ))
+      })
+      .collect::<Result<Vec<(Address, Outgoing)>>>()?;
+
+    let inscription_info = wallet.inscription_info();
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+
+    // Each inscription is sent from its own input, whose sats are divided
+    // between the recipient and change, so that inscriptions cannot end up in
+    // each other's outputs, or in outputs which follow.
+    for (address, outgoing) in &recipients {
+      let Outgoing::InscriptionId(id) = outgoing else {
+        continue;
+      };
+
+      let satpoint = inscription_info
+        .get(id)
+        .ok_or_else(|| anyhow!("inscription {id} not found"))?
+        .satpoint;
+
+      ensure!(
+        wallet
+          .inscriptions()
+          .range(
+            SatPoint {
+              outpoint: satpoint.outpoint,
+              offset: 0,
+            }..=SatPoint {
+              outpoint: satpoint.outpoint,
+              offset: u64::MAX,
+            }
+          )
+          .flat_map(|(_, inscription_ids)| inscription_ids)
+          .count()
+          == 1,
+        "inscription {id} is in output {}, which contains other inscriptions",
+        satpoint.outpoint,
+      );
+
+      ensure!(
+        !runic_outputs.contains(&satpoint.outpoint),
+        "inscription {id} is in output {}, which contains runes",
+        satpoint.outpoint,
+      );
+
+      let value = wallet
+        .utxos()
+        .get(&satpoint.outpoint)
+        .ok_or_else(|| anyhow!("output {} not found in wallet", satpoint.outpoint))?
+        .value;
+
+      inputs.push(satpoint.outpoint);
+
+      let change = wallet.get_change_address()?.script_pubkey();
+
+      let mut start = 0;
+      if satpoint.offset >= change.dust_value().to_sat() {
+        outputs.push(TxOut {
+          script_pubkey: change.clone(),
+          value: satpoint.offset,
+        });
+        start = satpoint.offset;
+      }
+
+      let remaining = value - start;
+
+      let postage = match self.postage {
+        Some(postage) => postage.to_sat(),
+        None if remaining > TransactionBuilder::MAX_POSTAGE.to_sat() => TARGET_POSTAGE.to_sat(),
+        None => remaining,
+      };
+
+      let script_pubkey = address.script_pubkey();
+
+      ensure!(
+        postage > satpoint.offset - start
+          && postage <= remaining
+          && postage >= script_pubkey.dust_value().to_sat(),
+        "cannot send inscription {id} in output {} with {postage} sats of postage",
+        satpoint.outpoint,
+      );
+
+      let excess = remaining - postage;
+      if excess >= change.dust_value().to_sat() {
+        outputs.push(TxOut {
+          script_pubkey,
+          value: postage,
+        });
+        outputs.push(TxOut {
+          script_pubkey: change,
+          value: excess,
+        });
+      } else {
+        outputs.push(TxOut {
+          script_pubkey,
+          value: remaining,
+        });
+      }
+    }
+
+    let mut edicts = Vec::new();
+    let mut required: BTreeMap<Rune, (RuneId, RuneEntry, u128)> = BTreeMap::new();
+
+    for (address, outgoing) in &recipients {
+      let Outgoing::Rune { decimal, rune } = outgoing else {
+        continue;
+      };
+
+      ensure!(
+        wallet.has_rune_index(),
+        "sending runes with `ord wallet send-many` requires index created with `--index-runes` flag",
+      );
+
+      let (id, entry, _parent) = match required.get(&rune.rune) {
+        Some((id, entry, _)) => (*id, *entry, None),
+        None => wallet
+          .get_rune(rune.rune)?
+          .with_context(|| format!("rune `{}` has not been etched", rune.rune))?,
+      };
+
+      let amount = decimal.to_integer(entry.divisibility)?;
+
+      ensure!(amount > 0, "cannot send zero `{}`", entry.spaced_rune);
+
+      required.entry(rune.rune).or_insert((id, entry, 0)).2 += amount;
+
+      edicts.push(Edict {
+        id,
+        amount,
+        output: outputs.len().try_into()?,
+      });
+
+      outputs.push(TxOut {
+        script_pubkey: address.script_pubkey(),
+        value: self.postage.unwrap_or(TARGET_POSTAGE).to_sat(),
+      });
+    }
+
+    let mut runestone = None;
+
+    if !required.is_empty() {
+      let inscribed_outputs = wallet
+        .inscriptions()
+        .keys()
+        .map(|satpoint| satpoint.outpoint)
+        .collect::<HashSet<OutPoint>>();
+
+      let mut input_balances: BTreeMap<Rune, u128> = BTreeMap::new();
+
+      for output in &runic_outputs {
+        if inscribed_outputs.contains(output) {
+          continue;
+        }
+
+        let balances = wallet.get_runes_balances_in_output(output)?;
+
+        let needed = balances.iter().any(|(spaced_rune, _)| {
+          required
+            .get(&spaced_rune.rune)
+            .map(|(_, _, amount)| {
+              input_balances
+                .get(&spaced_rune.rune)
+                .copied()
+                .unwrap_or_default()
+                < *amount
+            })
+            .unwrap_or_default()
+        });
+
+        if !needed {
+          continue;
+        }
+
+        for (spaced_rune, pile) in balances {
+          *input_balances.entry(spaced_rune.rune).or_default() += pile.amount;
+        }
+
+        inputs.push(*output);
+      }
+
+      for (rune, (_, entry, amount)) in &required {
+        let balance = input_balances.get(rune).copied().unwrap_or_default();
+
+        ensure! {
+          balance >= *amount,
+          "insufficient `{}` balance, only {} in wallet",
+          entry.spaced_rune,
+          Pile {
+            amount: balance,
+            divisibility: entry.divisibility,
+            symbol: entry.symbol,
+          },
+        }
+      }
+
+      let needs_runes_change_output = input_balances.iter().any(|(rune, balance)| {
+        required
+          .get(rune)
+          .map(|(_, _, amount)| balance > amount)
+          .unwrap_or(true)
+      });
+
+      let pointer = if needs_runes_change_output {
+        outputs.push(TxOut {
+          script_pubkey: wallet.get_change_address()?.script_pubkey(),
+          value: self.postage.unwrap_or(TARGET_POSTAGE).to_sat(),
+        });
+
+        Some((outputs.len() - 1).try_into()?)
+      } else {
+        None
+      };
+
+      runestone = Some(Runestone {
+        edicts,
+        pointer,
+        ..default()
+      });
+    }
+
+    for (address, outgoing) in &recipients {
+      match outgoing {
+        Outgoing::Amount(amount) => outputs.push(TxOut {
+          script_pubkey: address.script_pubkey(),
+          value: amount.to_sat(),
+        }),
+        Outgoing::InscriptionId(_) | Outgoing::Rune { .. } => {}
+        Outgoing::Sat(_) | Outgoing::SatPoint(_) => {
+          bail!("`ord wallet send-many` cannot send sats or satpoints, use `ord wallet send`")
+        }
+      }
+    }
+
+    if let Some(runestone) = &runestone {
+      let script_pubkey = runestone.encipher();
+
+      ensure!(
+        self.no_limit || script_pubkey.len() <= 82,
+        "runestone greater than maximum OP_RETURN size: {} > 82",
+        script_pubkey.len()
+      );
+
+      outputs.push(TxOut {
+        script_pubkey,
+        value: 0,
+      });
+    }
+
+    let unfunded_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::MAX,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: outputs,
+    };
+
+    let unsigned_transaction: Transaction = consensus::encode::deserialize(
+      &wallet.fund_raw_transaction(self.fee_rate, &unfunded_transaction)?,
+    )?;
+
+    if let Some(runestone) = runestone {
+      assert_eq!(
+        Runestone::decipher(&unsigned_transaction),
+        Some(Artifact::Runestone(runestone)),
+      );
+    }
+
+    Ok(unsigned_transaction)
+  }
+}
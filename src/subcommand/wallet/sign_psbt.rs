@@ -0,0 +1,37 @@
+use {super::*, base64::Engine};
+
+#[derive(Debug, Parser)]
+pub(crate) struct SignPsbt {
+  #[arg(help = "Sign base64-encoded <PSBT>.")]
+  psbt: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Output {
+  pub psbt: String,
+  pub complete: bool,
+}
+
+impl SignPsbt {
+  pub(crate) fn run(self, name: String, settings: &Settings) -> SubcommandResult {
+    let database = Wallet::open_existing_database(&name, settings)?;
+
+    let Some(keychain) = Wallet::load_keychain(&database)? else {
+      bail!("wallet `{name}` has no keys, sign with Litecoin Core instead");
+    };
+
+    let mut psbt = decode_psbt(&self.psbt)?;
+
+    let complete = Wallet::sign_psbt_offline(
+      &database,
+      &keychain,
+      &Wallet::read_password(false)?,
+      &mut psbt,
+    )?;
+
+    Ok(Some(Box::new(Output {
+      psbt: base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+      complete,
+    })))
+  }
+}
@@ -0,0 +1,281 @@
+use {super::*, crate::wallet::transaction_builder::TransactionBuilder};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Split {
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Trim inscription outputs, and give rare sats outputs of, <AMOUNT> postage. [default: 10000 sat]"
+  )]
+  postage: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub split: Vec<OutPoint>,
+  pub outputs: usize,
+  pub fee: u64,
+}
+
+impl Split {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE).to_sat();
+
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    let mut inscription_offsets: BTreeMap<OutPoint, Vec<u64>> = BTreeMap::new();
+    for satpoint in wallet.inscriptions().keys() {
+      inscription_offsets
+        .entry(satpoint.outpoint)
+        .or_default()
+        .push(satpoint.offset);
+    }
+
+    let change = wallet.get_change_address()?.script_pubkey();
+    let dust = change.dust_value().to_sat();
+
+    ensure!(
+      postage >= dust,
+      "postage of {postage} sats is below the dust limit of {dust} sats",
+    );
+
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut cardinal_tail = false;
+
+    for (outpoint, txout) in wallet.utxos() {
+      // rune balances would all go to the first piece, so runic outputs are
+      // left alone, as are locked outputs
+      if runic_outputs.contains(outpoint) || wallet.locked_utxos().contains_key(outpoint) {
+        continue;
+      }
+
+      let mut offsets = wallet.get_rare_sat_offsets(outpoint)?;
+
+      if let Some(inscriptions) = inscription_offsets.get(outpoint) {
+        offsets.extend(inscriptions);
+      }
+
+      if offsets.is_empty() {
+        continue;
+      }
+
+      let pieces = Self::pieces(txout.value, offsets, postage, dust);
+
+      if pieces.len() < 2 {
+        continue;
+      }
+
+      inputs.push(*outpoint);
+
+      for (value, _) in &pieces {
+        outputs.push(TxOut {
+          script_pubkey: wallet.get_change_address()?.script_pubkey(),
+          value: *value,
+        });
+      }
+
+      cardinal_tail = !pieces.last().unwrap().1;
+    }
+
+    ensure!(
+      !inputs.is_empty(),
+      "no outputs contain excess postage or rare sats to split off",
+    );
+
+    let split = inputs.clone();
+
+    let fee = self
+      .fee_rate
+      .fee(TransactionBuilder::estimate_vbytes_with(
+        inputs.len(),
+        outputs.clone(),
+      ))
+      .to_sat();
+
+    // fees are paid with the last sats of the transaction, so if the last
+    // piece holds nothing of value, it pays them, and otherwise cardinal
+    // outputs are added, with change going last
+    let tail = outputs.last_mut().unwrap();
+
+    if cardinal_tail && tail.value >= fee + dust {
+      tail.value -= fee;
+    } else {
+      let mut cardinals = wallet
+        .get_common_cardinal_utxos()?
+        .into_iter()
+        .collect::<Vec<(OutPoint, TxOut)>>();
+
+      cardinals.sort_by(|(_, a), (_, b)| b.value.cmp(&a.value));
+
+      let change_output = TxOut {
+        script_pubkey: change,
+        value: 0,
+      };
+
+      let mut funding = 0;
+      let mut fee;
+      let mut cardinals = cardinals.into_iter();
+      loop {
+        let mut estimated_outputs = outputs.clone();
+        estimated_outputs.push(change_output.clone());
+
+        fee = self
+          .fee_rate
+          .fee(TransactionBuilder::estimate_vbytes_with(
+            inputs.len(),
+            estimated_outputs,
+          ))
+          .to_sat();
+
+        if funding >= fee + dust {
+          break;
+        }
+
+        let (outpoint, txout) = cardinals.next().ok_or_else(|| {
+          anyhow!("wallet contains too few cardinal outputs to pay fee of {fee} sats")
+        })?;
+
+        inputs.push(outpoint);
+        funding += txout.value;
+      }
+
+      outputs.push(TxOut {
+        value: funding - fee,
+        ..change_output
+      });
+    }
+
+    let output_count = outputs.len();
+
+    let unsigned_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: outputs,
+    };
+
+    let (txid, psbt, fee) = wallet.sign_transaction(unsigned_transaction, self.dry_run)?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      split,
+      outputs: output_count,
+      fee,
+    })))
+  }
+
+  /// Divide an output of `value` sats into pieces, so that every sat at one
+  /// of `offsets` begins a piece of `postage` sats, or lies within one, and
+  /// the sats between those pieces are split off into their own. Pieces
+  /// smaller than `dust` are merged into their neighbour. Each piece is
+  /// returned with whether it holds any of the sats at `offsets`.
+  fn pieces(value: u64, mut offsets: Vec<u64>, postage: u64, dust: u64) -> Vec<(u64, bool)> {
+    offsets.sort();
+
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+
+    for offset in offsets {
+      if offset < end {
+        continue;
+      }
+
+      if offset - end >= dust {
+        if end > start {
+          pieces.push((end - start, true));
+        }
+        pieces.push((offset - end, false));
+        start = offset;
+      }
+
+      end = (offset + postage).min(value);
+    }
+
+    if value - end >= dust {
+      pieces.push((end - start, true));
+      pieces.push((value - end, false));
+    } else {
+      pieces.push((value - start, true));
+    }
+
+    pieces
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn inscription_outputs_are_trimmed_to_postage() {
+    assert_eq!(
+      Split::pieces(50_000, vec![0], 10_000, 330),
+      [(10_000, true), (40_000, false)]
+    );
+  }
+
+  #[test]
+  fn outputs_at_postage_are_not_split() {
+    assert_eq!(
+      Split::pieces(10_000, vec![0], 10_000, 330),
+      [(10_000, true)]
+    );
+    assert_eq!(
+      Split::pieces(10_100, vec![0], 10_000, 330),
+      [(10_100, true)]
+    );
+  }
+
+  #[test]
+  fn sats_before_first_offset_are_split_off() {
+    assert_eq!(
+      Split::pieces(50_000, vec![20_000], 10_000, 330),
+      [(20_000, false), (10_000, true), (20_000, false)]
+    );
+    assert_eq!(
+      Split::pieces(50_000, vec![100], 10_000, 330),
+      [(10_100, true), (39_900, false)]
+    );
+  }
+
+  #[test]
+  fn nearby_offsets_share_a_piece() {
+    assert_eq!(
+      Split::pieces(50_000, vec![5_000, 0], 10_000, 330),
+      [(10_000, true), (40_000, false)]
+    );
+    assert_eq!(
+      Split::pieces(50_000, vec![0, 10_100], 10_000, 330),
+      [(20_100, true), (29_900, false)]
+    );
+  }
+
+  #[test]
+  fn distant_offsets_get_their_own_pieces() {
+    assert_eq!(
+      Split::pieces(100_000, vec![0, 50_000], 10_000, 330),
+      [
+        (10_000, true),
+        (40_000, false),
+        (10_000, true),
+        (40_000, false)
+      ]
+    );
+  }
+}
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, std::collections::HashMap};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Transactions {
@@ -6,16 +6,66 @@ pub(crate) struct Transactions {
   limit: Option<u16>,
 }
 
+/// The pages of an address's history fetched so far. Pages are fetched only
+/// until the activity of a transaction is found, or the history has passed
+/// the transaction's block, so that addresses with long histories don't
+/// require reading every page.
+#[derive(Default)]
+struct History {
+  activity: Vec<api::AddressActivity>,
+  done: bool,
+  page: usize,
+}
+
+impl History {
+  fn find(
+    &mut self,
+    wallet: &Wallet,
+    address: &Address<NetworkUnchecked>,
+    txid: Txid,
+    height: u32,
+  ) -> Result<Option<api::AddressActivity>> {
+    loop {
+      if let Some(activity) = self
+        .activity
+        .iter()
+        .find(|activity| activity.transaction == txid)
+      {
+        return Ok(Some(activity.clone()));
+      }
+
+      // activity is ordered by height, most recent first
+      if self.done
+        || self
+          .activity
+          .last()
+          .is_some_and(|activity| activity.height < height)
+      {
+        return Ok(None);
+      }
+
+      let history = wallet.get_address_history(address, self.page)?;
+
+      self.activity.extend(history.activity);
+      self.done = !history.more;
+      self.page += 1;
+    }
+  }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Output {
   pub transaction: Txid,
   pub confirmations: i32,
+  pub activity: Option<api::AddressActivity>,
 }
 
 impl Transactions {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
     let client = wallet.bitcoin_client();
 
+    let mut histories = HashMap::new();
+
     let mut output = Vec::new();
 
     for tx in client.list_transactions(
@@ -28,9 +78,22 @@ impl Transactions {
         // MWEB transactions do not have a vout
         continue;
       }
+
+      // the inscriptions, runes and value the wallet address sent and
+      // received in this transaction, if the server tracks address history,
+      // which only includes confirmed transactions
+      let activity = match (&tx.detail.address, tx.info.blockheight) {
+        (Some(address), Some(height)) if wallet.has_address_history_index() => histories
+          .entry(address.clone())
+          .or_default()
+          .find(&wallet, address, tx.info.txid, height)?,
+        _ => None,
+      };
+
       output.push(Output {
         transaction: tx.info.txid,
         confirmations: tx.info.confirmations,
+        activity,
       });
     }
 
@@ -3,14 +3,18 @@ use {super::*, boilerplate::Boilerplate};
 pub(crate) use {
   crate::subcommand::server::ServerConfig,
   address::AddressHtml,
+  address_history::AddressHistoryHtml,
   block::BlockHtml,
   children::ChildrenHtml,
   clock::ClockSvg,
+  collection::CollectionHtml,
+  collection_holders::CollectionHoldersHtml,
   collections::CollectionsHtml,
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
   inscription::InscriptionHtml,
+  inscription_history::InscriptionHistoryHtml,
   inscriptions::InscriptionsHtml,
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
@@ -21,7 +25,10 @@ pub(crate) use {
     PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
   },
   rare::RareTxt,
+  rune_history::RuneHistoryHtml,
+  rune_holders::RuneHoldersHtml,
   sat::SatHtml,
+  search::SearchHtml,
 };
 
 pub use {
@@ -30,15 +37,19 @@ pub use {
 };
 
 pub mod address;
+mod address_history;
 pub mod block;
 pub mod blocks;
 mod children;
 mod clock;
+mod collection;
+mod collection_holders;
 pub mod collections;
 mod home;
 mod iframe;
 mod input;
 pub mod inscription;
+mod inscription_history;
 pub mod inscriptions;
 mod inscriptions_block;
 mod metadata;
@@ -47,8 +58,11 @@ mod parents;
 mod preview;
 mod rare;
 pub mod rune;
+mod rune_history;
+mod rune_holders;
 pub mod runes;
 pub mod sat;
+mod search;
 pub mod status;
 pub mod transaction;
 
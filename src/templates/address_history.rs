@@ -0,0 +1,103 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressHistoryHtml {
+  pub(crate) address: Address,
+  pub(crate) activity: Vec<api::AddressActivity>,
+  pub(crate) prev_page: Option<usize>,
+  pub(crate) next_page: Option<usize>,
+}
+
+impl PageContent for AddressHistoryHtml {
+  fn title(&self) -> String {
+    format!("Address {} History", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn address() -> Address {
+    Address::from_str("bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8")
+      .unwrap()
+      .require_network(Network::Bitcoin)
+      .unwrap()
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      AddressHistoryHtml {
+        address: address(),
+        activity: Vec::new(),
+        prev_page: None,
+        next_page: None,
+      },
+      "<h1><a href=/address/bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8>Address bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8</a> History</h1>\n<h3>No activity</h3>\n"
+    );
+  }
+
+  #[test]
+  fn with_activity() {
+    assert_regex_match!(
+      AddressHistoryHtml {
+        address: address(),
+        activity: vec![api::AddressActivity {
+          height: 2,
+          inscriptions_received: vec![inscription_id(1)],
+          inscriptions_sent: Vec::new(),
+          received: 1000,
+          runes_received: BTreeMap::new(),
+          runes_sent: [(
+            SpacedRune {
+              rune: Rune(26),
+              spacers: 0,
+            },
+            Pile {
+              amount: 5,
+              divisibility: 0,
+              symbol: None,
+            },
+          )]
+          .into_iter()
+          .collect(),
+          sent: 500,
+          transaction: txid(1),
+        }],
+        prev_page: Some(0),
+        next_page: Some(2),
+      },
+      "
+        <h1>.*</h1>
+        <table>
+          <tr>
+            <th>block</th>
+            <th>transaction</th>
+            <th>received</th>
+            <th>sent</th>
+          </tr>
+          <tr>
+            <td><a href=/block/2>2</a></td>
+            <td><a class=monospace href=/tx/1{64}>1{64}</a></td>
+            <td>
+              1000 sats
+              <div class=thumbnails>
+                <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+              </div>
+            </td>
+            <td>
+              500 sats
+              <div><a class=monospace href=/rune/AA>AA</a>: 5\u{A0}¤</div>
+            </td>
+          </tr>
+        </table>
+        <div class=center>
+          <a class=prev href=/address/bc1p.*/history/0>prev</a>
+          <a class=next href=/address/bc1p.*/history/2>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct CollectionHtml {
+  pub(crate) collection: api::Collection,
+}
+
+impl PageContent for CollectionHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} Collection", self.collection.number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      CollectionHtml {
+        collection: api::Collection {
+          children: 0,
+          content_types: Vec::new(),
+          holders: None,
+          id: inscription_id(1),
+          mints: Vec::new(),
+          number: 0,
+        },
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <dl>
+          <dt>children</dt>
+          <dd><a href=/children/1{64}i1>0</a></dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_stats() {
+    assert_regex_match!(
+      CollectionHtml {
+        collection: api::Collection {
+          children: 3,
+          content_types: vec![
+            api::CollectionContentType {
+              children: 2,
+              content_type: Some("text/plain".into()),
+            },
+            api::CollectionContentType {
+              children: 1,
+              content_type: None,
+            },
+          ],
+          holders: Some(2),
+          id: inscription_id(1),
+          mints: vec![
+            api::CollectionMints {
+              children: 1,
+              height: 5,
+            },
+            api::CollectionMints {
+              children: 2,
+              height: 6,
+            },
+          ],
+          number: 0,
+        },
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <dl>
+          <dt>children</dt>
+          <dd><a href=/children/1{64}i1>3</a></dd>
+          <dt>holders</dt>
+          <dd><a href=/collection/1{64}i1/holders>2</a></dd>
+        </dl>
+        <h2>Content Types</h2>
+        <table>
+          <tr>
+            <th>content type</th>
+            <th>children</th>
+          </tr>
+          <tr>
+            <td>text/plain</td>
+            <td>2</td>
+          </tr>
+          <tr>
+            <td>none</td>
+            <td>1</td>
+          </tr>
+        </table>
+        <h2>Mints</h2>
+        <table>
+          <tr>
+            <th>block</th>
+            <th>children</th>
+          </tr>
+          <tr>
+            <td><a href=/block/5>5</a></td>
+            <td>1</td>
+          </tr>
+          <tr>
+            <td><a href=/block/6>6</a></td>
+            <td>2</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+}
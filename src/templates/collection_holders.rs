@@ -0,0 +1,90 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct CollectionHoldersHtml {
+  pub(crate) holders: api::CollectionHolders,
+  pub(crate) next_page: Option<usize>,
+  pub(crate) number: i32,
+  pub(crate) prev_page: Option<usize>,
+}
+
+impl PageContent for CollectionHoldersHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} Collection Holders", self.number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      CollectionHoldersHtml {
+        holders: api::CollectionHolders {
+          holders: Vec::new(),
+          id: inscription_id(1),
+          more: false,
+          page: 0,
+        },
+        next_page: None,
+        number: 0,
+        prev_page: None,
+      },
+      "
+        <h1><a href=/collection/1{64}i1>Inscription 0 Collection</a> Holders</h1>
+        <h3>No holders</h3>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_holders() {
+    assert_regex_match!(
+      CollectionHoldersHtml {
+        holders: api::CollectionHolders {
+          holders: vec![
+            api::CollectionHolder {
+              address: Some("foo".into()),
+              children: 2,
+            },
+            api::CollectionHolder {
+              address: None,
+              children: 1,
+            },
+          ],
+          id: inscription_id(1),
+          more: true,
+          page: 1,
+        },
+        next_page: Some(2),
+        number: 0,
+        prev_page: Some(0),
+      },
+      "
+        <h1><a href=/collection/1{64}i1>Inscription 0 Collection</a> Holders</h1>
+        <table>
+          <tr>
+            <th>holder</th>
+            <th>children</th>
+          </tr>
+          <tr>
+            <td><a class=monospace href=/address/foo>foo</a></td>
+            <td>2</td>
+          </tr>
+          <tr>
+            <td>other</td>
+            <td>1</td>
+          </tr>
+        </table>
+        <div class=center>
+          <a class=prev href=/collection/1{64}i1/holders/0>prev</a>
+          <a class=next href=/collection/1{64}i1/holders/2>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
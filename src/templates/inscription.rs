@@ -292,6 +292,7 @@ mod tests {
             </div>
             <div class=center>
               <a href=/children/1{64}i1>all</a>
+              <a href=/collection/1{64}i1>stats</a>
             </div>
           </dd>
           <dt>id</dt>
@@ -353,6 +354,7 @@ mod tests {
             </div>
             <div class=center>
               <a href=/children/1{64}i1>all</a>
+              <a href=/collection/1{64}i1>stats</a>
             </div>
           </dd>
           <dt>id</dt>
@@ -0,0 +1,99 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionHistoryHtml {
+  pub(crate) id: InscriptionId,
+  pub(crate) number: i32,
+  pub(crate) transfers: Vec<api::InscriptionTransfer>,
+  pub(crate) prev_page: Option<usize>,
+  pub(crate) next_page: Option<usize>,
+}
+
+impl PageContent for InscriptionHistoryHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} History", self.number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      InscriptionHistoryHtml {
+        id: inscription_id(1),
+        number: 0,
+        transfers: Vec::new(),
+        prev_page: None,
+        next_page: None,
+      },
+      "<h1><a href=/inscription/1{64}i1>Inscription 0</a> History</h1>\n<h3>No transfers</h3>\n"
+    );
+  }
+
+  #[test]
+  fn with_transfers() {
+    assert_regex_match!(
+      InscriptionHistoryHtml {
+        id: inscription_id(1),
+        number: 0,
+        transfers: vec![
+          api::InscriptionTransfer {
+            address: None,
+            height: 1,
+            new_satpoint: satpoint(1, 0),
+            old_satpoint: None,
+            timestamp: 1,
+            transaction: txid(1),
+          },
+          api::InscriptionTransfer {
+            address: Some("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".into()),
+            height: 2,
+            new_satpoint: satpoint(2, 0),
+            old_satpoint: Some(satpoint(1, 0)),
+            timestamp: 2,
+            transaction: txid(2),
+          },
+        ],
+        prev_page: None,
+        next_page: Some(1),
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> History</h1>
+        <table>
+          <tr>
+            <th>block</th>
+            <th>time</th>
+            <th>transaction</th>
+            <th>from</th>
+            <th>to</th>
+            <th>address</th>
+          </tr>
+          <tr>
+            <td><a href=/block/1>1</a></td>
+            <td><time>1970-01-01 00:00:01 UTC</time></td>
+            <td><a class=monospace href=/tx/1{64}>1{64}</a></td>
+            <td>created</td>
+            <td><a class=monospace href=/satpoint/1{64}:1:0>1{64}:1:0</a></td>
+            <td></td>
+          </tr>
+          <tr>
+            <td><a href=/block/2>2</a></td>
+            <td><time>1970-01-01 00:00:02 UTC</time></td>
+            <td><a class=monospace href=/tx/2{64}>2{64}</a></td>
+            <td><a class=monospace href=/satpoint/1{64}:1:0>1{64}:1:0</a></td>
+            <td><a class=monospace href=/satpoint/2{64}:2:0>2{64}:2:0</a></td>
+            <td><a class=monospace href=/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</a></td>
+          </tr>
+        </table>
+        <div class=center>
+          prev
+          <a class=next href=/inscription/1{64}i1/history/1>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,96 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct RuneHistoryHtml {
+  pub(crate) history: api::RuneHistory,
+}
+
+impl PageContent for RuneHistoryHtml {
+  fn title(&self) -> String {
+    format!("Rune {} History", self.history.rune)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      RuneHistoryHtml {
+        history: api::RuneHistory {
+          divisibility: 0,
+          id: RuneId { block: 1, tx: 0 },
+          rune: SpacedRune {
+            rune: Rune(26),
+            spacers: 0,
+          },
+          stats: vec![
+            api::RuneStats {
+              burned: 0,
+              height: 1,
+              holders: 1,
+              mints: 0,
+              supply: 100,
+            },
+            api::RuneStats {
+              burned: 10,
+              height: 3,
+              holders: 2,
+              mints: 1,
+              supply: 150,
+            },
+          ],
+          symbol: None,
+        },
+      },
+      "
+        <h1><a href=/rune/AA>AA</a> History</h1>
+        <table>
+          <tr>
+            <th>block</th>
+            <th>supply</th>
+            <th>mints</th>
+            <th>burned</th>
+            <th>holding outputs</th>
+          </tr>
+          <tr>
+            <td><a href=/rune/AA/holders\\?height=1>1</a></td>
+            <td>100\u{A0}¤</td>
+            <td>0</td>
+            <td>0\u{A0}¤</td>
+            <td>1</td>
+          </tr>
+          <tr>
+            <td><a href=/rune/AA/holders\\?height=3>3</a></td>
+            <td>150\u{A0}¤</td>
+            <td>1</td>
+            <td>10\u{A0}¤</td>
+            <td>2</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      RuneHistoryHtml {
+        history: api::RuneHistory {
+          divisibility: 0,
+          id: RuneId { block: 1, tx: 0 },
+          rune: SpacedRune {
+            rune: Rune(26),
+            spacers: 0,
+          },
+          stats: Vec::new(),
+          symbol: None,
+        },
+      },
+      "<h1><a href=/rune/AA>AA</a> History</h1>\n<h3>No history</h3>\n"
+    );
+  }
+}
@@ -0,0 +1,145 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct RuneHoldersHtml {
+  pub(crate) holders: api::RuneHolders,
+  pub(crate) next_page: Option<usize>,
+  pub(crate) prev_page: Option<usize>,
+  pub(crate) query_height: Option<u32>,
+}
+
+impl RuneHoldersHtml {
+  fn query(&self) -> String {
+    self
+      .query_height
+      .map(|height| format!("?height={height}"))
+      .unwrap_or_default()
+  }
+}
+
+impl PageContent for RuneHoldersHtml {
+  fn title(&self) -> String {
+    format!(
+      "Rune {} Holders at Height {}",
+      self.holders.rune, self.holders.height
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      RuneHoldersHtml {
+        holders: api::RuneHolders {
+          divisibility: 0,
+          height: 5,
+          holders: Vec::new(),
+          id: RuneId { block: 1, tx: 0 },
+          more: false,
+          page: 0,
+          rune: SpacedRune {
+            rune: Rune(26),
+            spacers: 1,
+          },
+          stats: None,
+          symbol: None,
+        },
+        next_page: None,
+        prev_page: None,
+        query_height: None,
+      },
+      "
+        <h1><a href=/rune/A•A>A•A</a> Holders</h1>
+        <dl>
+          <dt>height</dt>
+          <dd><a href=/block/5>5</a></dd>
+        </dl>
+        <h3>No holders</h3>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_holders() {
+    assert_regex_match!(
+      RuneHoldersHtml {
+        holders: api::RuneHolders {
+          divisibility: 1,
+          height: 5,
+          holders: vec![
+            api::RuneHolder {
+              address: Some("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".into()),
+              amount: 30,
+              outputs: vec![outpoint(1), outpoint(2)],
+            },
+            api::RuneHolder {
+              address: None,
+              amount: 10,
+              outputs: vec![outpoint(3)],
+            },
+          ],
+          id: RuneId { block: 1, tx: 0 },
+          more: false,
+          page: 0,
+          rune: SpacedRune {
+            rune: Rune(26),
+            spacers: 0,
+          },
+          stats: Some(api::RuneStats {
+            burned: 5,
+            height: 4,
+            holders: 3,
+            mints: 2,
+            supply: 45,
+          }),
+          symbol: Some('%'),
+        },
+        next_page: Some(2),
+        prev_page: Some(0),
+        query_height: Some(5),
+      },
+      "
+        <h1><a href=/rune/AA>AA</a> Holders</h1>
+        <dl>
+          <dt>height</dt>
+          <dd><a href=/block/5>5</a></dd>
+          <dt>supply</dt>
+          <dd>4.5\u{A0}%</dd>
+          <dt>mints</dt>
+          <dd>2</dd>
+          <dt>burned</dt>
+          <dd>0.5\u{A0}%</dd>
+          <dt>holders</dt>
+          <dd>3</dd>
+        </dl>
+        <table>
+          <tr>
+            <th>holder</th>
+            <th>balance</th>
+            <th>outputs</th>
+          </tr>
+          <tr>
+            <td><a class=monospace href=/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</a></td>
+            <td>3\u{A0}%</td>
+            <td>2</td>
+          </tr>
+          <tr>
+            <td><a class=monospace href=/output/3{64}:3>3{64}:3</a></td>
+            <td>1\u{A0}%</td>
+            <td>1</td>
+          </tr>
+        </table>
+        <div class=center>
+          <a class=prev href=/rune/AA/holders/0\\?height=5>prev</a>
+          <a class=next href=/rune/AA/holders/2\\?height=5>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct SearchHtml {
+  pub(crate) parameters: String,
+  pub(crate) search: api::Search,
+}
+
+impl SearchHtml {
+  fn cursor_url(&self, cursor: u32) -> String {
+    format!("/search?{}&cursor={cursor}", self.parameters)
+  }
+}
+
+impl PageContent for SearchHtml {
+  fn title(&self) -> String {
+    format!("Search: {}", self.search.query)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      SearchHtml {
+        parameters: "q=foo".into(),
+        search: api::Search {
+          cursor: None,
+          inscriptions: Vec::new(),
+          more: false,
+          next_cursor: None,
+          query: "foo".into(),
+          runes: Vec::new(),
+        },
+      },
+      r#"
+        <h1>Search</h1>
+        <form action=/search method=get>
+          <input .* name=q spellcheck=false value="foo">
+          .*
+        </form>
+        <h2>Inscriptions</h2>
+        <p>No matching inscriptions</p>
+      "#
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_results() {
+    assert_regex_match!(
+      SearchHtml {
+        parameters: "q=foo&charm=cursed".into(),
+        search: api::Search {
+          cursor: Some(7),
+          inscriptions: vec![inscription_id(1)],
+          more: true,
+          next_cursor: Some(5),
+          query: "foo".into(),
+          runes: vec!["FOO•BAR".parse().unwrap()],
+        },
+      },
+      r#"
+        <h1>Search</h1>
+        <form action=/search method=get>
+          .*
+        </form>
+        <h2>Runes</h2>
+        <ul>
+          <li><a href=/rune/FOO•BAR>FOO•BAR</a></li>
+        </ul>
+        <h2>Inscriptions</h2>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+        </div>
+        <div class=center>
+          <a class=prev href="/search\?q=foo&amp;charm=cursed">first</a>
+          <a class=next href="/search\?q=foo&amp;charm=cursed&amp;cursor=5">next</a>
+        </div>
+      "#
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn empty_page_with_more_results() {
+    assert_regex_match!(
+      SearchHtml {
+        parameters: "q=foo".into(),
+        search: api::Search {
+          cursor: None,
+          inscriptions: Vec::new(),
+          more: true,
+          next_cursor: Some(3),
+          query: "foo".into(),
+          runes: Vec::new(),
+        },
+      },
+      r#"
+        <h1>Search</h1>
+        <form action=/search method=get>
+          .*
+        </form>
+        <h2>Inscriptions</h2>
+        <div class=center>
+        first
+          <a class=next href="/search\?q=foo&amp;cursor=3">next</a>
+        </div>
+      "#
+      .unindent()
+    );
+  }
+}
@@ -2,19 +2,25 @@ use super::*;
 
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StatusHtml {
+  pub address_history_index: bool,
   pub address_index: bool,
   pub blessed_inscriptions: u64,
   pub chain: Chain,
   pub cursed_inscriptions: u64,
   pub height: Option<u32>,
   pub initial_sync_time: Duration,
+  pub inscription_details_index: bool,
+  pub inscription_history_index: bool,
   pub inscription_index: bool,
   pub inscriptions: u64,
   pub lost_sats: u64,
   pub minimum_rune_for_next_block: Rune,
+  pub mweb_sats: u64,
+  pub rune_history_index: bool,
   pub rune_index: bool,
   pub runes: u64,
   pub sat_index: bool,
+  pub search_index: bool,
   pub started: DateTime<Utc>,
   pub transaction_index: bool,
   pub unrecoverably_reorged: bool,
@@ -0,0 +1,364 @@
+use super::*;
+
+/// Where the inscriptions and runes carried by the inputs of a transaction end
+/// up, following the same rules as the index.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Transfer {
+  pub(crate) burned: BTreeMap<SpacedRune, api::RuneBalance>,
+  pub(crate) default_output: Option<usize>,
+  pub(crate) fee: BTreeMap<InscriptionId, u64>,
+  pub(crate) outputs: Vec<api::Assets>,
+}
+
+impl Transfer {
+  /// `inputs` holds the value and assets of each output spent by `tx`, and
+  /// `created` any runes minted or premined by it. Runes etched by `tx` do not
+  /// have an ID yet, and must use the default rune ID, as edicts do.
+  pub(crate) fn simulate(
+    tx: &Transaction,
+    inputs: &[(u64, api::Assets)],
+    created: &[(SpacedRune, api::RuneBalance)],
+  ) -> Self {
+    let mut outputs = vec![api::Assets::default(); tx.output.len()];
+
+    let mut floating = Vec::new();
+    let mut input_value = 0;
+    for (value, assets) in inputs {
+      for (id, offset) in &assets.inscriptions {
+        floating.push((input_value + offset, *id));
+      }
+      input_value += value;
+    }
+
+    floating.sort();
+
+    let mut floating = floating.into_iter().peekable();
+    let mut output_value = 0;
+    for (vout, txout) in tx.output.iter().enumerate() {
+      let end = output_value + txout.value;
+
+      while let Some((offset, id)) = floating.next_if(|(offset, _)| *offset < end) {
+        outputs[vout].inscriptions.insert(id, offset - output_value);
+      }
+
+      output_value = end;
+    }
+
+    let fee = floating
+      .map(|(offset, id)| (id, offset - output_value))
+      .collect();
+
+    let mut runes = BTreeMap::new();
+    let mut unallocated: BTreeMap<RuneId, u128> = BTreeMap::new();
+    for (rune, balance) in inputs
+      .iter()
+      .flat_map(|(_, assets)| &assets.runes)
+      .chain(created.iter().map(|(rune, balance)| (rune, balance)))
+    {
+      runes.insert(balance.id, (*rune, *balance));
+      *unallocated.entry(balance.id).or_default() += balance.amount;
+    }
+
+    let mut allocated: Vec<BTreeMap<RuneId, u128>> = vec![BTreeMap::new(); tx.output.len()];
+    let mut burned: BTreeMap<RuneId, u128> = BTreeMap::new();
+
+    let artifact = Runestone::decipher(tx);
+
+    if let Some(Artifact::Runestone(runestone)) = &artifact {
+      for Edict { id, amount, output } in runestone.edicts.iter().copied() {
+        let output = output.into_usize();
+
+        let Some(balance) = unallocated.get_mut(&id) else {
+          continue;
+        };
+
+        let mut allocate = |balance: &mut u128, amount: u128, output: usize| {
+          if amount > 0 {
+            *balance -= amount;
+            *allocated[output].entry(id).or_default() += amount;
+          }
+        };
+
+        if output == tx.output.len() {
+          let destinations = tx
+            .output
+            .iter()
+            .enumerate()
+            .filter_map(|(output, tx_out)| (!tx_out.script_pubkey.is_op_return()).then_some(output))
+            .collect::<Vec<usize>>();
+
+          if !destinations.is_empty() {
+            if amount == 0 {
+              let amount = *balance / destinations.len() as u128;
+              let remainder = usize::try_from(*balance % destinations.len() as u128).unwrap();
+
+              for (i, output) in destinations.iter().enumerate() {
+                allocate(
+                  balance,
+                  if i < remainder { amount + 1 } else { amount },
+                  *output,
+                );
+              }
+            } else {
+              for output in destinations {
+                allocate(balance, amount.min(*balance), output);
+              }
+            }
+          }
+        } else {
+          let amount = if amount == 0 {
+            *balance
+          } else {
+            amount.min(*balance)
+          };
+
+          allocate(balance, amount, output);
+        }
+      }
+    }
+
+    let default_output = match &artifact {
+      Some(Artifact::Cenotaph(_)) => None,
+      Some(Artifact::Runestone(Runestone {
+        pointer: Some(pointer),
+        ..
+      })) => Some(pointer.into_usize()),
+      _ => tx
+        .output
+        .iter()
+        .position(|tx_out| !tx_out.script_pubkey.is_op_return()),
+    };
+
+    if let Some(Artifact::Cenotaph(_)) = artifact {
+      for (id, balance) in unallocated {
+        *burned.entry(id).or_default() += balance;
+      }
+    } else {
+      for (id, balance) in unallocated {
+        if balance > 0 {
+          match default_output {
+            Some(vout) => *allocated[vout].entry(id).or_default() += balance,
+            None => *burned.entry(id).or_default() += balance,
+          }
+        }
+      }
+    }
+
+    for (vout, balances) in allocated.into_iter().enumerate() {
+      for (id, amount) in balances {
+        if tx.output[vout].script_pubkey.is_op_return() {
+          *burned.entry(id).or_default() += amount;
+        } else {
+          let (rune, balance) = runes[&id];
+          outputs[vout]
+            .runes
+            .insert(rune, api::RuneBalance { amount, ..balance });
+        }
+      }
+    }
+
+    Self {
+      burned: burned
+        .into_iter()
+        .map(|(id, amount)| {
+          let (rune, balance) = runes[&id];
+          (rune, api::RuneBalance { amount, ..balance })
+        })
+        .collect(),
+      default_output,
+      fee,
+      outputs,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RUNE: u128 = 99246114928149462;
+
+  fn tx(values: &[u64], runestone: Option<Runestone>) -> Transaction {
+    let mut output = values
+      .iter()
+      .map(|value| TxOut {
+        value: *value,
+        script_pubkey: ScriptBuf::new_v0_p2wpkh(&bitcoin::WPubkeyHash::all_zeros()),
+      })
+      .collect::<Vec<TxOut>>();
+
+    if let Some(runestone) = runestone {
+      output.push(TxOut {
+        value: 0,
+        script_pubkey: runestone.encipher(),
+      });
+    }
+
+    Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output,
+    }
+  }
+
+  fn balance(amount: u128) -> api::RuneBalance {
+    api::RuneBalance {
+      id: RuneId { block: 1, tx: 1 },
+      amount,
+      divisibility: 0,
+      symbol: None,
+    }
+  }
+
+  fn rune() -> SpacedRune {
+    SpacedRune {
+      rune: Rune(RUNE),
+      spacers: 0,
+    }
+  }
+
+  #[test]
+  fn inscriptions_are_assigned_first_in_first_out() {
+    let transfer = Transfer::simulate(
+      &tx(&[100, 100], None),
+      &[
+        (
+          150,
+          api::Assets {
+            inscriptions: [(inscription_id(1), 0), (inscription_id(2), 120)].into(),
+            ..default()
+          },
+        ),
+        (
+          100,
+          api::Assets {
+            inscriptions: [(inscription_id(3), 60)].into(),
+            ..default()
+          },
+        ),
+      ],
+      &[],
+    );
+
+    assert_eq!(
+      transfer.outputs[0].inscriptions,
+      [(inscription_id(1), 0)].into()
+    );
+    assert_eq!(
+      transfer.outputs[1].inscriptions,
+      [(inscription_id(2), 20)].into()
+    );
+    assert_eq!(transfer.fee, [(inscription_id(3), 10)].into());
+  }
+
+  #[test]
+  fn unallocated_runes_go_to_first_non_op_return_output() {
+    let transfer = Transfer::simulate(
+      &tx(&[100, 100], Some(Runestone::default())),
+      &[(
+        200,
+        api::Assets {
+          runes: [(rune(), balance(1000))].into(),
+          ..default()
+        },
+      )],
+      &[],
+    );
+
+    assert_eq!(transfer.default_output, Some(0));
+    assert_eq!(transfer.outputs[0].runes, [(rune(), balance(1000))].into());
+    assert!(transfer.outputs[1].runes.is_empty());
+    assert!(transfer.burned.is_empty());
+  }
+
+  #[test]
+  fn edicts_and_pointer_allocate_runes() {
+    let transfer = Transfer::simulate(
+      &tx(
+        &[100, 100],
+        Some(Runestone {
+          edicts: vec![Edict {
+            id: RuneId { block: 1, tx: 1 },
+            amount: 300,
+            output: 0,
+          }],
+          pointer: Some(1),
+          ..default()
+        }),
+      ),
+      &[(
+        200,
+        api::Assets {
+          runes: [(rune(), balance(1000))].into(),
+          ..default()
+        },
+      )],
+      &[],
+    );
+
+    assert_eq!(transfer.default_output, Some(1));
+    assert_eq!(transfer.outputs[0].runes, [(rune(), balance(300))].into());
+    assert_eq!(transfer.outputs[1].runes, [(rune(), balance(700))].into());
+  }
+
+  #[test]
+  fn edicts_to_op_return_burn_runes() {
+    let transfer = Transfer::simulate(
+      &tx(
+        &[100],
+        Some(Runestone {
+          edicts: vec![Edict {
+            id: RuneId { block: 1, tx: 1 },
+            amount: 300,
+            output: 1,
+          }],
+          ..default()
+        }),
+      ),
+      &[(
+        100,
+        api::Assets {
+          runes: [(rune(), balance(1000))].into(),
+          ..default()
+        },
+      )],
+      &[],
+    );
+
+    assert_eq!(transfer.outputs[0].runes, [(rune(), balance(700))].into());
+    assert_eq!(transfer.burned, [(rune(), balance(300))].into());
+  }
+
+  #[test]
+  fn cenotaphs_burn_all_runes() {
+    let mut tx = tx(&[100], None);
+
+    tx.output.push(TxOut {
+      value: 0,
+      script_pubkey: script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_opcode(Runestone::MAGIC_NUMBER)
+        .push_opcode(opcodes::all::OP_VERIFY)
+        .into_script(),
+    });
+
+    let created = [(rune(), balance(5))];
+
+    let transfer = Transfer::simulate(
+      &tx,
+      &[(
+        100,
+        api::Assets {
+          runes: [(rune(), balance(1000))].into(),
+          ..default()
+        },
+      )],
+      &created,
+    );
+
+    assert_eq!(transfer.default_output, None);
+    assert!(transfer.outputs[0].runes.is_empty());
+    assert_eq!(transfer.burned, [(rune(), balance(1005))].into());
+  }
+}
@@ -2,31 +2,62 @@ use {
   super::*,
   base64::{self, Engine},
   batch::ParentInfo,
-  bitcoin::psbt::Psbt,
+  bitcoin::{
+    psbt::Psbt,
+    sighash::{EcdsaSighashType, TapSighashType},
+  },
+  bitcoincore_rpc::bitcoincore_rpc_json::{
+    ImportMultiOptions, ImportMultiRequest, ImportMultiRequestScriptPubkey,
+    SignRawTransactionInput, SignRawTransactionResult, Timestamp,
+  },
   entry::{EtchingEntry, EtchingEntryValue},
   fee_rate::FeeRate,
   index::entry::Entry,
   indicatif::{ProgressBar, ProgressStyle},
+  keychain::{Keychain, KeychainValue},
   log::log_enabled,
-  redb::{Database, DatabaseError, ReadableTable, RepairSession, StorageError, TableDefinition},
+  redb::{
+    Database, DatabaseError, ReadableTable, RepairSession, StorageError, TableDefinition,
+    TableError,
+  },
   reqwest::header,
-  std::sync::Once,
+  std::{
+    cell::{Cell, OnceCell},
+    ops::Range,
+    sync::Once,
+  },
   transaction_builder::TransactionBuilder,
 };
 
+pub mod annotation;
 pub mod batch;
 pub mod entry;
+pub mod keychain;
 pub mod transaction_builder;
 pub mod wallet_constructor;
 
-const SCHEMA_VERSION: u64 = 1;
+const SCHEMA_VERSION: u64 = 2;
 
+// wallets created before ord managed its own keys have no keychain, and keep
+// signing with Litecoin Core
+const MINIMUM_SCHEMA_VERSION: u64 = 1;
+
+// default number of addresses on each chain imported into Litecoin Core when
+// restoring
+pub(crate) const RESTORE_LOOKAHEAD: u64 = 100;
+
+pub(crate) const PASSWORD_ENV_VAR: &str = "ORD_WALLET_PASSWORD";
+
+define_table! { ACCOUNT_TO_KEYCHAIN, u32, KeychainValue }
 define_table! { RUNE_TO_ETCHING, u128, EtchingEntryValue }
+define_table! { SCRIPT_PUBKEY_TO_KEY_PATH, &[u8], (bool, u32) }
 define_table! { STATISTICS, u64, u64 }
 
 #[derive(Copy, Clone)]
 pub(crate) enum Statistic {
   Schema = 0,
+  NextReceiveIndex = 1,
+  NextChangeIndex = 2,
 }
 
 impl Statistic {
@@ -69,8 +100,12 @@ pub(crate) enum Maturity {
 pub(crate) struct Wallet {
   bitcoin_client: Client,
   database: Database,
+  has_address_history_index: bool,
   has_rune_index: bool,
   has_sat_index: bool,
+  keychain: Option<Keychain>,
+  password: OnceCell<String>,
+  pending_change_addresses: Cell<u64>,
   rpc_url: Url,
   utxos: BTreeMap<OutPoint, TxOut>,
   ord_client: reqwest::blocking::Client,
@@ -145,6 +180,52 @@ impl Wallet {
     )))
   }
 
+  /// Offsets of uncommon and rarer sats in `output`. Without a sat index no
+  /// sats are known to be rare.
+  pub(crate) fn get_rare_sat_offsets(&self, output: &OutPoint) -> Result<Vec<u64>> {
+    if !self.has_sat_index {
+      return Ok(Vec::new());
+    }
+
+    let mut offsets = Vec::new();
+    let mut offset = 0;
+    for (start, end) in self.get_output_sat_ranges(output)? {
+      if Sat(start).rarity() > Rarity::Common {
+        offsets.push(offset);
+      }
+      offset += end - start;
+    }
+
+    Ok(offsets)
+  }
+
+  /// Unlocked outputs without inscriptions, runes, or rare sats, which can be
+  /// spent without moving anything but sats.
+  pub(crate) fn get_common_cardinal_utxos(&self) -> Result<BTreeMap<OutPoint, TxOut>> {
+    let inscribed_outputs = self
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<HashSet<OutPoint>>();
+
+    let runic_outputs = self.get_runic_outputs()?;
+
+    let mut utxos = BTreeMap::new();
+    for (outpoint, txout) in &self.utxos {
+      if inscribed_outputs.contains(outpoint)
+        || runic_outputs.contains(outpoint)
+        || self.locked_utxos.contains_key(outpoint)
+        || !self.get_rare_sat_offsets(outpoint)?.is_empty()
+      {
+        continue;
+      }
+
+      utxos.insert(*outpoint, txout.clone());
+    }
+
+    Ok(utxos)
+  }
+
   pub(crate) fn bitcoin_client(&self) -> &Client {
     &self.bitcoin_client
   }
@@ -269,26 +350,253 @@ impl Wallet {
     &self,
     rune: Rune,
   ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    self.get_rune_by_query(SpacedRune { rune, spacers: 0 })
+  }
+
+  fn get_rune_by_id(
+    &self,
+    id: RuneId,
+  ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    self.get_rune_by_query(id)
+  }
+
+  fn get_rune_by_query(
+    &self,
+    query: impl Display,
+  ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    let response = self
+      .ord_client
+      .get(self.rpc_url.join(&format!("/rune/{query}")).unwrap())
+      .send()?;
+
+    if !response.status().is_success() {
+      return Ok(None);
+    }
+
+    let rune_json: api::Rune = serde_json::from_str(&response.text()?)?;
+
+    Ok(Some((rune_json.id, rune_json.entry, rune_json.parent)))
+  }
+
+  fn get_output(&self, outpoint: OutPoint) -> Result<api::Output> {
+    let response = self
+      .ord_client
+      .get(self.rpc_url.join(&format!("/output/{outpoint}")).unwrap())
+      .send()?;
+
+    if !response.status().is_success() {
+      bail!("failed to get output {outpoint}: {}", response.text()?);
+    }
+
+    Ok(serde_json::from_str(&response.text()?)?)
+  }
+
+  /// Get page `page` of the activity of `address`, most recent first.
+  pub(crate) fn get_address_history(
+    &self,
+    address: &Address<NetworkUnchecked>,
+    page: usize,
+  ) -> Result<api::AddressHistory> {
+    let address = address.clone().assume_checked();
+
     let response = self
       .ord_client
       .get(
         self
           .rpc_url
-          .join(&format!("/rune/{}", SpacedRune { rune, spacers: 0 }))
+          .join(&format!("/address/{address}/history/{page}"))
           .unwrap(),
       )
       .send()?;
 
     if !response.status().is_success() {
-      return Ok(None);
+      bail!(
+        "failed to get history of address {address}: {}",
+        response.text()?
+      );
     }
 
-    let rune_json: api::Rune = serde_json::from_str(&response.text()?)?;
+    Ok(serde_json::from_str(&response.text()?)?)
+  }
 
-    Ok(Some((rune_json.id, rune_json.entry, rune_json.parent)))
+  fn get_inscription(&self, inscription_id: InscriptionId) -> Result<api::Inscription> {
+    let response = self
+      .ord_client
+      .get(
+        self
+          .rpc_url
+          .join(&format!("/inscription/{inscription_id}"))
+          .unwrap(),
+      )
+      .send()?;
+
+    if !response.status().is_success() {
+      bail!(
+        "failed to get inscription {inscription_id}: {}",
+        response.text()?
+      );
+    }
+
+    Ok(serde_json::from_str(&response.text()?)?)
+  }
+
+  /// Get the output spent by `outpoint`, along with the inscriptions and runes
+  /// it holds. Outputs not in the wallet are looked up on the ord server.
+  pub(crate) fn get_output_assets(&self, outpoint: OutPoint) -> Result<(TxOut, api::Assets)> {
+    let fetched;
+    let output = match self.output_info.get(&outpoint) {
+      Some(output) => output,
+      None => {
+        fetched = self.get_output(outpoint)?;
+        &fetched
+      }
+    };
+
+    let mut inscriptions = BTreeMap::new();
+    for inscription_id in &output.inscriptions {
+      let satpoint = match self.inscription_info.get(inscription_id) {
+        Some(info) => info.satpoint,
+        None => self.get_inscription(*inscription_id)?.satpoint,
+      };
+
+      inscriptions.insert(*inscription_id, satpoint.offset);
+    }
+
+    let mut runes = BTreeMap::new();
+    for (spaced_rune, pile) in &output.runes {
+      let Some((id, ..)) = self.get_rune(spaced_rune.rune)? else {
+        bail!("rune {spaced_rune} not found");
+      };
+
+      runes.insert(
+        *spaced_rune,
+        api::RuneBalance {
+          id,
+          amount: pile.amount,
+          divisibility: pile.divisibility,
+          symbol: pile.symbol,
+        },
+      );
+    }
+
+    Ok((
+      TxOut {
+        value: output.value,
+        script_pubkey: output.script_pubkey.clone(),
+      },
+      api::Assets {
+        inscriptions,
+        runes,
+      },
+    ))
   }
 
+  /// Runes minted or premined by `transaction`. Etched runes are given the
+  /// default rune ID, which edicts use to refer to them.
+  fn get_created_runes(
+    &self,
+    transaction: &Transaction,
+  ) -> Result<Vec<(SpacedRune, api::RuneBalance)>> {
+    let Some(Artifact::Runestone(runestone)) = Runestone::decipher(transaction) else {
+      return Ok(Vec::new());
+    };
+
+    let mut created = Vec::new();
+
+    if let Some(id) = runestone.mint {
+      if let Some((id, entry, _)) = self.get_rune_by_id(id)? {
+        if let Some(amount) = entry.terms.and_then(|terms| terms.amount) {
+          created.push((
+            entry.spaced_rune,
+            api::RuneBalance {
+              id,
+              amount,
+              divisibility: entry.divisibility,
+              symbol: entry.symbol,
+            },
+          ));
+        }
+      }
+    }
+
+    if let Some(Etching {
+      divisibility,
+      premine: Some(premine),
+      rune: Some(rune),
+      spacers,
+      symbol,
+      ..
+    }) = runestone.etching
+    {
+      created.push((
+        SpacedRune {
+          rune,
+          spacers: spacers.unwrap_or_default(),
+        },
+        api::RuneBalance {
+          id: RuneId::default(),
+          amount: premine,
+          divisibility: divisibility.unwrap_or_default(),
+          symbol,
+        },
+      ));
+    }
+
+    Ok(created)
+  }
+
+  /// Annotate `psbt` with the inscriptions and runes on its inputs and where
+  /// they end up. Outputs in `prevouts`, for example those of a commit
+  /// transaction which has not been broadcast, are assumed to hold no assets.
+  pub(crate) fn annotate_psbt(
+    &self,
+    psbt: &mut Psbt,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+  ) -> Result {
+    let mut inputs = Vec::new();
+
+    for (txin, input) in psbt.unsigned_tx.input.iter().zip(&mut psbt.inputs) {
+      let (txout, assets) = match prevouts.get(&txin.previous_output) {
+        Some(txout) => (txout.clone(), api::Assets::default()),
+        None => self.get_output_assets(txin.previous_output)?,
+      };
+
+      if input.witness_utxo.is_none() && input.non_witness_utxo.is_none() {
+        input.witness_utxo = Some(txout.clone());
+      }
+
+      inputs.push((txout.value, assets));
+    }
+
+    let created = self.get_created_runes(&psbt.unsigned_tx)?;
+
+    annotation::annotate(psbt, &inputs, &created)
+  }
+
+  /// Get a fresh change address. Addresses taken from the keychain are only
+  /// marked as used once a transaction paying to them is broadcast, so that
+  /// dry runs do not use up change addresses.
   pub(crate) fn get_change_address(&self) -> Result<Address> {
+    if let Some(keychain) = &self.keychain {
+      let pending = self.pending_change_addresses.get();
+
+      self.pending_change_addresses.set(pending + 1);
+
+      let index = Self::next_index(&self.database, Statistic::NextChangeIndex)? + pending;
+
+      return Ok(
+        Self::import_addresses(
+          &self.database,
+          &self.bitcoin_client,
+          keychain,
+          true,
+          index..index + 1,
+          false,
+        )?
+        .remove(0),
+      );
+    }
+
     Ok(
       self
         .bitcoin_client
@@ -298,6 +606,611 @@ impl Wallet {
     )
   }
 
+  pub(crate) fn fund_raw_transaction(
+    &self,
+    fee_rate: FeeRate,
+    unfunded_transaction: &Transaction,
+  ) -> Result<Vec<u8>> {
+    // watch-only wallets have no keypool to draw change addresses from
+    let change_address = match self.keychain {
+      Some(_) => Some(self.get_change_address()?),
+      None => None,
+    };
+
+    fund_raw_transaction(
+      &self.bitcoin_client,
+      fee_rate,
+      unfunded_transaction,
+      change_address,
+    )
+  }
+
+  pub(crate) fn get_receive_address(&self) -> Result<Address<NetworkUnchecked>> {
+    if let Some(keychain) = &self.keychain {
+      return Ok(
+        Self::derive_addresses(
+          &self.database,
+          &self.bitcoin_client,
+          keychain,
+          false,
+          1,
+          false,
+        )?
+        .remove(0)
+        .as_unchecked()
+        .clone(),
+      );
+    }
+
+    Ok(
+      self
+        .bitcoin_client
+        .get_new_address(None, Some(bitcoincore_rpc::json::AddressType::Bech32))?,
+    )
+  }
+
+  fn next_index(database: &Database, statistic: Statistic) -> Result<u64> {
+    Ok(
+      database
+        .begin_read()?
+        .open_table(STATISTICS)?
+        .get(&statistic.key())?
+        .map(|next| next.value())
+        .unwrap_or_default(),
+    )
+  }
+
+  /// Derive the next `count` addresses on the receive or change chain, import
+  /// them into Litecoin Core, and mark them as used.
+  fn derive_addresses(
+    database: &Database,
+    client: &Client,
+    keychain: &Keychain,
+    change: bool,
+    count: u64,
+    rescan: bool,
+  ) -> Result<Vec<Address>> {
+    let statistic = if change {
+      Statistic::NextChangeIndex
+    } else {
+      Statistic::NextReceiveIndex
+    };
+
+    let next = Self::next_index(database, statistic)?;
+
+    let addresses = Self::import_addresses(
+      database,
+      client,
+      keychain,
+      change,
+      next..next + count,
+      rescan,
+    )?;
+
+    let wtx = database.begin_write()?;
+
+    wtx
+      .open_table(STATISTICS)?
+      .insert(&statistic.key(), &(next + count))?;
+
+    wtx.commit()?;
+
+    Ok(addresses)
+  }
+
+  /// Derive the addresses at `indices` on the receive or change chain, and
+  /// import them into Litecoin Core as watch-only, so that it tracks their
+  /// UTXOs. Core rescans the chain for every watched address if `rescan` is
+  /// set.
+  fn import_addresses(
+    database: &Database,
+    client: &Client,
+    keychain: &Keychain,
+    change: bool,
+    indices: Range<u64>,
+    rescan: bool,
+  ) -> Result<Vec<Address>> {
+    let wtx = database.begin_write()?;
+
+    let mut addresses = Vec::new();
+
+    {
+      let mut script_pubkey_to_key_path = wtx.open_table(SCRIPT_PUBKEY_TO_KEY_PATH)?;
+
+      for index in indices {
+        let index = u32::try_from(index)?;
+        let address = keychain.address(change, index)?;
+        script_pubkey_to_key_path.insert(address.script_pubkey().as_bytes(), (change, index))?;
+        addresses.push(address);
+      }
+    }
+
+    let requests = addresses
+      .iter()
+      .map(|address| ImportMultiRequest {
+        timestamp: if rescan {
+          Timestamp::Time(0)
+        } else {
+          Timestamp::Now
+        },
+        script_pubkey: Some(ImportMultiRequestScriptPubkey::Address(address)),
+        internal: Some(change),
+        watchonly: Some(true),
+        ..Default::default()
+      })
+      .collect::<Vec<ImportMultiRequest>>();
+
+    for result in client.import_multi(
+      &requests,
+      Some(&ImportMultiOptions {
+        rescan: Some(rescan),
+      }),
+    )? {
+      if !result.success {
+        bail!(
+          "failed to import addresses into Litecoin Core: {}",
+          result
+            .error
+            .map(|error| error.message)
+            .unwrap_or_else(|| "unknown error".into())
+        );
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(addresses)
+  }
+
+  /// Mark the change addresses that `transaction` pays to as used, once it has
+  /// been broadcast.
+  pub(crate) fn advance_change_index(database: &Database, transaction: &Transaction) -> Result {
+    let wtx = database.begin_write()?;
+
+    {
+      let script_pubkey_to_key_path = wtx.open_table(SCRIPT_PUBKEY_TO_KEY_PATH)?;
+      let mut statistics = wtx.open_table(STATISTICS)?;
+
+      let mut next = statistics
+        .get(&Statistic::NextChangeIndex.key())?
+        .map(|next| next.value())
+        .unwrap_or_default();
+
+      for output in &transaction.output {
+        if let Some(key_path) = script_pubkey_to_key_path.get(output.script_pubkey.as_bytes())? {
+          if let (true, index) = key_path.value() {
+            next = next.max(u64::from(index) + 1);
+          }
+        }
+      }
+
+      statistics.insert(&Statistic::NextChangeIndex.key(), &next)?;
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn change_addresses_used(&self, transaction: &Transaction) -> Result {
+    if self.keychain.is_some() {
+      Self::advance_change_index(&self.database, transaction)?;
+      self.pending_change_addresses.set(0);
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn send_raw_transaction(&self, transaction: &Transaction) -> Result<Txid> {
+    let txid = self.bitcoin_client.send_raw_transaction(transaction)?;
+
+    self.change_addresses_used(transaction)?;
+
+    Ok(txid)
+  }
+
+  pub(crate) fn load_keychain(database: &Database) -> Result<Option<Keychain>> {
+    let rtx = database.begin_read()?;
+
+    let account_to_keychain = match rtx.open_table(ACCOUNT_TO_KEYCHAIN) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(
+      account_to_keychain
+        .get(keychain::ACCOUNT)?
+        .map(|keychain| Keychain::load(keychain.value())),
+    )
+  }
+
+  /// Read the wallet password from `ORD_WALLET_PASSWORD`, or prompt for it on
+  /// the terminal. A `new` password, used to encrypt the keys of a wallet being
+  /// created, must not be empty, and is confirmed when prompted for.
+  pub(crate) fn read_password(new: bool) -> Result<String> {
+    let password = match env::var(PASSWORD_ENV_VAR) {
+      Ok(password) => password,
+      Err(env::VarError::NotPresent) => {
+        let password = rpassword::prompt_password("Wallet password: ").with_context(|| {
+          format!("failed to read wallet password, set {PASSWORD_ENV_VAR} to run non-interactively")
+        })?;
+
+        if new {
+          ensure!(
+            rpassword::prompt_password("Confirm wallet password: ")? == password,
+            "wallet passwords do not match"
+          );
+        }
+
+        password
+      }
+      Err(err) => bail!("invalid {PASSWORD_ENV_VAR}: {err}"),
+    };
+
+    ensure!(
+      !new || !password.is_empty(),
+      "wallet password must not be empty"
+    );
+
+    Ok(password)
+  }
+
+  /// The wallet password, which is only read once it is needed to sign.
+  fn password(&self) -> Result<&str> {
+    if self.password.get().is_none() {
+      self.password.set(Self::read_password(false)?).ok();
+    }
+
+    Ok(self.password.get().unwrap())
+  }
+
+  /// Look up the key paths of `script_pubkeys`, which are `None` for scripts
+  /// not derived from the wallet keychain.
+  pub(crate) fn script_key_paths<'a>(
+    database: &Database,
+    script_pubkeys: impl IntoIterator<Item = Option<&'a Script>>,
+  ) -> Result<Vec<Option<(bool, u32)>>> {
+    let rtx = database.begin_read()?;
+
+    let script_pubkey_to_key_path = rtx.open_table(SCRIPT_PUBKEY_TO_KEY_PATH)?;
+
+    script_pubkeys
+      .into_iter()
+      .map(|script_pubkey| {
+        let Some(script_pubkey) = script_pubkey else {
+          return Ok(None);
+        };
+
+        Ok(
+          script_pubkey_to_key_path
+            .get(script_pubkey.as_bytes())?
+            .map(|key_path| key_path.value()),
+        )
+      })
+      .collect()
+  }
+
+  /// The outputs spent by each input of `psbt`, taken from its witness or
+  /// non-witness UTXO.
+  pub(crate) fn psbt_prevouts(psbt: &Psbt) -> Vec<Option<TxOut>> {
+    psbt
+      .unsigned_tx
+      .input
+      .iter()
+      .zip(&psbt.inputs)
+      .map(|(txin, input)| {
+        input.witness_utxo.clone().or_else(|| {
+          input.non_witness_utxo.as_ref().and_then(|tx| {
+            tx.output
+              .get(usize::try_from(txin.previous_output.vout).ok()?)
+              .cloned()
+          })
+        })
+      })
+      .collect()
+  }
+
+  /// Sign the inputs of `psbt` that spend from `keychain` without Litecoin
+  /// Core or the ord server, using the outputs they spend recorded in the
+  /// PSBT. Returns true if every input is now finalized.
+  pub(crate) fn sign_psbt_offline(
+    database: &Database,
+    keychain: &Keychain,
+    password: &str,
+    psbt: &mut Psbt,
+  ) -> Result<bool> {
+    let prevouts = Self::psbt_prevouts(psbt);
+
+    let key_paths = Self::script_key_paths(
+      database,
+      prevouts.iter().map(|prevout| {
+        prevout
+          .as_ref()
+          .map(|txout| txout.script_pubkey.as_script())
+      }),
+    )?;
+
+    if key_paths.iter().all(Option::is_none) {
+      bail!("PSBT does not spend any outputs of this wallet");
+    }
+
+    let Some(prevouts) = prevouts.into_iter().collect::<Option<Vec<TxOut>>>() else {
+      bail!("PSBT is missing the outputs spent by one or more inputs");
+    };
+
+    keychain.update_psbt(psbt, &key_paths)?;
+
+    keychain.sign_psbt(
+      psbt,
+      &key_paths,
+      &prevouts,
+      password,
+      TapSighashType::Default,
+    )?;
+
+    Ok(
+      psbt
+        .inputs
+        .iter()
+        .all(|input| input.final_script_witness.is_some()),
+    )
+  }
+
+  fn key_paths(&self, transaction: &Transaction) -> Result<Vec<Option<(bool, u32)>>> {
+    Self::script_key_paths(
+      &self.database,
+      transaction.input.iter().map(|txin| {
+        self
+          .utxos
+          .get(&txin.previous_output)
+          .map(|txout| txout.script_pubkey.as_script())
+      }),
+    )
+  }
+
+  /// Sign the inputs of `psbt` that spend from the wallet keychain, returning
+  /// true if every input is now finalized. Other inputs, for example those of
+  /// wallets created before ord managed its own keys, are left for Litecoin
+  /// Core.
+  fn sign_with_keychain(
+    &self,
+    psbt: &mut Psbt,
+    extra_prevouts: &BTreeMap<OutPoint, TxOut>,
+    sign: bool,
+    sighash_type: TapSighashType,
+  ) -> Result<bool> {
+    let Some(keychain) = &self.keychain else {
+      return Ok(false);
+    };
+
+    let key_paths = self.key_paths(&psbt.unsigned_tx)?;
+
+    let prevouts = psbt
+      .unsigned_tx
+      .input
+      .iter()
+      .map(|txin| {
+        self
+          .utxos
+          .get(&txin.previous_output)
+          .or_else(|| extra_prevouts.get(&txin.previous_output))
+          .cloned()
+      })
+      .collect::<Vec<Option<TxOut>>>();
+
+    for (input, prevout) in psbt.inputs.iter_mut().zip(&prevouts) {
+      if input.witness_utxo.is_none() {
+        input.witness_utxo = prevout.clone();
+      }
+    }
+
+    keychain.update_psbt(psbt, &key_paths)?;
+
+    if sign && key_paths.iter().any(Option::is_some) {
+      let Some(prevouts) = prevouts.into_iter().collect::<Option<Vec<TxOut>>>() else {
+        bail!("cannot sign transaction spending outputs not in wallet");
+      };
+
+      keychain.sign_psbt(psbt, &key_paths, &prevouts, self.password()?, sighash_type)?;
+    }
+
+    Ok(
+      psbt
+        .inputs
+        .iter()
+        .all(|input| input.final_script_witness.is_some()),
+    )
+  }
+
+  /// Sign the inputs of `psbt` that the wallet controls with `sighash_type`,
+  /// using the keychain or Litecoin Core. Inputs which are already finalized,
+  /// such as the seller's input of an offer, are left untouched.
+  fn sign_psbt(
+    &self,
+    mut psbt: Psbt,
+    extra_prevouts: &BTreeMap<OutPoint, TxOut>,
+    sign: bool,
+    sighash_type: TapSighashType,
+  ) -> Result<Psbt> {
+    if self.sign_with_keychain(&mut psbt, extra_prevouts, sign, sighash_type)? {
+      return Ok(psbt);
+    }
+
+    let finalized = psbt
+      .inputs
+      .iter()
+      .map(|input| input.final_script_witness.clone())
+      .collect::<Vec<Option<Witness>>>();
+
+    let sighash_type = match sighash_type {
+      TapSighashType::Default => None,
+      sighash_type => Some(EcdsaSighashType::from_standard(sighash_type as u32)?.into()),
+    };
+
+    let mut psbt = Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD.decode(
+        self
+          .bitcoin_client()
+          .wallet_process_psbt(
+            &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+            Some(sign),
+            sighash_type,
+            None,
+          )?
+          .psbt,
+      )?,
+    )?;
+
+    for (input, witness) in psbt.inputs.iter_mut().zip(finalized) {
+      if witness.is_some() {
+        input.final_script_witness = witness;
+      }
+    }
+
+    Ok(psbt)
+  }
+
+  pub(crate) fn process_psbt(&self, transaction: &Transaction, sign: bool) -> Result<String> {
+    let psbt = self.sign_psbt(
+      Psbt::from_unsigned_tx(transaction.clone())?,
+      &BTreeMap::new(),
+      sign,
+      TapSighashType::Default,
+    )?;
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(psbt.serialize()))
+  }
+
+  /// Sign `transaction` with SIGHASH_SINGLE|ANYONECANPAY, so that each input
+  /// commits only to itself and the output at the same index, and a
+  /// counterparty can add their own inputs and outputs to complete it.
+  pub(crate) fn sign_offer(&self, transaction: &Transaction) -> Result<Psbt> {
+    let mut psbt = self.sign_psbt(
+      Psbt::from_unsigned_tx(transaction.clone())?,
+      &BTreeMap::new(),
+      true,
+      TapSighashType::SinglePlusAnyoneCanPay,
+    )?;
+
+    ensure!(
+      psbt
+        .inputs
+        .iter()
+        .all(|input| input.final_script_witness.is_some()),
+      "unable to sign offer",
+    );
+
+    self.annotate_psbt(&mut psbt, &BTreeMap::new())?;
+
+    Ok(psbt)
+  }
+
+  /// Sign the wallet's inputs of `psbt`, whose other inputs are already
+  /// signed and spend `extra_prevouts`, and broadcast it.
+  pub(crate) fn complete_psbt(
+    &self,
+    psbt: Psbt,
+    extra_prevouts: &BTreeMap<OutPoint, TxOut>,
+  ) -> Result<Txid> {
+    let psbt = self.sign_psbt(psbt, extra_prevouts, true, TapSighashType::Default)?;
+
+    let signed_tx = self
+      .bitcoin_client()
+      .finalize_psbt(
+        &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+        None,
+      )?
+      .hex
+      .ok_or_else(|| anyhow!("unable to sign transaction"))?;
+
+    self.send_raw_transaction(&consensus::encode::deserialize(&signed_tx)?)
+  }
+
+  /// Create an unsigned PSBT for `transaction`, annotated with the assets it
+  /// moves, to be signed elsewhere with `ord wallet sign-psbt`.
+  pub(crate) fn export_psbt(
+    &self,
+    transaction: &Transaction,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+  ) -> Result<String> {
+    let mut psbt = Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD.decode(self.process_psbt(transaction, false)?)?,
+    )?;
+
+    self.annotate_psbt(&mut psbt, prevouts)?;
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(psbt.serialize()))
+  }
+
+  /// Sign `transaction`, using the wallet keychain for inputs it controls and
+  /// Litecoin Core for any others. Inputs that already have a witness are left
+  /// untouched.
+  pub(crate) fn sign_raw_transaction(
+    &self,
+    transaction: &Transaction,
+    utxos: Option<&[SignRawTransactionInput]>,
+  ) -> Result<SignRawTransactionResult> {
+    let mut unsigned_transaction = transaction.clone();
+    for txin in &mut unsigned_transaction.input {
+      txin.witness = Witness::new();
+    }
+
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_transaction)?;
+
+    for (txin, input) in transaction.input.iter().zip(&mut psbt.inputs) {
+      if !txin.witness.is_empty() {
+        input.final_script_witness = Some(txin.witness.clone());
+      }
+    }
+
+    let extra_prevouts = utxos
+      .unwrap_or_default()
+      .iter()
+      .map(|utxo| {
+        (
+          OutPoint::new(utxo.txid, utxo.vout),
+          TxOut {
+            script_pubkey: utxo.script_pub_key.clone(),
+            value: utxo
+              .amount
+              .map(|amount| amount.to_sat())
+              .unwrap_or_default(),
+          },
+        )
+      })
+      .collect::<BTreeMap<OutPoint, TxOut>>();
+
+    let complete =
+      self.sign_with_keychain(&mut psbt, &extra_prevouts, true, TapSighashType::Default)?;
+
+    let mut signed = transaction.clone();
+
+    for (txin, input) in signed.input.iter_mut().zip(psbt.inputs) {
+      if let Some(witness) = input.final_script_witness {
+        txin.witness = witness;
+      }
+    }
+
+    if complete {
+      return Ok(SignRawTransactionResult {
+        hex: consensus::encode::serialize(&signed),
+        complete: true,
+        errors: None,
+      });
+    }
+
+    Ok(
+      self
+        .bitcoin_client()
+        .sign_raw_transaction_with_wallet(&signed, utxos, None)?,
+    )
+  }
+
+  pub(crate) fn has_address_history_index(&self) -> bool {
+    self.has_address_history_index
+  }
+
   pub(crate) fn has_sat_index(&self) -> bool {
     self.has_sat_index
   }
@@ -406,7 +1319,7 @@ impl Wallet {
   }
 
   pub(crate) fn send_etching(&self, rune: Rune, entry: &EtchingEntry) -> Result<batch::Output> {
-    match self.bitcoin_client().send_raw_transaction(&entry.reveal) {
+    match self.send_raw_transaction(&entry.reveal) {
       Ok(txid) => txid,
       Err(err) => {
         return Err(anyhow!(
@@ -460,86 +1373,52 @@ impl Wallet {
   //   Ok(())
   // }
 
-  pub(crate) fn initialize(name: String, settings: &Settings, _seed: [u8; 64]) -> Result {
-    Self::check_version(settings.bitcoin_rpc_client(None)?)?.create_wallet(
-      &name,
-      None,
-      Some(true),
-      None,
-      None,
+  pub(crate) fn initialize(
+    name: String,
+    settings: &Settings,
+    seed: [u8; 64],
+    password: &str,
+    restore_lookahead: Option<u64>,
+  ) -> Result {
+    let client = Self::check_version(settings.bitcoin_rpc_client(None)?)?;
+
+    // Litecoin Core only watches addresses derived by ord, and never holds keys
+    client.create_wallet(&name, Some(true), Some(true), None, None)?;
+
+    let keychain = Keychain::from_seed(
+      settings.chain().network(),
+      &seed,
+      password,
+      if settings.integration_test() {
+        argon2::Params::MIN_M_COST
+      } else {
+        keychain::KDF_MEMORY_COST
+      },
     )?;
 
-    // Functionally not supported with Litecoincore v21
-    // let network = settings.chain().network();
-    //
-    // let secp = Secp256k1::new();
-    //
-    // let master_private_key = ExtendedPrivKey::new_master(network, &seed)?;
-    //
-    // let fingerprint = master_private_key.fingerprint(&secp);
-    //
-    // let derivation_path = DerivationPath::master()
-    //   .child(ChildNumber::Hardened { index: 86 })
-    //   .child(ChildNumber::Hardened {
-    //     index: u32::from(network != Network::Bitcoin),
-    //   })
-    //   .child(ChildNumber::Hardened { index: 0 });
-    //
-    // let derived_private_key = master_private_key.derive_priv(&secp, &derivation_path)?;
-    //
-    // for change in [false, true] {
-    //   Self::derive_and_import_descriptor(
-    //     name.clone(),
-    //     settings,
-    //     &secp,
-    //     (fingerprint, derivation_path.clone()),
-    //     derived_private_key,
-    //     change,
-    //   )?;
-    // }
+    let database = Self::open_database(&name, settings)?;
+
+    {
+      let wtx = database.begin_write()?;
+      wtx
+        .open_table(ACCOUNT_TO_KEYCHAIN)?
+        .insert(keychain::ACCOUNT, keychain.clone().store())?;
+      wtx.commit()?;
+    }
+
+    if let Some(lookahead) = restore_lookahead {
+      let client = settings.bitcoin_rpc_client(Some(name))?;
+
+      // a single rescan after both chains have been imported finds every
+      // output sent to them
+      for change in [false, true] {
+        Self::derive_addresses(&database, &client, &keychain, change, lookahead, change)?;
+      }
+    }
 
     Ok(())
   }
 
-  // fn derive_and_import_descriptor(
-  //   name: String,
-  //   settings: &Settings,
-  //   secp: &Secp256k1<All>,
-  //   origin: (Fingerprint, DerivationPath),
-  //   derived_private_key: ExtendedPrivKey,
-  //   change: bool,
-  // ) -> Result {
-  //   let secret_key = DescriptorSecretKey::XPrv(DescriptorXKey {
-  //     origin: Some(origin),
-  //     xkey: derived_private_key,
-  //     derivation_path: DerivationPath::master().child(ChildNumber::Normal {
-  //       index: change.into(),
-  //     }),
-  //     wildcard: Wildcard::Unhardened,
-  //   });
-  //
-  //   let public_key = secret_key.to_public(secp)?;
-  //
-  //   let mut key_map = HashMap::new();
-  //   key_map.insert(public_key.clone(), secret_key);
-  //
-  //   let descriptor = miniscript::descriptor::Descriptor::new_tr(public_key, None)?;
-  //
-  //   settings
-  //     .bitcoin_rpc_client(Some(name.clone()))?
-  //     .import_descriptors(ImportDescriptors {
-  //       descriptor: descriptor.to_string_with_secret(&key_map),
-  //       timestamp: Timestamp::Now,
-  //       active: Some(true),
-  //       range: None,
-  //       next_index: None,
-  //       internal: Some(change),
-  //       label: None,
-  //     })?;
-  //
-  //   Ok(())
-  // }
-
   pub(crate) fn check_version(client: Client) -> Result<Client> {
     const MIN_VERSION: usize = 210000;
 
@@ -564,11 +1443,28 @@ impl Wallet {
     )
   }
 
-  pub(crate) fn open_database(wallet_name: &String, settings: &Settings) -> Result<Database> {
-    let path = settings
+  fn database_path(wallet_name: &String, settings: &Settings) -> PathBuf {
+    settings
       .data_dir()
       .join("wallets")
-      .join(format!("{wallet_name}.redb"));
+      .join(format!("{wallet_name}.redb"))
+  }
+
+  /// Open the database of a wallet which must already exist, for commands
+  /// which run without Litecoin Core or the ord server.
+  pub(crate) fn open_existing_database(
+    wallet_name: &String,
+    settings: &Settings,
+  ) -> Result<Database> {
+    if !Self::database_path(wallet_name, settings).exists() {
+      bail!("wallet `{wallet_name}` does not exist");
+    }
+
+    Self::open_database(wallet_name, settings)
+  }
+
+  pub(crate) fn open_database(wallet_name: &String, settings: &Settings) -> Result<Database> {
+    let path = Self::database_path(wallet_name, settings);
 
     if let Err(err) = fs::create_dir_all(path.parent().unwrap()) {
       bail!(
@@ -620,7 +1516,7 @@ impl Wallet {
             .unwrap_or(0);
 
           match schema_version.cmp(&SCHEMA_VERSION) {
-            cmp::Ordering::Less =>
+            cmp::Ordering::Less if schema_version < MINIMUM_SCHEMA_VERSION =>
               bail!(
                 "wallet database at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
                 path.display()
@@ -630,7 +1526,7 @@ impl Wallet {
                 "wallet database at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
                 path.display()
               ),
-            cmp::Ordering::Equal => {
+            cmp::Ordering::Less | cmp::Ordering::Equal => {
             }
           }
         }
@@ -644,7 +1540,9 @@ impl Wallet {
 
         let tx = database.begin_write()?;
 
+        tx.open_table(ACCOUNT_TO_KEYCHAIN)?;
         tx.open_table(RUNE_TO_ETCHING)?;
+        tx.open_table(SCRIPT_PUBKEY_TO_KEY_PATH)?;
 
         tx.open_table(STATISTICS)?
           .insert(&Statistic::Schema.key(), &SCHEMA_VERSION)?;
@@ -725,29 +1623,12 @@ impl Wallet {
     let unspent_outputs = self.utxos();
 
     let (txid, psbt) = if dry_run {
-      let psbt = self
-        .bitcoin_client()
-        .wallet_process_psbt(
-          &base64::engine::general_purpose::STANDARD
-            .encode(Psbt::from_unsigned_tx(unsigned_transaction.clone())?.serialize()),
-          Some(false),
-          None,
-          None,
-        )?
-        .psbt;
-
-      (unsigned_transaction.txid(), psbt)
+      (
+        unsigned_transaction.txid(),
+        self.export_psbt(&unsigned_transaction, &BTreeMap::new())?,
+      )
     } else {
-      let psbt = self
-        .bitcoin_client()
-        .wallet_process_psbt(
-          &base64::engine::general_purpose::STANDARD
-            .encode(Psbt::from_unsigned_tx(unsigned_transaction.clone())?.serialize()),
-          Some(true),
-          None,
-          None,
-        )?
-        .psbt;
+      let psbt = self.process_psbt(&unsigned_transaction, true)?;
 
       let signed_tx = self
         .bitcoin_client()
@@ -756,7 +1637,7 @@ impl Wallet {
         .ok_or_else(|| anyhow!("unable to sign transaction"))?;
 
       (
-        self.bitcoin_client().send_raw_transaction(&signed_tx)?,
+        self.send_raw_transaction(&consensus::encode::deserialize(&signed_tx)?)?,
         psbt,
       )
     };
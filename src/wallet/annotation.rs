@@ -0,0 +1,133 @@
+use {super::*, bitcoin::psbt::raw::ProprietaryKey, serde::de::DeserializeOwned};
+
+// inscriptions and runes on PSBT inputs and outputs, and runes created by the
+// transaction, are stored as JSON under proprietary keys, so they travel with
+// the PSBT to an offline signer
+const PREFIX: &[u8] = b"ord";
+const ASSETS: u8 = 0;
+const CREATED: u8 = 1;
+
+fn key(subtype: u8) -> ProprietaryKey {
+  ProprietaryKey {
+    prefix: PREFIX.to_vec(),
+    subtype,
+    key: Vec::new(),
+  }
+}
+
+fn get<T: DeserializeOwned>(
+  proprietary: &BTreeMap<ProprietaryKey, Vec<u8>>,
+  subtype: u8,
+) -> Result<Option<T>> {
+  proprietary
+    .get(&key(subtype))
+    .map(|value| serde_json::from_slice(value).context("invalid PSBT asset annotation"))
+    .transpose()
+}
+
+fn set(
+  proprietary: &mut BTreeMap<ProprietaryKey, Vec<u8>>,
+  subtype: u8,
+  value: &impl Serialize,
+) -> Result {
+  proprietary.insert(key(subtype), serde_json::to_vec(value)?);
+  Ok(())
+}
+
+pub(crate) fn created(psbt: &Psbt) -> Result<Vec<(SpacedRune, api::RuneBalance)>> {
+  Ok(get(&psbt.proprietary, CREATED)?.unwrap_or_default())
+}
+
+pub(crate) fn inputs(psbt: &Psbt) -> Result<Vec<Option<api::Assets>>> {
+  psbt
+    .inputs
+    .iter()
+    .map(|input| get(&input.proprietary, ASSETS))
+    .collect()
+}
+
+pub(crate) fn outputs(psbt: &Psbt) -> Result<Vec<Option<api::Assets>>> {
+  psbt
+    .outputs
+    .iter()
+    .map(|output| get(&output.proprietary, ASSETS))
+    .collect()
+}
+
+/// Annotate every input with the assets it spends, and every output with the
+/// assets it will receive.
+pub(crate) fn annotate(
+  psbt: &mut Psbt,
+  inputs: &[(u64, api::Assets)],
+  created: &[(SpacedRune, api::RuneBalance)],
+) -> Result {
+  for (input, (_value, assets)) in psbt.inputs.iter_mut().zip(inputs) {
+    set(&mut input.proprietary, ASSETS, assets)?;
+  }
+
+  if !created.is_empty() {
+    set(&mut psbt.proprietary, CREATED, &created)?;
+  }
+
+  let transfer = Transfer::simulate(&psbt.unsigned_tx, inputs, created);
+
+  for (output, assets) in psbt.outputs.iter_mut().zip(&transfer.outputs) {
+    set(&mut output.proprietary, ASSETS, assets)?;
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn annotations_round_trip() {
+    let mut psbt = Psbt::from_unsigned_tx(Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint(1),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![
+        TxOut {
+          value: 50,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 50,
+          script_pubkey: ScriptBuf::new(),
+        },
+      ],
+    })
+    .unwrap();
+
+    assert_eq!(inputs(&psbt).unwrap(), [None]);
+    assert_eq!(outputs(&psbt).unwrap(), [None, None]);
+
+    let assets = api::Assets {
+      inscriptions: [(inscription_id(1), 60)].into(),
+      ..default()
+    };
+
+    annotate(&mut psbt, &[(100, assets.clone())], &[]).unwrap();
+
+    let psbt = Psbt::deserialize(&psbt.serialize()).unwrap();
+
+    assert_eq!(inputs(&psbt).unwrap(), [Some(assets)]);
+    assert_eq!(
+      outputs(&psbt).unwrap(),
+      [
+        Some(api::Assets::default()),
+        Some(api::Assets {
+          inscriptions: [(inscription_id(1), 10)].into(),
+          ..default()
+        })
+      ]
+    );
+  }
+}
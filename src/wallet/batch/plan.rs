@@ -64,18 +64,30 @@ impl Plan {
     )?;
 
     if self.dry_run {
-      let commit_psbt = wallet
-        .bitcoin_client()
-        .wallet_process_psbt(
-          &base64::engine::general_purpose::STANDARD
-            .encode(Psbt::from_unsigned_tx(Self::remove_witnesses(commit_tx.clone()))?.serialize()),
-          Some(false),
-          None,
-          None,
-        )?
-        .psbt;
+      let commit_psbt =
+        wallet.export_psbt(&Self::remove_witnesses(commit_tx.clone()), &BTreeMap::new())?;
+
+      let mut reveal_psbt = Psbt::from_unsigned_tx(Self::remove_witnesses(reveal_tx.clone()))?;
 
-      let reveal_psbt = Psbt::from_unsigned_tx(Self::remove_witnesses(reveal_tx.clone()))?;
+      let commit_txid = commit_tx.txid();
+
+      wallet.annotate_psbt(
+        &mut reveal_psbt,
+        &commit_tx
+          .output
+          .iter()
+          .enumerate()
+          .map(|(vout, txout)| {
+            (
+              OutPoint {
+                txid: commit_txid,
+                vout: vout.try_into().unwrap(),
+              },
+              txout.clone(),
+            )
+          })
+          .collect(),
+      )?;
 
       return Ok(Some(Box::new(self.output(
         commit_tx.txid(),
@@ -89,12 +101,9 @@ impl Plan {
       ))));
     }
 
-    let signed_commit_tx = wallet
-      .bitcoin_client()
-      .sign_raw_transaction_with_wallet(&commit_tx, None, None)?
-      .hex;
+    let signed_commit_tx = wallet.sign_raw_transaction(&commit_tx, None)?.hex;
 
-    let result = wallet.bitcoin_client().sign_raw_transaction_with_wallet(
+    let result = wallet.sign_raw_transaction(
       &reveal_tx,
       Some(
         &commit_tx
@@ -110,7 +119,6 @@ impl Plan {
           })
           .collect::<Vec<SignRawTransactionInput>>(),
       ),
-      None,
     )?;
 
     ensure!(
@@ -124,9 +132,14 @@ impl Plan {
       // Self::backup_recovery_key(wallet, recovery_key_pair)?;
     }
 
-    let commit_txid = wallet
-      .bitcoin_client()
-      .send_raw_transaction(&signed_commit_tx)?;
+    let commit = consensus::encode::deserialize::<Transaction>(&signed_commit_tx)?;
+    let reveal = consensus::encode::deserialize::<Transaction>(&signed_reveal_tx)?;
+
+    let commit_txid = wallet.send_raw_transaction(&commit)?;
+
+    // once the commit transaction is broadcast the reveal transaction may be
+    // broadcast at any time, so its change addresses are used too
+    wallet.change_addresses_used(&reveal)?;
 
     if let Some(ref rune_info) = rune {
       wallet.bitcoin_client().lock_unspent(&[OutPoint {
@@ -134,9 +147,6 @@ impl Plan {
         vout: commit_vout.try_into().unwrap(),
       }])?;
 
-      let commit = consensus::encode::deserialize::<Transaction>(&signed_commit_tx)?;
-      let reveal = consensus::encode::deserialize::<Transaction>(&signed_reveal_tx)?;
-
       wallet.save_etching(
         &rune_info.rune.rune,
         &commit,
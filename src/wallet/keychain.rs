@@ -0,0 +1,551 @@
+use {
+  super::*,
+  argon2::{Algorithm, Argon2, Params, Version},
+  bitcoin::{
+    bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, Fingerprint},
+    key::{TapTweak, UntweakedKeyPair},
+    secp256k1::{
+      self,
+      rand::{self, RngCore},
+      Secp256k1, XOnlyPublicKey,
+    },
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    taproot::Signature,
+  },
+  chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+  },
+};
+
+pub(crate) const ACCOUNT: u32 = 0;
+pub(crate) const KDF_MEMORY_COST: u32 = 64 * 1024;
+
+const ENCRYPTION_VERSION: u8 = 1;
+const KDF_TIME_COST: u32 = 3;
+const KDF_PARALLELISM: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+const HEADER_LEN: usize = 1 + 4 + SALT_LEN + NONCE_LEN;
+
+/// BIP86 account keys derived from the wallet seed. The account xpub is kept
+/// in the clear so addresses can be derived without the wallet password, while
+/// the account xprv is only stored encrypted.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Keychain {
+  pub(crate) account_xpub: ExtendedPubKey,
+  pub(crate) encrypted_account_xprv: Vec<u8>,
+  pub(crate) fingerprint: Fingerprint,
+}
+
+pub(super) type KeychainValue = (
+  u32,     // fingerprint
+  Vec<u8>, // account xpub
+  Vec<u8>, // encrypted account xprv
+);
+
+impl Entry for Keychain {
+  type Value = KeychainValue;
+
+  fn load((fingerprint, account_xpub, encrypted_account_xprv): KeychainValue) -> Self {
+    Self {
+      account_xpub: ExtendedPubKey::decode(&account_xpub).unwrap(),
+      encrypted_account_xprv,
+      fingerprint: Fingerprint::from(fingerprint.to_be_bytes()),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      u32::from_be_bytes(self.fingerprint.to_bytes()),
+      self.account_xpub.encode().to_vec(),
+      self.encrypted_account_xprv,
+    )
+  }
+}
+
+impl Keychain {
+  pub(crate) fn from_seed(
+    network: Network,
+    seed: &[u8],
+    password: &str,
+    memory_cost: u32,
+  ) -> Result<Self> {
+    let secp = Secp256k1::new();
+
+    let master_private_key = ExtendedPrivKey::new_master(network, seed)?;
+
+    let account_xprv = master_private_key.derive_priv(&secp, &Self::account_path(network))?;
+
+    Ok(Self {
+      account_xpub: ExtendedPubKey::from_priv(&secp, &account_xprv),
+      encrypted_account_xprv: encrypt(&account_xprv.encode(), password, memory_cost)?,
+      fingerprint: master_private_key.fingerprint(&secp),
+    })
+  }
+
+  /// `m/86'/2'/0'` on mainnet, using Litecoin's SLIP-44 coin type, and
+  /// `m/86'/1'/0'` on test networks.
+  pub(crate) fn account_path(network: Network) -> DerivationPath {
+    DerivationPath::master()
+      .child(ChildNumber::Hardened { index: 86 })
+      .child(ChildNumber::Hardened {
+        index: if network == Network::Bitcoin { 2 } else { 1 },
+      })
+      .child(ChildNumber::Hardened { index: ACCOUNT })
+  }
+
+  fn key_path(change: bool, index: u32) -> Result<[ChildNumber; 2]> {
+    Ok([
+      ChildNumber::from_normal_idx(change.into())?,
+      ChildNumber::from_normal_idx(index)?,
+    ])
+  }
+
+  fn public_key(&self, change: bool, index: u32) -> Result<XOnlyPublicKey> {
+    Ok(XOnlyPublicKey::from(
+      self
+        .account_xpub
+        .derive_pub(
+          &Secp256k1::verification_only(),
+          &Self::key_path(change, index)?,
+        )?
+        .public_key,
+    ))
+  }
+
+  pub(crate) fn address(&self, change: bool, index: u32) -> Result<Address> {
+    Ok(Address::p2tr(
+      &Secp256k1::verification_only(),
+      self.public_key(change, index)?,
+      None,
+      self.account_xpub.network,
+    ))
+  }
+
+  fn decrypt(&self, password: &str) -> Result<ExtendedPrivKey> {
+    Ok(ExtendedPrivKey::decode(&decrypt(
+      &self.encrypted_account_xprv,
+      password,
+    )?)?)
+  }
+
+  /// Add the internal key and key origin of each input spending from this
+  /// keychain, so that the PSBT can be signed by other software.
+  pub(crate) fn update_psbt(&self, psbt: &mut Psbt, key_paths: &[Option<(bool, u32)>]) -> Result {
+    for (input, key_path) in psbt.inputs.iter_mut().zip(key_paths) {
+      let Some((change, index)) = *key_path else {
+        continue;
+      };
+
+      let public_key = self.public_key(change, index)?;
+
+      input.tap_internal_key = Some(public_key);
+
+      input.tap_key_origins.insert(
+        public_key,
+        (
+          Vec::new(),
+          (
+            self.fingerprint,
+            Self::account_path(self.account_xpub.network).extend(Self::key_path(change, index)?),
+          ),
+        ),
+      );
+    }
+
+    Ok(())
+  }
+
+  /// Sign and finalize each input spending from this keychain with a taproot
+  /// key path spend. Signing requires the outputs spent by every input, not
+  /// just our own, since they are committed to by the signature hash, unless
+  /// `sighash_type` is one of the `ANYONECANPAY` types.
+  pub(crate) fn sign_psbt(
+    &self,
+    psbt: &mut Psbt,
+    key_paths: &[Option<(bool, u32)>],
+    prevouts: &[TxOut],
+    password: &str,
+    sighash_type: TapSighashType,
+  ) -> Result {
+    let secp = Secp256k1::new();
+
+    let account_xprv = self.decrypt(password)?;
+
+    let mut sighash_cache = SighashCache::new(&psbt.unsigned_tx);
+
+    for (i, (input, key_path)) in psbt.inputs.iter_mut().zip(key_paths).enumerate() {
+      let Some((change, index)) = *key_path else {
+        continue;
+      };
+
+      if input.final_script_witness.is_some() {
+        continue;
+      }
+
+      let sighash = if matches!(
+        sighash_type,
+        TapSighashType::AllPlusAnyoneCanPay
+          | TapSighashType::NonePlusAnyoneCanPay
+          | TapSighashType::SinglePlusAnyoneCanPay
+      ) {
+        sighash_cache.taproot_key_spend_signature_hash(
+          i,
+          &Prevouts::One(i, &prevouts[i]),
+          sighash_type,
+        )?
+      } else {
+        sighash_cache.taproot_key_spend_signature_hash(i, &Prevouts::All(prevouts), sighash_type)?
+      };
+
+      let key_pair = UntweakedKeyPair::from_secret_key(
+        &secp,
+        &account_xprv
+          .derive_priv(&secp, &Self::key_path(change, index)?)?
+          .private_key,
+      )
+      .tap_tweak(&secp, None)
+      .to_inner();
+
+      let sig = secp.sign_schnorr(
+        &secp256k1::Message::from_slice(sighash.as_ref())
+          .expect("should be cryptographically secure hash"),
+        &key_pair,
+      );
+
+      input.final_script_witness = Some(Witness::from_slice(&[Signature {
+        sig,
+        hash_ty: sighash_type,
+      }
+      .to_vec()]));
+    }
+
+    Ok(())
+  }
+}
+
+/// Encrypt `plaintext` under `password`, returning a self-describing blob of
+/// version, KDF memory cost, salt, nonce, and ciphertext with its
+/// authentication tag.
+///
+/// The key is derived from the password with Argon2id and the plaintext sealed
+/// with XChaCha20-Poly1305, which also authenticates the header. The salt and
+/// nonce are fresh for every encryption.
+fn encrypt(plaintext: &[u8], password: &str, memory_cost: u32) -> Result<Vec<u8>> {
+  let mut salt = [0; SALT_LEN];
+  rand::thread_rng().fill_bytes(&mut salt);
+
+  let mut nonce = [0; NONCE_LEN];
+  rand::thread_rng().fill_bytes(&mut nonce);
+
+  let mut encrypted = Vec::with_capacity(HEADER_LEN + plaintext.len() + TAG_LEN);
+  encrypted.push(ENCRYPTION_VERSION);
+  encrypted.extend_from_slice(&memory_cost.to_be_bytes());
+  encrypted.extend_from_slice(&salt);
+  encrypted.extend_from_slice(&nonce);
+
+  let ciphertext = cipher(password, &salt, memory_cost)?
+    .encrypt(
+      XNonce::from_slice(&nonce),
+      Payload {
+        msg: plaintext,
+        aad: &encrypted,
+      },
+    )
+    .map_err(|_| anyhow!("failed to encrypt wallet key"))?;
+
+  encrypted.extend_from_slice(&ciphertext);
+
+  Ok(encrypted)
+}
+
+fn decrypt(encrypted: &[u8], password: &str) -> Result<Vec<u8>> {
+  ensure!(
+    encrypted.len() >= HEADER_LEN + TAG_LEN,
+    "encrypted wallet key is truncated"
+  );
+
+  ensure!(
+    encrypted[0] == ENCRYPTION_VERSION,
+    "unsupported wallet key encryption version {}",
+    encrypted[0]
+  );
+
+  let memory_cost = u32::from_be_bytes(encrypted[1..5].try_into().unwrap());
+
+  ensure!(
+    memory_cost <= KDF_MEMORY_COST,
+    "wallet key derivation memory cost {memory_cost} KiB exceeds maximum of {KDF_MEMORY_COST} KiB"
+  );
+
+  let salt = &encrypted[5..5 + SALT_LEN];
+  let nonce = &encrypted[5 + SALT_LEN..HEADER_LEN];
+  let (header, ciphertext) = encrypted.split_at(HEADER_LEN);
+
+  cipher(password, salt, memory_cost)?
+    .decrypt(
+      XNonce::from_slice(nonce),
+      Payload {
+        msg: ciphertext,
+        aad: header,
+      },
+    )
+    .map_err(|_| anyhow!("incorrect wallet password"))
+}
+
+fn cipher(password: &str, salt: &[u8], memory_cost: u32) -> Result<XChaCha20Poly1305> {
+  let params = Params::new(memory_cost, KDF_TIME_COST, KDF_PARALLELISM, None)
+    .map_err(|err| anyhow!("invalid wallet key derivation parameters: {err}"))?;
+
+  let mut key = [0; 32];
+
+  Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+    .hash_password_into(password.as_bytes(), salt, &mut key)
+    .map_err(|err| anyhow!("failed to derive wallet key: {err}"))?;
+
+  Ok(XChaCha20Poly1305::new(&key.into()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SEED: [u8; 64] = [7; 64];
+
+  #[test]
+  fn encryption_round_trips() {
+    let encrypted = encrypt(b"foo", "bar", Params::MIN_M_COST).unwrap();
+    assert_eq!(encrypted.len(), HEADER_LEN + 3 + TAG_LEN);
+    assert_eq!(decrypt(&encrypted, "bar").unwrap(), b"foo");
+  }
+
+  #[test]
+  fn encryption_is_salted() {
+    assert_ne!(
+      encrypt(b"foo", "bar", Params::MIN_M_COST).unwrap(),
+      encrypt(b"foo", "bar", Params::MIN_M_COST).unwrap()
+    );
+  }
+
+  #[test]
+  fn decryption_requires_correct_password() {
+    assert_eq!(
+      decrypt(&encrypt(b"foo", "bar", Params::MIN_M_COST).unwrap(), "baz")
+        .unwrap_err()
+        .to_string(),
+      "incorrect wallet password",
+    );
+  }
+
+  #[test]
+  fn decryption_detects_tampering() {
+    let mut encrypted = encrypt(b"foo", "bar", Params::MIN_M_COST).unwrap();
+    encrypted[HEADER_LEN] ^= 1;
+    assert!(decrypt(&encrypted, "bar").is_err());
+
+    let mut encrypted = encrypt(b"foo", "bar", Params::MIN_M_COST).unwrap();
+    encrypted[5] ^= 1;
+    assert!(decrypt(&encrypted, "bar").is_err());
+  }
+
+  #[test]
+  fn decryption_rejects_excessive_memory_cost() {
+    let mut encrypted = encrypt(b"foo", "bar", Params::MIN_M_COST).unwrap();
+    encrypted[1..5].copy_from_slice(&(KDF_MEMORY_COST + 1).to_be_bytes());
+    assert_eq!(
+      decrypt(&encrypted, "bar").unwrap_err().to_string(),
+      format!(
+        "wallet key derivation memory cost {} KiB exceeds maximum of {KDF_MEMORY_COST} KiB",
+        KDF_MEMORY_COST + 1
+      ),
+    );
+  }
+
+  #[test]
+  fn keychain_entry() {
+    let keychain = Keychain::from_seed(Network::Regtest, &SEED, "", Params::MIN_M_COST).unwrap();
+    assert_eq!(Keychain::load(keychain.clone().store()), keychain);
+  }
+
+  #[test]
+  fn account_path() {
+    assert_eq!(
+      Keychain::account_path(Network::Bitcoin).to_string(),
+      "m/86'/2'/0'"
+    );
+    assert_eq!(
+      Keychain::account_path(Network::Testnet).to_string(),
+      "m/86'/1'/0'"
+    );
+  }
+
+  #[test]
+  fn addresses_do_not_depend_on_password() {
+    let a = Keychain::from_seed(Network::Bitcoin, &SEED, "foo", Params::MIN_M_COST).unwrap();
+    let b = Keychain::from_seed(Network::Bitcoin, &SEED, "bar", Params::MIN_M_COST).unwrap();
+
+    assert_eq!(a.address(false, 0).unwrap(), b.address(false, 0).unwrap());
+    assert_ne!(a.address(false, 0).unwrap(), a.address(true, 0).unwrap());
+    assert_ne!(a.address(false, 0).unwrap(), a.address(false, 1).unwrap());
+    assert_eq!(
+      a.address(false, 0).unwrap().address_type(),
+      Some(bitcoin::AddressType::P2tr)
+    );
+  }
+
+  #[test]
+  fn signed_inputs_verify() {
+    let keychain = Keychain::from_seed(Network::Regtest, &SEED, "foo", Params::MIN_M_COST).unwrap();
+
+    let prevout = TxOut {
+      value: 50_000,
+      script_pubkey: keychain.address(true, 3).unwrap().script_pubkey(),
+    };
+
+    let mut psbt = Psbt::from_unsigned_tx(Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: 40_000,
+        script_pubkey: ScriptBuf::new(),
+      }],
+    })
+    .unwrap();
+
+    let key_paths = [Some((true, 3))];
+
+    keychain.update_psbt(&mut psbt, &key_paths).unwrap();
+
+    assert_eq!(
+      psbt.inputs[0].tap_internal_key,
+      Some(keychain.public_key(true, 3).unwrap())
+    );
+
+    assert!(keychain
+      .sign_psbt(
+        &mut psbt,
+        &key_paths,
+        &[prevout.clone()],
+        "bar",
+        TapSighashType::Default
+      )
+      .is_err());
+
+    keychain
+      .sign_psbt(
+        &mut psbt,
+        &key_paths,
+        &[prevout.clone()],
+        "foo",
+        TapSighashType::Default,
+      )
+      .unwrap();
+
+    let witness = psbt.inputs[0].final_script_witness.clone().unwrap();
+    assert_eq!(witness.len(), 1);
+
+    let secp = Secp256k1::new();
+
+    let sighash = SighashCache::new(&psbt.unsigned_tx)
+      .taproot_key_spend_signature_hash(0, &Prevouts::All(&[prevout]), TapSighashType::Default)
+      .unwrap();
+
+    let output_key = keychain
+      .public_key(true, 3)
+      .unwrap()
+      .tap_tweak(&secp, None)
+      .0
+      .to_inner();
+
+    secp
+      .verify_schnorr(
+        &secp256k1::schnorr::Signature::from_slice(&witness[0]).unwrap(),
+        &secp256k1::Message::from_slice(sighash.as_ref()).unwrap(),
+        &output_key,
+      )
+      .unwrap();
+  }
+
+  #[test]
+  fn anyonecanpay_signatures_commit_only_to_own_input() {
+    let keychain = Keychain::from_seed(Network::Regtest, &SEED, "foo", Params::MIN_M_COST).unwrap();
+
+    let prevout = TxOut {
+      value: 50_000,
+      script_pubkey: keychain.address(false, 0).unwrap().script_pubkey(),
+    };
+
+    let mut psbt = Psbt::from_unsigned_tx(Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: 100_000,
+        script_pubkey: ScriptBuf::new(),
+      }],
+    })
+    .unwrap();
+
+    keychain
+      .sign_psbt(
+        &mut psbt,
+        &[Some((false, 0))],
+        &[prevout.clone()],
+        "foo",
+        TapSighashType::SinglePlusAnyoneCanPay,
+      )
+      .unwrap();
+
+    let witness = psbt.inputs[0].final_script_witness.clone().unwrap();
+    assert_eq!(witness[0].len(), 65);
+    assert_eq!(witness[0][64], 0x83);
+
+    let mut tx = psbt.unsigned_tx.clone();
+
+    tx.input.push(TxIn {
+      previous_output: outpoint(1),
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::MAX,
+      witness: Witness::new(),
+    });
+
+    tx.output.push(TxOut {
+      value: 1_000,
+      script_pubkey: ScriptBuf::new(),
+    });
+
+    let sighash = SighashCache::new(&tx)
+      .taproot_key_spend_signature_hash(
+        0,
+        &Prevouts::One(0, &prevout),
+        TapSighashType::SinglePlusAnyoneCanPay,
+      )
+      .unwrap();
+
+    Secp256k1::new()
+      .verify_schnorr(
+        &secp256k1::schnorr::Signature::from_slice(&witness[0][..64]).unwrap(),
+        &secp256k1::Message::from_slice(sighash.as_ref()).unwrap(),
+        &keychain
+          .public_key(false, 0)
+          .unwrap()
+          .tap_tweak(&Secp256k1::new(), None)
+          .0
+          .to_inner(),
+      )
+      .unwrap();
+  }
+}
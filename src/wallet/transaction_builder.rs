@@ -443,7 +443,7 @@ impl TransactionBuilder {
     Self::estimate_vbytes_with(self.inputs.len(), self.outputs.to_vec())
   }
 
-  fn estimate_vbytes_with(inputs: usize, outputs: Vec<TxOut>) -> usize {
+  pub(crate) fn estimate_vbytes_with(inputs: usize, outputs: Vec<TxOut>) -> usize {
     Transaction {
       version: 2,
       lock_time: LockTime::ZERO,
@@ -47,6 +47,8 @@ impl WalletConstructor {
   pub(crate) fn build(self) -> Result<Wallet> {
     let database = Wallet::open_database(&self.name, &self.settings)?;
 
+    let keychain = Wallet::load_keychain(&database)?;
+
     let bitcoin_client = {
       let client =
         Wallet::check_version(self.settings.bitcoin_rpc_client(Some(self.name.clone()))?)?;
@@ -105,13 +107,17 @@ impl WalletConstructor {
     Ok(Wallet {
       bitcoin_client,
       database,
+      has_address_history_index: status.address_history_index,
       has_rune_index: status.rune_index,
       has_sat_index: status.sat_index,
       inscription_info,
       inscriptions,
+      keychain,
       locked_utxos,
       ord_client: self.ord_client,
       output_info,
+      password: OnceCell::new(),
+      pending_change_addresses: Cell::new(0),
       rpc_url: self.rpc_url,
       settings: self.settings,
       utxos,
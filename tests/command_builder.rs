@@ -213,6 +213,8 @@ impl CommandBuilder {
       }
     }
 
+    command.env("ORD_WALLET_PASSWORD", "hunter2");
+
     for (key, value) in &self.env {
       command.env(key, value);
     }
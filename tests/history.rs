@@ -0,0 +1,93 @@
+use {super::*, ord::subcommand::history::Output};
+
+#[test]
+fn history_shows_creation_and_transfers() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-inscription-history"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 ltc1qfmvk898k6jgfgp98dhsc5gvr9hpxl2ggd25ygk {inscription}",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!("--index-inscription-history history {inscription}"))
+    .core(&core)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.id, inscription);
+  assert_eq!(output.number, 0);
+  assert_eq!(output.transfers.len(), 2);
+
+  let created = &output.transfers[0];
+
+  assert_eq!(created.transaction, reveal);
+  assert_eq!(created.old_satpoint, None);
+  assert_eq!(
+    created.new_satpoint,
+    SatPoint {
+      outpoint: OutPoint {
+        txid: reveal,
+        vout: 0,
+      },
+      offset: 0,
+    }
+  );
+
+  let sent = &output.transfers[1];
+
+  assert_eq!(sent.transaction, send.txid);
+  assert_eq!(sent.old_satpoint, Some(created.new_satpoint));
+  assert_eq!(
+    sent.new_satpoint,
+    SatPoint {
+      outpoint: OutPoint {
+        txid: send.txid,
+        vout: 0,
+      },
+      offset: 0,
+    }
+  );
+  assert_eq!(
+    sent.address.as_deref(),
+    Some("ltc1qfmvk898k6jgfgp98dhsc5gvr9hpxl2ggd25ygk"),
+  );
+  assert_eq!(sent.height, created.height + 1);
+  assert_eq!(sent.timestamp, i64::from(sent.height));
+}
+
+#[test]
+fn history_requires_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("history 0000000000000000000000000000000000000000000000000000000000000000i0")
+    .core(&core)
+    .expected_stderr(
+      "error: history requires index created with `--index-inscription-history` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn history_of_unknown_inscription() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(
+    "--index-inscription-history history 0000000000000000000000000000000000000000000000000000000000000000i0",
+  )
+  .core(&core)
+  .expected_stderr(
+    "error: inscription 0000000000000000000000000000000000000000000000000000000000000000i0 not found\n",
+  )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
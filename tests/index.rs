@@ -90,3 +90,278 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn export_datasets_as_json_lines() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let temp_dir = Arc::new(TempDir::new().unwrap());
+
+  let output = CommandBuilder::new("--index-inscription-details index export --output-dir out")
+    .core(&core)
+    .temp_dir(temp_dir.clone())
+    .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  assert_eq!(output.start_height, 0);
+  assert_eq!(output.end_height, 4);
+
+  assert_eq!(
+    output
+      .files
+      .iter()
+      .map(|file| (file.dataset, file.rows))
+      .collect::<Vec<(ord::subcommand::index::export::Dataset, u64)>>(),
+    [
+      (ord::subcommand::index::export::Dataset::Blocks, 4),
+      (ord::subcommand::index::export::Dataset::Inscriptions, 1),
+      (ord::subcommand::index::export::Dataset::RuneBalances, 0),
+      (ord::subcommand::index::export::Dataset::Runes, 0),
+    ],
+  );
+
+  let inscriptions = fs::read_to_string(temp_dir.path().join("out/inscriptions.jsonl")).unwrap();
+
+  let row = serde_json::from_str::<serde_json::Value>(inscriptions.trim_end()).unwrap();
+
+  assert_eq!(row["id"], inscription.to_string());
+  assert_eq!(row["number"], 0);
+  assert_eq!(row["content_type"], "text/plain;charset=utf-8");
+
+  let output = CommandBuilder::new(
+    "index export --output-dir out --format csv --dataset blocks --start-height 2",
+  )
+  .core(&core)
+  .temp_dir(temp_dir.clone())
+  .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  assert_eq!(output.start_height, 2);
+  assert_eq!(output.files.len(), 1);
+  assert_eq!(output.files[0].rows, 2);
+
+  let blocks = fs::read_to_string(temp_dir.path().join("out/blocks.csv")).unwrap();
+
+  let mut lines = blocks.lines();
+
+  assert_eq!(
+    lines.next().unwrap(),
+    "height,hash,previous_blockhash,timestamp,inscriptions,runes",
+  );
+
+  let row = lines.next().unwrap();
+  assert!(row.starts_with("2,") && row.ends_with(",1,0"));
+
+  let row = lines.next().unwrap();
+  assert!(row.starts_with("3,") && row.ends_with(",0,0"));
+  assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn export_inscriptions_requires_inscription_details() {
+  let core = mockcore::spawn();
+
+  let temp_dir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("index export --output-dir out --dataset inscriptions")
+    .core(&core)
+    .temp_dir(temp_dir.clone())
+    .expected_stderr(
+      "error: exporting inscriptions requires index created with `--index-inscription-details` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert!(!temp_dir.path().join("out").exists());
+}
+
+#[test]
+fn export_resumes_from_checkpoint() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let temp_dir = Arc::new(TempDir::new().unwrap());
+
+  fs::create_dir(temp_dir.path().join("out")).unwrap();
+
+  fs::write(
+    temp_dir.path().join("out/export-checkpoint.json"),
+    r#"{"format":"jsonl","start_height":0,"end_height":2,"files":[{"dataset":"blocks","path":"out/blocks.jsonl","rows":7}]}"#,
+  )
+  .unwrap();
+
+  let output = CommandBuilder::new(
+    "index export --output-dir out --dataset blocks --dataset runes --end-height 2",
+  )
+  .core(&core)
+  .temp_dir(temp_dir.clone())
+  .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  assert_eq!(
+    output
+      .files
+      .iter()
+      .map(|file| (file.dataset, file.rows))
+      .collect::<Vec<(ord::subcommand::index::export::Dataset, u64)>>(),
+    [
+      (ord::subcommand::index::export::Dataset::Blocks, 7),
+      (ord::subcommand::index::export::Dataset::Runes, 0),
+    ],
+  );
+
+  assert!(!temp_dir.path().join("out/blocks.jsonl").exists());
+  assert!(temp_dir.path().join("out/runes.jsonl").exists());
+  assert!(!temp_dir.path().join("out/runes.jsonl.partial").exists());
+  assert!(!temp_dir.path().join("out/export-checkpoint.json").exists());
+}
+
+#[test]
+fn export_rune_balances_for_height_range_requires_rune_history() {
+  let core = mockcore::spawn();
+  core.mine_blocks(2);
+
+  CommandBuilder::new("index export --output-dir out --dataset rune-balances --start-height 1")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: exporting rune balances for a height range requires index created with `--index-rune-history` flag\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn snapshot_can_be_restored() {
+  let core = mockcore::spawn();
+  core.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot = tempdir.path().join("snapshot.redb");
+
+  let output = CommandBuilder::new(format!("index snapshot {}", snapshot.display()))
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  assert_eq!(output.block_count, 3);
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  let output = CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    index_path.display(),
+    snapshot.display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::restore::Output>();
+
+  assert_eq!(output.height, 2);
+  assert!(index_path.is_file());
+
+  CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    index_path.display(),
+    snapshot.display()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .stderr_regex(
+    "error: index already exists at `.*foo.redb`, delete it before restoring a snapshot\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn snapshot_not_in_node_chain_is_not_restored() {
+  let core = mockcore::spawn();
+  core.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot = tempdir.path().join("snapshot.redb");
+
+  CommandBuilder::new(format!("index snapshot {}", snapshot.display()))
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  core.invalidate_tip();
+  core.mine_blocks(1);
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    index_path.display(),
+    snapshot.display()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .stderr_regex("error: snapshot block [[:xdigit:]]{64} at height 2 is not in the node's chain\n")
+  .run_and_extract_stdout();
+
+  assert!(!index_path.exists());
+}
+
+#[test]
+fn rollback_with_undo_log() {
+  let core = mockcore::spawn();
+  core.mine_blocks(5);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!(
+    "--undo-depth 10 --index {} index update",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!(
+    "--undo-depth 10 --index {} index rollback --height 2",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::rollback::Output>();
+
+  assert_eq!(output.height, 2);
+
+  CommandBuilder::new(format!(
+    "--undo-depth 10 --index {} index rollback --height 3",
+    index_path.display()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .expected_stderr("error: cannot roll back index to height 3, which has not been indexed\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn rollback_requires_undo_log() {
+  let core = mockcore::spawn();
+  core.mine_blocks(5);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--index {} index rollback --height 2",
+    index_path.display()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: cannot roll back index without an undo log, update the index with `--undo-depth` to keep one\n",
+  )
+  .run_and_extract_stdout();
+}
@@ -343,6 +343,7 @@ fn get_output() {
         InscriptionId { txid, index: 2 },
       ],
       indexed: true,
+      mweb: false,
       runes: BTreeMap::new(),
       sat_ranges: Some(vec![
         (5000000000, 10000000000,),
@@ -494,19 +495,25 @@ fn get_status() {
   pretty_assert_eq!(
     status_json,
     api::Status {
+      address_history_index: false,
       address_index: false,
       blessed_inscriptions: 1,
       chain: Chain::Regtest,
       cursed_inscriptions: 0,
       height: Some(3),
       initial_sync_time: dummy_duration,
+      inscription_details_index: false,
+      inscription_history_index: false,
       inscription_index: true,
       inscriptions: 1,
       lost_sats: 0,
       minimum_rune_for_next_block: Rune(99239298574102199),
+      mweb_sats: 0,
+      rune_history_index: false,
       rune_index: true,
       runes: 0,
       sat_index: true,
+      search_index: false,
       started: dummy_started,
       transaction_index: false,
       unrecoverably_reorged: false,
@@ -755,3 +762,61 @@ fn get_decode_tx() {
     }
   );
 }
+
+#[test]
+fn simulate_psbt() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {inscription} --dry-run",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  ord.sync_server();
+
+  let response = reqwest::blocking::Client::new()
+    .post(ord.url().join("/simulate").unwrap())
+    .header(reqwest::header::ACCEPT, "application/json")
+    .body(send.psbt)
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let simulation = response.json::<api::Simulation>().unwrap();
+
+  assert_eq!(simulation.txid, send.txid);
+  assert_eq!(simulation.fee, send.fee);
+  assert_eq!(
+    simulation.outputs[0].address.as_ref().unwrap(),
+    &"bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+  );
+  assert_eq!(
+    simulation.outputs[0].assets.inscriptions,
+    [(inscription, 0)].into()
+  );
+  assert!(simulation.fee_inscriptions.is_empty());
+  assert_eq!(
+    simulation
+      .fee_sat_ranges
+      .unwrap()
+      .iter()
+      .map(|(start, end)| end - start)
+      .sum::<u64>(),
+    send.fee
+  );
+}
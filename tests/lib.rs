@@ -57,6 +57,7 @@ mod balances;
 mod decode;
 mod epochs;
 mod find;
+mod history;
 mod index;
 mod info;
 mod json_api;
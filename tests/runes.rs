@@ -141,3 +141,111 @@ fn two_runes() {
     }
   );
 }
+
+#[test]
+fn snapshot_at_past_height_requires_rune_history_index() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest runes snapshot {} --height 0",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: `ord runes snapshot --height` requires index created with `--index-rune-history` flag\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn snapshot_of_current_holders_does_not_require_rune_history_index() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etch = etch(&core, &ord, Rune(RUNE));
+
+  let holders = CommandBuilder::new(format!(
+    "--index-runes --regtest runes snapshot {}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<api::RuneHolders>();
+
+  assert_eq!(holders.height, core.height());
+  assert!(!holders.more);
+  assert_eq!(holders.stats, None);
+  assert_eq!(holders.holders.len(), 1);
+  assert_eq!(holders.holders[0].amount, 1000);
+  assert_eq!(holders.holders[0].outputs[0].txid, etch.output.reveal);
+}
+
+#[test]
+fn snapshot_shows_holders_at_past_height() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--regtest", "--index-runes", "--index-rune-history"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  let etch = etch(&core, &ord, Rune(RUNE));
+
+  let send = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 1000:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let before = CommandBuilder::new(format!(
+    "--index-runes --index-rune-history --regtest runes snapshot {} --height 7",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<api::RuneHolders>();
+
+  assert_eq!(before.height, 7);
+  assert_eq!(before.id, etch.id);
+  assert_eq!(before.holders.len(), 1);
+  assert_eq!(before.holders[0].amount, 1000);
+  assert!(before.holders[0].address.is_some());
+  assert!(before.holders[0].outputs.is_empty());
+
+  let after = CommandBuilder::new(format!(
+    "--index-runes --index-rune-history --regtest runes snapshot {}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<api::RuneHolders>();
+
+  assert_eq!(after.height, core.height());
+  assert_eq!(after.holders.len(), 1);
+  assert_eq!(after.holders[0].amount, 1000);
+  assert_eq!(after.holders[0].outputs[0].txid, send.txid);
+}
+
+#[test]
+fn snapshot_height_must_not_exceed_index_height() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  CommandBuilder::new(format!(
+    "--index-runes --index-rune-history --regtest runes snapshot {} --height 100",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .expected_stderr("error: height 100 is greater than index height 0\n")
+  .run_and_extract_stdout();
+}
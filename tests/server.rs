@@ -92,6 +92,288 @@ fn address_page_shows_single_rune() {
   );
 }
 
+#[test]
+fn address_history_shows_received_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_args(
+    &core,
+    &["--index-runes", "--index-address-history", "--regtest"],
+  );
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw";
+
+  let send = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 {address} 1000:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/address/{address}/history"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let history: api::AddressHistory = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert!(!history.more);
+  assert_eq!(history.page, 0);
+  assert_eq!(history.activity.len(), 1);
+
+  let activity = &history.activity[0];
+
+  assert_eq!(activity.transaction, send.txid);
+  assert_eq!(activity.received, 10000);
+  assert_eq!(activity.sent, 0);
+  assert!(activity.inscriptions_received.is_empty());
+  assert_eq!(
+    activity.runes_received,
+    [(
+      SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0,
+      },
+      Pile {
+        amount: 1000,
+        divisibility: 0,
+        symbol: Some('¢'),
+      },
+    )]
+    .into_iter()
+    .collect(),
+  );
+  assert!(activity.runes_sent.is_empty());
+
+  ord.assert_response_regex(
+    format!("/address/{address}/history"),
+    format!(
+      ".*<td><a class=monospace href=/tx/{}>.*<td>\\s*10000 sats.*: 1000.¢</div>.*",
+      send.txid,
+    ),
+  );
+}
+
+#[test]
+fn address_history_requires_index() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_args(&core, &["--index-addresses", "--regtest"]);
+
+  let response = ord.json_request("/address/bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw/history");
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert_eq!(
+    response.text().unwrap(),
+    "this server has no address history index",
+  );
+}
+
+#[test]
+fn inscription_history_shows_transfers() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-inscription-history"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 ltc1qfmvk898k6jgfgp98dhsc5gvr9hpxl2ggd25ygk {inscription}",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/inscription/{inscription}/history"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let history: api::InscriptionHistory = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert_eq!(history.id, inscription);
+  assert!(!history.more);
+  assert_eq!(history.page, 0);
+  assert_eq!(
+    history
+      .transfers
+      .iter()
+      .map(|transfer| transfer.transaction)
+      .collect::<Vec<Txid>>(),
+    [reveal, send.txid],
+  );
+  assert_eq!(
+    history.transfers[1].address.as_deref(),
+    Some("ltc1qfmvk898k6jgfgp98dhsc5gvr9hpxl2ggd25ygk"),
+  );
+
+  ord.assert_response_regex(
+    format!("/inscription/{inscription}/history"),
+    format!(
+      ".*<h1><a href=/inscription/{inscription}>Inscription 0</a> History</h1>.*\
+<td><a class=monospace href=/tx/{reveal}>.*<td>created</td>.*\
+<td><a class=monospace href=/tx/{}>.*\
+<td><a class=monospace href=/address/ltc1qfmvk898k6jgfgp98dhsc5gvr9hpxl2ggd25ygk>.*",
+      send.txid,
+    ),
+  );
+}
+
+#[test]
+fn inscription_history_requires_index() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  let response = ord.json_request(
+    "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0/history",
+  );
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert_eq!(
+    response.text().unwrap(),
+    "this server has no inscription history index",
+  );
+}
+
+#[test]
+fn rune_history_and_holders() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--regtest", "--index-runes", "--index-rune-history"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  let etch = etch(&core, &ord, Rune(RUNE));
+
+  CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 1000:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/rune/{}/history", Rune(RUNE)));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let history: api::RuneHistory = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert_eq!(history.id, etch.id);
+  assert_eq!(
+    history
+      .stats
+      .iter()
+      .map(|stats| (stats.height, stats.holders, stats.supply))
+      .collect::<Vec<(u32, u64, u128)>>(),
+    [(7, 1, 1000), (8, 1, 1000)],
+  );
+
+  let response = ord.json_request(format!("/rune/{}/holders?height=7", Rune(RUNE)));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let holders: api::RuneHolders = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert_eq!(holders.height, 7);
+  assert_eq!(holders.holders.len(), 1);
+  assert_eq!(holders.holders[0].amount, 1000);
+  assert_eq!(holders.stats.unwrap().holders, 1);
+
+  ord.assert_response_regex(
+    format!("/rune/{}/holders?height=7", Rune(RUNE)),
+    format!(
+      ".*<h1><a href=/rune/{0}>{0}</a> Holders</h1>.*<td>1000\u{A0}¢</td>.*",
+      Rune(RUNE)
+    ),
+  );
+
+  let response = ord.json_request(format!("/rune/{}/holders?height=100", Rune(RUNE)));
+
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn rune_holders_at_past_height_requires_index() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/rune/{}/holders?height=0", Rune(RUNE)));
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert_eq!(
+    response.text().unwrap(),
+    "this server has no rune history index"
+  );
+}
+
+#[test]
+fn current_rune_holders_are_grouped_by_address() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--regtest", "--index-runes", "--index-addresses"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let send = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 600:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/rune/{}/holders", Rune(RUNE)));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let holders: api::RuneHolders = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert_eq!(holders.height, core.height());
+  assert!(!holders.more);
+  assert_eq!(holders.page, 0);
+  assert_eq!(
+    holders
+      .holders
+      .iter()
+      .map(|holder| holder.amount)
+      .collect::<Vec<u128>>(),
+    [600, 400],
+  );
+  assert!(holders.holders[0].address.is_some());
+  assert_eq!(holders.holders[0].outputs.len(), 1);
+  assert_eq!(holders.holders[0].outputs[0].txid, send.txid);
+
+  let response = ord.json_request(format!("/rune/{}/holders/1", Rune(RUNE)));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let holders: api::RuneHolders = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert!(holders.holders.is_empty());
+  assert_eq!(holders.page, 1);
+}
+
 #[test]
 fn address_page_shows_multiple_runes() {
   let core = mockcore::builder().network(Network::Regtest).build();
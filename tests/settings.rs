@@ -21,16 +21,27 @@ fn default() {
   "hidden": \[\],
   "http_port": null,
   "index": ".*index\.redb",
+  "index_address_history": false,
   "index_addresses": false,
   "index_cache_size": \d+,
+  "index_inscription_details": false,
+  "index_inscription_history": false,
+  "index_rune_history": false,
   "index_runes": false,
   "index_sats": false,
+  "index_search": false,
   "index_transactions": false,
   "integration_test": false,
+  "max_savepoints": 2,
   "no_index_inscriptions": false,
+  "read_block_files": false,
+  "savepoint_interval": 10,
   "server_password": null,
   "server_url": null,
-  "server_username": null
+  "server_username": null,
+  "snapshot_reload": false,
+  "undo_depth": null,
+  "webhooks": null
 \}
 "#,
     )
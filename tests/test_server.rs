@@ -2,7 +2,7 @@ use {
   super::*,
   axum_server::Handle,
   bitcoincore_rpc::{Auth, Client, RpcApi},
-  ord::{parse_ord_server_args, Index},
+  ord::parse_ord_server_args,
   reqwest::blocking::Response,
 };
 
@@ -50,13 +50,17 @@ impl TestServer {
       ord_server_args.join(" "),
     ));
 
-    let index = Arc::new(Index::open(&settings).unwrap());
+    let (index, event_receiver) = server.open_index(&settings).unwrap();
     let ord_server_handle = Handle::new();
 
     {
       let index = index.clone();
       let ord_server_handle = ord_server_handle.clone();
-      thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+      thread::spawn(|| {
+        server
+          .run(settings, index, ord_server_handle, event_receiver)
+          .unwrap()
+      });
     }
 
     for i in 0.. {
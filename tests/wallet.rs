@@ -3,14 +3,19 @@ use super::*;
 mod authentication;
 mod balance;
 mod batch_command;
+mod broadcast;
 mod burn;
+mod burn_runes;
 mod cardinals;
+mod consolidate;
 mod create;
 mod dump;
 mod inscribe;
 mod inscriptions;
+mod inspect_psbt;
 mod label;
 mod mint;
+mod offer;
 mod outputs;
 #[cfg(unix)]
 mod pending;
@@ -22,4 +27,7 @@ mod runics;
 mod sats;
 mod selection;
 mod send;
+mod send_many;
+mod sign_psbt;
+mod split;
 mod transactions;
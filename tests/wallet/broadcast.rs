@@ -0,0 +1,68 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{broadcast::Output, create},
+};
+
+#[test]
+fn broadcast_finalizes_and_sends_psbt() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("wallet create")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .run_and_deserialize_output::<create::Output>();
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {inscription} --dry-run",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  assert!(core.mempool().is_empty());
+
+  let output = CommandBuilder::new(format!("wallet broadcast {}", send.psbt))
+    .temp_dir(tempdir)
+    .core(&core)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.txid, send.txid);
+  assert_eq!(core.mempool().len(), 1);
+  assert_eq!(core.mempool()[0].txid(), send.txid);
+}
+
+#[test]
+fn broadcast_requires_existing_wallet() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 1btc --dry-run",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  CommandBuilder::new(format!("wallet broadcast {}", send.psbt))
+    .core(&core)
+    .expected_stderr("error: wallet `ord` does not exist\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
@@ -1,4 +1,7 @@
-use super::*;
+use {
+  super::*,
+  ord::{outgoing::Outgoing, subcommand::wallet::burn::Output as Burn},
+};
 
 #[test]
 fn inscriptions_can_be_burned() {
@@ -18,10 +21,12 @@ fn inscriptions_can_be_burned() {
     .core(&core)
     .ord(&ord)
     .stdout_regex(r".*")
-    .run_and_deserialize_output::<Send>();
+    .run_and_deserialize_output::<Burn>();
 
   let txid = core.mempool()[0].txid();
   assert_eq!(txid, output.txid);
+  assert_eq!(output.outgoing, Outgoing::InscriptionId(inscription));
+  assert_eq!(output.outgoings, [Outgoing::InscriptionId(inscription)]);
 
   core.mine_blocks(1);
 
@@ -231,3 +236,66 @@ fn large_postage_is_protected() {
   .expected_exit_code(1)
   .run_and_extract_stdout();
 }
+
+#[test]
+fn multiple_inscriptions_can_be_burned_at_once() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (a, _) = inscribe(&core, &ord);
+  let (b, _) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new(format!("wallet burn --fee-rate 1 {a} {b}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Burn>();
+
+  assert_eq!(output.outgoing, Outgoing::InscriptionId(a));
+  assert_eq!(
+    output.outgoings,
+    [Outgoing::InscriptionId(a), Outgoing::InscriptionId(b)]
+  );
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.txid(), output.txid);
+  assert!(tx.output[0].script_pubkey.is_op_return());
+  assert_eq!(tx.output[0].value, 2 * TARGET_POSTAGE.to_sat());
+
+  core.mine_blocks(1);
+
+  for inscription in [a, b] {
+    let response = ord.json_request(format!("/inscription/{inscription}"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let inscription = response.json::<api::Inscription>().unwrap();
+
+    assert_eq!(inscription.satpoint.outpoint.txid, output.txid);
+    assert!(inscription.charms.contains(&Charm::Burned));
+  }
+}
+
+#[test]
+fn postage_cannot_be_set_when_burning_multiple_inscriptions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (a, _) = inscribe(&core, &ord);
+  let (b, _) = inscribe(&core, &ord);
+
+  CommandBuilder::new(format!(
+    "wallet burn --fee-rate 1 --postage 1000sat {a} {b}"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: `--postage` cannot be used when burning more than one inscription\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -0,0 +1,117 @@
+use {
+  super::*,
+  ord::{outgoing::Outgoing, subcommand::wallet::burn::Output},
+};
+
+#[test]
+fn runes_can_be_burned() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etch = etch(&core, &ord, Rune(RUNE));
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet burn-runes --fee-rate 1 300:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output.outgoing,
+    [Outgoing::Rune {
+      decimal: "300".parse().unwrap(),
+      rune: SpacedRune::new(Rune(RUNE), 0),
+    }]
+  );
+
+  core.mine_blocks(1);
+
+  let tx = core.tx_by_id(output.txid);
+
+  pretty_assert_eq!(
+    Runestone::decipher(&tx).unwrap(),
+    Artifact::Runestone(Runestone {
+      edicts: vec![Edict {
+        id: etch.id,
+        amount: 300,
+        output: 0,
+      }],
+      ..default()
+    }),
+  );
+
+  let response = ord.json_request(format!("/rune/{}", Rune(RUNE)));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  assert_eq!(response.json::<api::Rune>().unwrap().entry.burned, 300);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  pretty_assert_eq!(
+    balances,
+    ord::subcommand::balances::Output {
+      runes: [(
+        SpacedRune::new(Rune(RUNE), 0),
+        [(
+          OutPoint {
+            txid: output.txid,
+            vout: 1,
+          },
+          Pile {
+            amount: 700,
+            divisibility: 0,
+            symbol: Some('¢'),
+          },
+        )]
+        .into(),
+      )]
+      .into(),
+    }
+  );
+}
+
+#[test]
+fn burning_more_runes_than_held_is_an_error() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet burn-runes --fee-rate 1 1001:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: insufficient `AAAAAAAAAAAAA` balance, only 1000\u{A0}¢ in wallet\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn only_rune_amounts_can_be_burned_with_burn_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("--regtest --index-runes wallet burn-runes --fee-rate 1 1btc")
+    .core(&core)
+    .ord(&ord)
+    .stderr_regex("error: 1 .* is not a rune amount\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -0,0 +1,66 @@
+use {super::*, ord::subcommand::wallet::consolidate::Output};
+
+#[test]
+fn consolidate_merges_cardinal_outputs() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new("wallet consolidate --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.txid(), output.txid);
+  assert_eq!(tx.input.len(), output.inputs);
+  assert!(output.inputs >= 2);
+  assert!(tx
+    .input
+    .iter()
+    .all(|txin| txin.previous_output.txid != reveal));
+  assert_eq!(tx.output.len(), 1);
+  assert_eq!(tx.output[0].value, output.value);
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/inscription/{inscription}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  assert_eq!(
+    response.json::<api::Inscription>().unwrap().satpoint,
+    SatPoint {
+      outpoint: OutPoint {
+        txid: reveal,
+        vout: 0,
+      },
+      offset: 0,
+    }
+  );
+}
+
+#[test]
+fn consolidate_requires_two_cardinal_outputs() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet consolidate --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: wallet contains fewer than two cardinal outputs to consolidate\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -13,6 +13,20 @@ fn create() {
   assert!(core.wallets().contains("ord"));
 }
 
+#[test]
+fn wallet_password_must_not_be_empty() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("wallet create")
+    .env("ORD_WALLET_PASSWORD", "")
+    .core(&core)
+    .expected_stderr("error: wallet password must not be empty\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert!(!core.wallets().contains("ord"));
+}
+
 #[test]
 fn seed_phrases_are_twelve_words_long() {
   let Output { mnemonic, .. } = CommandBuilder::new("wallet create")
@@ -0,0 +1,104 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{absolute::LockTime, psbt::Psbt, ScriptBuf, Transaction, TxIn, TxOut},
+  ord::subcommand::wallet::{create, inspect_psbt::Output},
+};
+
+#[test]
+fn inspect_psbt_shows_where_inscriptions_go() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("wallet create")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .run_and_deserialize_output::<create::Output>();
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {inscription} --dry-run",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let output = CommandBuilder::new(format!("wallet inspect-psbt {}", send.psbt))
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output.inputs[0].assets.as_ref().unwrap().inscriptions,
+    [(inscription, 0)].into()
+  );
+  assert_eq!(
+    output.outputs[0].address.as_ref().unwrap(),
+    &"bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+  );
+  assert_eq!(
+    output.outputs[0].assets.as_ref().unwrap().inscriptions,
+    [(inscription, 0)].into()
+  );
+  assert!(!output.inputs[0].signed);
+  assert_eq!(output.fee, Some(send.fee));
+  assert!(output.burned.is_empty());
+  assert_eq!(output.warnings, Vec::<String>::new());
+}
+
+#[test]
+fn inspect_psbt_warns_about_inputs_without_annotations() {
+  let core = mockcore::spawn();
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("wallet create")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .run_and_deserialize_output::<create::Output>();
+
+  let psbt = Psbt::from_unsigned_tx(Transaction {
+    version: 2,
+    lock_time: LockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: OutPoint::null(),
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::MAX,
+      witness: Witness::new(),
+    }],
+    output: vec![TxOut {
+      value: 1000,
+      script_pubkey: ScriptBuf::new(),
+    }],
+  })
+  .unwrap();
+
+  let output = CommandBuilder::new(format!(
+    "wallet inspect-psbt {}",
+    base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+  ))
+  .temp_dir(tempdir)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.inputs[0].value, None);
+  assert_eq!(output.inputs[0].assets, None);
+  assert_eq!(output.fee, None);
+  assert_eq!(
+    output.warnings,
+    [format!(
+      "input 0 spending {} is not annotated, inscriptions and runes on it are unknown",
+      OutPoint::null()
+    )]
+  );
+}
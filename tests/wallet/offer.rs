@@ -0,0 +1,112 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::psbt::Psbt,
+  ord::subcommand::wallet::offer::{accept, create},
+};
+
+#[test]
+fn inscriptions_can_be_sold_with_offers() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let offer = CommandBuilder::new(format!("wallet offer create --amount 1btc {inscription}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<create::Output>();
+
+  let psbt = Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(&offer.psbt)
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(psbt.unsigned_tx.input.len(), 1);
+  assert_eq!(psbt.unsigned_tx.input[0].previous_output, offer.outpoint);
+  assert_eq!(psbt.unsigned_tx.output.len(), 1);
+  assert_eq!(psbt.unsigned_tx.output[0].value, COIN_VALUE);
+  assert_eq!(
+    psbt.inputs[0]
+      .final_script_witness
+      .as_ref()
+      .unwrap()
+      .nth(0)
+      .unwrap()
+      .last(),
+    Some(&0x83)
+  );
+
+  let accept = CommandBuilder::new(format!(
+    "wallet offer accept --amount 1btc --fee-rate 1 --psbt {} {inscription}",
+    offer.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<accept::Output>();
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.txid(), accept.txid);
+  assert_eq!(tx.input[1].previous_output, offer.outpoint);
+  assert_eq!(tx.output[1], psbt.unsigned_tx.output[0]);
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/inscription/{inscription}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  assert_eq!(
+    response
+      .json::<api::Inscription>()
+      .unwrap()
+      .satpoint
+      .outpoint,
+    OutPoint {
+      txid: accept.txid,
+      vout: 0
+    }
+  );
+}
+
+#[test]
+fn offers_for_a_different_amount_are_rejected() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let offer = CommandBuilder::new(format!("wallet offer create --amount 2btc {inscription}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<create::Output>();
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --amount 1btc --fee-rate 1 --psbt {} {inscription}",
+    offer.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .stderr_regex("error: offer asks for 2 .*, not 1 .*\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
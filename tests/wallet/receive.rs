@@ -1,4 +1,7 @@
-use {super::*, ord::subcommand::wallet::receive};
+use {
+  super::*,
+  ord::subcommand::wallet::{create, receive},
+};
 
 #[test]
 fn receive() {
@@ -18,3 +21,31 @@ fn receive() {
     .unwrap()
     .is_valid_for_network(Network::Bitcoin));
 }
+
+#[test]
+fn receive_addresses_are_derived_from_seed_and_watched_by_core() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("wallet create")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .run_and_deserialize_output::<create::Output>();
+
+  let output = CommandBuilder::new("wallet receive --number 2")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<receive::Output>();
+
+  assert_eq!(output.addresses.len(), 2);
+  assert_ne!(output.addresses[0], output.addresses[1]);
+
+  for address in output.addresses {
+    let address = address.assume_checked();
+    assert_eq!(address.address_type(), Some(bitcoin::AddressType::P2tr));
+    assert!(core.state().receive_addresses.contains(&address));
+  }
+}
@@ -1,3 +1,129 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{create, receive},
+};
+
+fn create_and_receive(args: &str) -> (create::Output, Address<NetworkUnchecked>) {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let output = CommandBuilder::new(format!("wallet create {args}"))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .run_and_deserialize_output::<create::Output>();
+
+  let receive::Output { addresses } = CommandBuilder::new("wallet receive")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output();
+
+  (output, addresses[0].clone())
+}
+
+#[test]
+fn restore_from_mnemonic_watches_same_addresses() {
+  let (create::Output { mnemonic, .. }, address) = create_and_receive("");
+
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(["wallet", "restore", "--from", "mnemonic"])
+    .stdin(mnemonic.to_string().into())
+    .core(&core)
+    .run_and_extract_stdout();
+
+  assert!(core
+    .state()
+    .receive_addresses
+    .contains(&address.assume_checked()));
+}
+
+#[test]
+fn restore_from_mnemonic_uses_passphrase() {
+  let (create::Output { mnemonic, .. }, address) = create_and_receive("--passphrase foo");
+
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(["wallet", "restore", "--from", "mnemonic"])
+    .stdin(mnemonic.to_string().into())
+    .core(&core)
+    .run_and_extract_stdout();
+
+  assert!(!core
+    .state()
+    .receive_addresses
+    .contains(&address.clone().assume_checked()));
+
+  let core = mockcore::spawn();
+
+  CommandBuilder::new([
+    "wallet",
+    "restore",
+    "--passphrase",
+    "foo",
+    "--from",
+    "mnemonic",
+  ])
+  .stdin(mnemonic.to_string().into())
+  .core(&core)
+  .run_and_extract_stdout();
+
+  assert!(core
+    .state()
+    .receive_addresses
+    .contains(&address.assume_checked()));
+}
+
+#[test]
+fn restore_from_mnemonic_watches_lookahead_addresses() {
+  let core = mockcore::spawn();
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let create::Output { mnemonic, .. } = CommandBuilder::new("wallet create")
+    .temp_dir(tempdir)
+    .core(&core)
+    .run_and_deserialize_output::<create::Output>();
+
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(["wallet", "restore", "--from", "mnemonic"])
+    .stdin(mnemonic.to_string().into())
+    .core(&core)
+    .run_and_extract_stdout();
+
+  assert_eq!(core.state().receive_addresses.len(), 100);
+  assert_eq!(core.state().change_addresses.len(), 100);
+
+  let core = mockcore::spawn();
+
+  CommandBuilder::new([
+    "wallet",
+    "restore",
+    "--from",
+    "mnemonic",
+    "--lookahead",
+    "5",
+  ])
+  .stdin(mnemonic.to_string().into())
+  .core(&core)
+  .run_and_extract_stdout();
+
+  assert_eq!(core.state().receive_addresses.len(), 5);
+  assert_eq!(core.state().change_addresses.len(), 5);
+}
+
+#[test]
+fn restore_from_descriptor_is_not_supported() {
+  CommandBuilder::new("wallet restore --from descriptor")
+    .core(&mockcore::spawn())
+    .expected_exit_code(1)
+    .stderr_regex("error: Descriptor wallets are not supported.*")
+    .run_and_extract_stdout();
+}
+
 // use {super::*, ord::subcommand::wallet::create};
 //
 // #[ignore] // Litecoincore does not support descriptors
@@ -360,6 +360,7 @@ inscriptions:
         },
       ],
       indexed: true,
+      mweb: false,
       runes: BTreeMap::new(),
       sat_ranges: Some(vec![(5_000_000_000, 5_000_030_000)]),
       script_pubkey: destination.payload.script_pubkey(),
@@ -436,6 +437,110 @@ fn inscriptions_cannot_be_sent_by_satpoint() {
   .run_and_extract_stdout();
 }
 
+#[test]
+fn inscriptions_cannot_be_sent_to_mweb_addresses() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let address = "ltcmweb1qq0xhpt8dwpqw4vjxc53ctlx9d5x0yesj4f8n9l7gaqzw6nxtl9u6cqjjcw9sxclz9mzkkxfwhh9zyek8ldgqfm0lqmpz4rxtj3nh9zmg4zzqw4syfh";
+
+  CommandBuilder::new(format!("wallet send --fee-rate 1 {address} {inscription}"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!(
+      "error: refusing to send {inscription} to MWEB address {address}: ordinals and runes cannot be tracked inside MWEB\n"
+    ))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn litoshis_cannot_be_sent_to_mweb_addresses_from_watch_only_wallets() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let address = "ltcmweb1qq0xhpt8dwpqw4vjxc53ctlx9d5x0yesj4f8n9l7gaqzw6nxtl9u6cqjjcw9sxclz9mzkkxfwhh9zyek8ldgqfm0lqmpz4rxtj3nh9zmg4zzqw4syfh";
+
+  let locked = core.get_locked();
+
+  CommandBuilder::new(format!("wallet send --fee-rate 1 {address} 1btc"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!(
+      "error: sending to MWEB address {address} requires Litecoin Core to sign, but Core wallet `ord` is watch-only\n"
+    ))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+  assert_eq!(core.get_locked(), locked);
+}
+
+#[test]
+fn litoshis_can_be_sent_to_mweb_addresses() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.state().watch_only_wallets.clear();
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let address = "ltcmweb1qq0xhpt8dwpqw4vjxc53ctlx9d5x0yesj4f8n9l7gaqzw6nxtl9u6cqjjcw9sxclz9mzkkxfwhh9zyek8ldgqfm0lqmpz4rxtj3nh9zmg4zzqw4syfh";
+
+  CommandBuilder::new(format!("wallet send --fee-rate 1 {address} 1btc --dry-run"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!(
+      "error: sending to MWEB address {address} does not support --dry-run\n"
+    ))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!("wallet send --fee-rate 1 {address} 1btc"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Send>();
+
+  assert_eq!(core.mempool().len(), 1);
+
+  let tx = &core.mempool()[0];
+
+  assert_eq!(tx.txid(), output.txid);
+  assert_eq!(tx.output[0].value, COIN_VALUE);
+  assert!(tx
+    .input
+    .iter()
+    .all(|txin| txin.previous_output.txid != reveal));
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!("/inscription/{inscription}"),
+    format!(".*<dt>location</dt>.*{reveal}:0:0</a></dd>.*"),
+  );
+}
+
 #[test]
 fn send_btc_with_fee_rate() {
   let core = mockcore::spawn();
@@ -679,6 +784,40 @@ fn send_dry_run() {
   assert_eq!(output.outgoing, Outgoing::InscriptionId(inscription));
 }
 
+#[test]
+fn send_dry_run_does_not_use_change_address() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = || {
+    let output = CommandBuilder::new(format!(
+      "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {inscription} --dry-run",
+    ))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Send>();
+
+    Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(output.psbt)
+        .unwrap(),
+    )
+    .unwrap()
+    .unsigned_tx
+  };
+
+  assert_eq!(send(), send());
+}
+
 #[test]
 fn sending_rune_that_has_not_been_etched_is_an_error() {
   let core = mockcore::builder().network(Network::Regtest).build();
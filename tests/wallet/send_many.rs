@@ -0,0 +1,189 @@
+use {super::*, ord::subcommand::wallet::send_many::Output};
+
+#[test]
+fn inscriptions_and_ltc_can_be_sent_to_many_recipients() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (a, _) = inscribe(&core, &ord);
+  let (b, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 recipients.csv")
+    .write(
+      "recipients.csv",
+      format!(
+        "address,outgoing
+ltc1qfmvk898k6jgfgp98dhsc5gvr9hpxl2ggd25ygk,{a}
+ltc1qcqgs2pps4u4yedfyl5pysdjjncs8et5u0vraeu,1btc
+ltc1qw508d6qejxtdg4y5r3zarvary0c5xw7kgmn4n9,{b}
+"
+      ),
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.recipients, 3);
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.txid(), output.txid);
+  assert_eq!(tx.output[0].value, TARGET_POSTAGE.to_sat());
+  assert_eq!(tx.output[1].value, TARGET_POSTAGE.to_sat());
+  assert_eq!(tx.output[2].value, COIN_VALUE);
+
+  core.mine_blocks(1);
+
+  for (inscription, vout) in [(a, 0), (b, 1)] {
+    let response = ord.json_request(format!("/inscription/{inscription}"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert_eq!(
+      response.json::<api::Inscription>().unwrap().satpoint,
+      SatPoint {
+        outpoint: OutPoint {
+          txid: output.txid,
+          vout,
+        },
+        offset: 0,
+      }
+    );
+  }
+}
+
+#[test]
+fn runes_can_be_sent_to_many_recipients() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etch = etch(&core, &ord, Rune(RUNE));
+
+  let output = CommandBuilder::new(
+    "--chain regtest --index-runes wallet send-many --fee-rate 1 recipients.yaml",
+  )
+  .write(
+    "recipients.yaml",
+    format!(
+      "- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  outgoing: 100:{rune}
+- address: bcrt1pyrmadgg78e38ewfv0an8c6eppk2fttv5vnuvz04yza60qau5va0saknu8k
+  outgoing: 200:{rune}
+- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  outgoing: 1btc
+",
+      rune = Rune(RUNE),
+    ),
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  core.mine_blocks(1);
+
+  let tx = core.tx_by_id(output.txid);
+
+  pretty_assert_eq!(
+    Runestone::decipher(&tx).unwrap(),
+    Artifact::Runestone(Runestone {
+      edicts: vec![
+        Edict {
+          id: etch.id,
+          amount: 100,
+          output: 0,
+        },
+        Edict {
+          id: etch.id,
+          amount: 200,
+          output: 1,
+        },
+      ],
+      pointer: Some(2),
+      ..default()
+    }),
+  );
+
+  assert_eq!(tx.output[3].value, COIN_VALUE);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  pretty_assert_eq!(
+    balances,
+    ord::subcommand::balances::Output {
+      runes: [(
+        SpacedRune::new(Rune(RUNE), 0),
+        [(0, 100), (1, 200), (2, 700)]
+          .into_iter()
+          .map(|(vout, amount)| {
+            (
+              OutPoint {
+                txid: output.txid,
+                vout,
+              },
+              Pile {
+                amount,
+                divisibility: 0,
+                symbol: Some('¢'),
+              },
+            )
+          })
+          .collect(),
+      )]
+      .into(),
+    }
+  );
+}
+
+#[test]
+fn sats_cannot_be_sent_to_many_recipients() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet send-many --fee-rate 1 recipients.csv")
+    .write(
+      "recipients.csv",
+      "ltc1qfmvk898k6jgfgp98dhsc5gvr9hpxl2ggd25ygk,nvtcsezkbth\n",
+    )
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(
+      "error: `ord wallet send-many` cannot send sats or satpoints, use `ord wallet send`\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn recipients_file_must_not_be_empty() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet send-many --fee-rate 1 recipients.csv")
+    .write("recipients.csv", "address,outgoing\n")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: recipients file must contain at least one recipient\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -0,0 +1,9 @@
+use super::*;
+
+#[test]
+fn sign_psbt_requires_existing_wallet() {
+  CommandBuilder::new("wallet sign-psbt cHNidP8BAAoCAAAAAAAAAAAAAA==")
+    .expected_stderr("error: wallet `ord` does not exist\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
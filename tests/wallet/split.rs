@@ -0,0 +1,139 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{sats::OutputRare, split::Output},
+};
+
+#[test]
+fn split_trims_excess_postage_from_inscriptions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe_with_custom_postage(&core, &ord, Some(50_000));
+
+  let output = CommandBuilder::new("wallet split --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output.split,
+    [OutPoint {
+      txid: reveal,
+      vout: 0
+    }]
+  );
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.txid(), output.txid);
+  assert_eq!(tx.input[0].previous_output, output.split[0]);
+  assert_eq!(tx.output[0].value, TARGET_POSTAGE.to_sat());
+  assert_eq!(tx.output[1].value, 40_000 - output.fee);
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/inscription/{inscription}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  assert_eq!(
+    response.json::<api::Inscription>().unwrap().satpoint,
+    SatPoint {
+      outpoint: OutPoint {
+        txid: output.txid,
+        vout: 0,
+      },
+      offset: 0,
+    }
+  );
+}
+
+#[test]
+fn split_skips_locked_outputs() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (_, reveal) = inscribe_with_custom_postage(&core, &ord, Some(50_000));
+
+  core.lock(OutPoint {
+    txid: reveal,
+    vout: 0,
+  });
+
+  CommandBuilder::new("wallet split --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: no outputs contain excess postage or rare sats to split off\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn split_carves_out_rare_sats() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let coinbase = core.mine_blocks(1)[0].txdata[0].txid();
+
+  let output = CommandBuilder::new("--index-sats wallet split --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output.split,
+    [OutPoint {
+      txid: coinbase,
+      vout: 0
+    }]
+  );
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.output.len(), 2);
+  assert_eq!(tx.output[0].value, TARGET_POSTAGE.to_sat());
+
+  core.mine_blocks(1);
+
+  let rare = CommandBuilder::new("--index-sats wallet sats")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<OutputRare>>();
+
+  assert!(rare.iter().any(|rare| rare.sat == 50 * COIN_VALUE
+    && rare.output
+      == OutPoint {
+        txid: output.txid,
+        vout: 0
+      }
+    && rare.offset == 0));
+}
+
+#[test]
+fn split_with_nothing_to_split_is_an_error() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  inscribe(&core, &ord);
+
+  CommandBuilder::new("wallet split --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: no outputs contain excess postage or rare sats to split off\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}